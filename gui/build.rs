@@ -1,6 +1,7 @@
 //! Build script for xero-toolkit GUI
 //!
-//! Handles resource optimization (PNG/SVG) and GLib resource compilation.
+//! Handles resource optimization (PNG/SVG), GLib resource compilation and
+//! embedding the git commit hash for the About dialog.
 
 use std::{fs, path::Path, process::Command};
 
@@ -12,6 +13,9 @@ fn main() {
     println!("cargo:rerun-if-changed=resources");
     println!("cargo:rerun-if-changed=Cargo.toml");
     println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+
+    emit_git_hash();
 
     let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set");
     let optimized_dir = Path::new(&out_dir).join("optimized_resources");
@@ -37,6 +41,21 @@ fn main() {
     );
 }
 
+/// Expose the short commit hash as `GIT_HASH`, or "unknown" outside a git checkout.
+fn emit_git_hash() {
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=GIT_HASH={}", hash);
+}
+
 #[derive(Default)]
 struct OptimizationStats {
     count: usize,
@@ -5,6 +5,8 @@ pub mod app_info {
     pub const NAME: &str = "xero-toolkit";
     pub const ID: &str = "xyz.xerolinux.xero-toolkit";
     pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+    /// Short commit hash the binary was built from, set by build.rs.
+    pub const GIT_HASH: &str = env!("GIT_HASH");
 }
 
 /// Sidebar configuration.
@@ -18,6 +20,10 @@ pub mod links {
     pub const YOUTUBE: &str = "https://www.youtube.com/@XeroLinux";
     pub const WEBSITE: &str = "https://xerolinux.xyz/";
     pub const DONATE: &str = "https://ko-fi.com/xerolinux";
+    pub const NEW_ISSUE: &str = "https://github.com/XeroLinuxDev/xero-toolkit/issues/new";
+    pub const WAYDROID_GUIDE: &str = "https://xerolinux.xyz/posts/waydroid-guide/";
+    pub const VIRTUALIZATION_GUIDE: &str =
+        "https://wiki.archlinux.org/title/KVM#Checking_support_for_KVM";
 }
 
 /// Binary paths for system executables.
@@ -34,6 +40,7 @@ pub mod paths {
     pub const SYSTEMD: &str = "/opt/xero-toolkit/sources/systemd";
     pub const DESKTOP_FILE: &str = "/usr/share/applications/xero-toolkit.desktop";
     pub const SYSTEM_AUTOSTART: &str = "/etc/xdg/autostart/xero-toolkit.desktop";
+    pub const PACMAN_DB_LOCK: &str = "/var/lib/pacman/db.lck";
 
     pub fn daemon() -> PathBuf {
         PathBuf::from(DAEMON)
@@ -99,6 +106,9 @@ pub mod resources {
         pub const DOWNLOAD: &str = "/xyz/xerolinux/xero-toolkit/ui/dialogs/download_dialog.ui";
         pub const DOWNLOAD_SETUP: &str =
             "/xyz/xerolinux/xero-toolkit/ui/dialogs/download_setup_dialog.ui";
+        pub const LOG_VIEWER: &str = "/xyz/xerolinux/xero-toolkit/ui/dialogs/log_viewer_dialog.ui";
+        pub const PACKAGE_INSTALL: &str =
+            "/xyz/xerolinux/xero-toolkit/ui/dialogs/package_install_dialog.ui";
         pub const SCHEDULER_SELECTION: &str =
             "/xyz/xerolinux/xero-toolkit/ui/dialogs/scheduler_selection_dialog.ui";
         pub const SELECTION: &str = "/xyz/xerolinux/xero-toolkit/ui/dialogs/selection_dialog.ui";
@@ -119,7 +129,9 @@ pub mod resources {
         pub const KERNEL_SCHEDULERS: &str =
             "/xyz/xerolinux/xero-toolkit/ui/tabs/kernel_schedulers.ui";
         pub const MAIN_PAGE: &str = "/xyz/xerolinux/xero-toolkit/ui/tabs/main_page.ui";
+        pub const MANGOHUD: &str = "/xyz/xerolinux/xero-toolkit/ui/tabs/mangohud.ui";
         pub const SERVICING_SYSTEM_TWEAKS: &str =
             "/xyz/xerolinux/xero-toolkit/ui/tabs/servicing_system_tweaks.ui";
+        pub const SETTINGS: &str = "/xyz/xerolinux/xero-toolkit/ui/tabs/settings.ui";
     }
 }
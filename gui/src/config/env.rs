@@ -1,23 +1,69 @@
 //! Environment variables module.
 
+use std::fmt;
+use std::path::Path;
 use std::sync::OnceLock;
 
 static ENV: OnceLock<Env> = OnceLock::new();
 
 /// Cached environment variables.
+///
+/// Both values are validated at startup, so handlers can build paths and
+/// commands from them without re-checking.
 pub struct Env {
+    /// Name of the logged in user, never empty
     pub user: String,
+    /// Absolute path to the user's home directory
     pub home: String,
 }
 
+/// Why a required environment variable can't be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvError {
+    /// The variable is not set
+    Missing(&'static str),
+    /// The variable is set but empty
+    Empty(&'static str),
+    /// The variable is not an absolute path
+    NotAbsolute(&'static str, String),
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvError::Missing(name) => write!(f, "{} environment variable is not set", name),
+            EnvError::Empty(name) => write!(f, "{} environment variable is empty", name),
+            EnvError::NotAbsolute(name, value) => write!(
+                f,
+                "{} environment variable is not an absolute path: {}",
+                name, value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EnvError {}
+
+/// Read a variable, rejecting unset and empty (or whitespace-only) values.
+fn non_empty_var(name: &'static str) -> Result<String, EnvError> {
+    let value = std::env::var(name).map_err(|_| EnvError::Missing(name))?;
+    if value.trim().is_empty() {
+        return Err(EnvError::Empty(name));
+    }
+    Ok(value)
+}
+
 impl Env {
-    fn new() -> anyhow::Result<Self> {
-        Ok(Self {
-            user: std::env::var("USER")
-                .map_err(|_| anyhow::anyhow!("USER environment variable is not set"))?,
-            home: std::env::var("HOME")
-                .map_err(|_| anyhow::anyhow!("HOME environment variable is not set"))?,
-        })
+    fn new() -> Result<Self, EnvError> {
+        let user = non_empty_var("USER")?;
+        let home = non_empty_var("HOME")?;
+
+        // A relative HOME would make paths like "$HOME/.config" resolve against the cwd
+        if !Path::new(&home).is_absolute() {
+            return Err(EnvError::NotAbsolute("HOME", home));
+        }
+
+        Ok(Self { user, home })
     }
 }
 
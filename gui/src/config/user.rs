@@ -1,13 +1,48 @@
 //! User-configurable settings stored in ~/.config/xero-toolkit/config.toml
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+thread_local! {
+    static SHARED: RefCell<Option<Rc<RefCell<Config>>>> = const { RefCell::new(None) };
+}
+
+/// Current on-disk config layout. Bump this and add a step to `migrate()`
+/// whenever a release renames or moves fields.
+pub const CONFIG_VERSION: u32 = 2;
+
+/// Number of pre-migration config backups to keep.
+const MAX_BACKUPS: usize = 5;
+
+/// File name prefix of config backups, followed by a timestamp.
+const BACKUP_PREFIX: &str = "config.toml.bak-";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
+    /// Layout version the file was written with
+    pub version: u32,
     pub general: GeneralConfig,
     pub warnings: WarningsConfig,
+    pub scx: ScxConfig,
+    pub seasonal: SeasonalConfig,
+    pub themes: ThemesConfig,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            general: GeneralConfig::default(),
+            warnings: WarningsConfig::default(),
+            scx: ScxConfig::default(),
+            seasonal: SeasonalConfig::default(),
+            themes: ThemesConfig::default(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -15,16 +50,132 @@ pub struct Config {
 pub struct GeneralConfig {
     /// Whether to launch xero-toolkit on login
     pub autostart: bool,
+    /// AUR helper to use instead of auto-detection, if still installed
+    pub preferred_aur_helper: Option<String>,
+    /// Which AUR helper steps run without `--noconfirm` so they can be reviewed
+    pub confirmation_level: ConfirmationLevel,
+    /// Page that was open when the app was last used
+    pub last_page: Option<String>,
+    /// Unmaximized window width when the app was last closed
+    pub window_width: Option<i32>,
+    /// Unmaximized window height when the app was last closed
+    pub window_height: Option<i32>,
+    /// Whether the window was maximized when the app was last closed
+    pub maximized: bool,
+    /// Whether the user hid the sidebar
+    pub sidebar_hidden: bool,
+}
+
+/// How much of the AUR helper's prompting the user wants to review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConfirmationLevel {
+    /// Pass `--noconfirm` to every step
+    #[default]
+    Silent,
+    /// Review upgrades and installs that may replace or conflict with installed packages
+    ConfirmConflicts,
+    /// Review every install and upgrade
+    AlwaysConfirm,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
 pub struct WarningsConfig {
-    /// User dismissed the "limited support on non-XeroLinux" notice
-    pub dismissed_generic_distro_notice: bool,
+    /// Distribution the "limited support on non-XeroLinux" notice was dismissed on
+    pub dismissed_generic_distro_for: Option<String>,
+    /// User skipped the confirmation before installing a kernel
+    pub dismissed_kernel_install_warning: bool,
+    /// User skipped the confirmation before stopping the SCX scheduler
+    pub dismissed_scheduler_stop_warning: bool,
     // Add future "don't show again" flags here, not as loose keys
 }
 
+/// A warning the user can silence with "don't show again", backed by a
+/// flag in [`WarningsConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DismissableWarning {
+    KernelInstall,
+    SchedulerStop,
+}
+
+impl WarningsConfig {
+    /// Get the "don't show again" flag for `warning`.
+    pub fn flag_mut(&mut self, warning: DismissableWarning) -> &mut bool {
+        match warning {
+            DismissableWarning::KernelInstall => &mut self.dismissed_kernel_install_warning,
+            DismissableWarning::SchedulerStop => &mut self.dismissed_scheduler_stop_warning,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScxConfig {
+    /// Last sched-ext scheduler the user started or switched to
+    pub scheduler: Option<String>,
+    /// Profile mode the scheduler was started with
+    pub mode: String,
+}
+
+impl Default for ScxConfig {
+    fn default() -> Self {
+        Self {
+            scheduler: None,
+            mode: "auto".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeasonalConfig {
+    /// Show the December snow effect when in season
+    pub snow: bool,
+    /// Show the October bats effect when in season
+    pub halloween: bool,
+}
+
+impl Default for SeasonalConfig {
+    fn default() -> Self {
+        Self {
+            snow: true,
+            halloween: true,
+        }
+    }
+}
+
+impl SeasonalConfig {
+    /// Look up whether an effect is enabled by its id; unknown effects are enabled.
+    pub fn is_enabled(&self, id: &str) -> bool {
+        match id {
+            "snow" => self.snow,
+            "halloween" => self.halloween,
+            _ => true,
+        }
+    }
+
+    /// Look up an effect's enabled flag by its id.
+    pub fn effect_mut(&mut self, id: &str) -> Option<&mut bool> {
+        match id {
+            "snow" => Some(&mut self.snow),
+            "halloween" => Some(&mut self.halloween),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ThemesConfig {
+    /// Layan color variant picked the last time the theme was installed
+    pub layan_color: Option<String>,
+    /// Paths created by each theme installer, so the theme can be removed again
+    pub installed_paths: BTreeMap<String, Vec<String>>,
+    /// Backup of the GRUB settings taken before a GRUB theme was installed
+    pub grub_backup: Option<String>,
+}
+
 pub fn config_path() -> PathBuf {
     dirs::config_dir()
         .unwrap_or_else(|| PathBuf::from("~/.config"))
@@ -36,9 +187,12 @@ impl Config {
     /// Load config from disk, returning defaults for any missing keys or
     /// if the file does not exist yet.
     pub fn load() -> Self {
-        let path = config_path();
+        Self::load_from(&config_path())
+    }
 
-        let content = match std::fs::read_to_string(&path) {
+    /// Load config from `path`; see [`Config::load`].
+    fn load_from(path: &Path) -> Self {
+        let content = match std::fs::read_to_string(path) {
             Ok(s) => s,
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
                 return Self::default();
@@ -49,15 +203,72 @@ impl Config {
             }
         };
 
-        match toml::from_str(&content) {
+        let mut table: toml::Table = match toml::from_str(&content) {
+            Ok(table) => table,
+            Err(e) => {
+                eprintln!("Warning: config parse error ({e}), trying backups");
+                return Self::restore_from_backup_at(path).unwrap_or_default();
+            }
+        };
+
+        // Keep a copy of the old layout so a bad migration can be rolled back
+        if table_version(&table) < CONFIG_VERSION {
+            if let Err(e) = backup_config(path) {
+                eprintln!("Warning: could not back up config before migrating ({e})");
+            }
+        }
+
+        migrate(&mut table);
+
+        match toml::Value::Table(table).try_into() {
             Ok(cfg) => cfg,
             Err(e) => {
-                eprintln!("Warning: config parse error ({e}), using defaults");
-                Self::default()
+                eprintln!("Warning: config parse error ({e}), trying backups");
+                Self::restore_from_backup_at(path).unwrap_or_default()
             }
         }
     }
 
+    /// Roll back to the newest backup that still parses, copying it over
+    /// the current config file.
+    pub fn restore_from_backup() -> Result<Self, ConfigError> {
+        Self::restore_from_backup_at(&config_path())
+    }
+
+    /// Roll back the config file at `path`; see [`Config::restore_from_backup`].
+    fn restore_from_backup_at(path: &Path) -> Result<Self, ConfigError> {
+        for backup in backups(path) {
+            let Ok(content) = std::fs::read_to_string(&backup) else {
+                continue;
+            };
+            let Ok(mut table) = toml::from_str::<toml::Table>(&content) else {
+                continue;
+            };
+            migrate(&mut table);
+            let Ok(cfg) = toml::Value::Table(table).try_into::<Self>() else {
+                continue;
+            };
+
+            std::fs::copy(&backup, path).map_err(ConfigError::Io)?;
+            eprintln!("Restored config from {}", backup.display());
+            return Ok(cfg);
+        }
+
+        Err(ConfigError::NoBackup)
+    }
+
+    /// Restore every setting to its default value.
+    ///
+    /// Records of installed themes describe the system rather than a
+    /// preference, so they are kept to allow removing the themes later.
+    pub fn reset(&mut self) {
+        let installed_paths = std::mem::take(&mut self.themes.installed_paths);
+        let grub_backup = self.themes.grub_backup.take();
+        *self = Self::default();
+        self.themes.installed_paths = installed_paths;
+        self.themes.grub_backup = grub_backup;
+    }
+
     /// Atomically write config to disk.
     /// Writes to a temp file first, then renames — avoids corruption on crash.
     pub fn save(&self) -> Result<(), ConfigError> {
@@ -81,10 +292,122 @@ impl Config {
     }
 }
 
+/// Upgrade an older on-disk layout to `CONFIG_VERSION` in place.
+///
+/// Steps run one version at a time, so a file from any older release is
+/// brought forward in sequence before it is deserialized.
+fn migrate(table: &mut toml::Table) {
+    let mut version = table_version(table);
+
+    if version > CONFIG_VERSION {
+        eprintln!(
+            "Warning: config version {version} is newer than this release ({CONFIG_VERSION}), \
+             unknown keys will be ignored"
+        );
+        return;
+    }
+
+    while version < CONFIG_VERSION {
+        match version {
+            // Files written before versioning share the version 1 layout
+            0 => {}
+            // The generic-distro dismissal is now keyed to the distribution name
+            1 => {
+                if let Some(warnings) = table.get_mut("warnings").and_then(|w| w.as_table_mut()) {
+                    let dismissed = warnings
+                        .remove("dismissed_generic_distro_notice")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    if let Some(distro) = crate::core::get_distribution_name().filter(|_| dismissed)
+                    {
+                        warnings.insert(
+                            "dismissed_generic_distro_for".to_string(),
+                            toml::Value::String(distro.to_lowercase()),
+                        );
+                    }
+                }
+            }
+            _ => unreachable!("no migration step from config version {version}"),
+        }
+        version += 1;
+    }
+
+    table.insert(
+        "version".to_string(),
+        toml::Value::Integer(i64::from(CONFIG_VERSION)),
+    );
+}
+
+/// Layout version recorded in a config table; unversioned files are version 0.
+fn table_version(table: &toml::Table) -> u32 {
+    table
+        .get("version")
+        .and_then(|v| v.as_integer())
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(0)
+}
+
+/// Copy the config file to a timestamped backup, pruning all but the
+/// newest `MAX_BACKUPS`.
+fn backup_config(path: &Path) -> std::io::Result<PathBuf> {
+    let stamp = gtk4::glib::DateTime::now_local()
+        .and_then(|now| now.format("%Y%m%d-%H%M%S"))
+        .map(|stamp| stamp.to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+    let backup = path.with_file_name(format!("{BACKUP_PREFIX}{stamp}"));
+    std::fs::copy(path, &backup)?;
+
+    for old in backups(path).into_iter().skip(MAX_BACKUPS) {
+        let _ = std::fs::remove_file(old);
+    }
+
+    Ok(backup)
+}
+
+/// List backups of the config file at `path`, newest first.
+fn backups(path: &Path) -> Vec<PathBuf> {
+    let Some(entries) = path.parent().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(BACKUP_PREFIX))
+        })
+        .collect();
+    // Timestamps sort lexically, so reverse order is newest first
+    backups.sort();
+    backups.reverse();
+    backups
+}
+
+/// Register the application's config so page handlers can reach it.
+/// Must be called at application startup, before pages are loaded.
+pub fn set_shared(config: Rc<RefCell<Config>>) {
+    SHARED.with(|shared| *shared.borrow_mut() = Some(config));
+}
+
+/// Get the application's config. Changes are persisted on shutdown.
+///
+/// Panics if not registered (call `set_shared()` at application startup).
+pub fn shared() -> Rc<RefCell<Config>> {
+    SHARED.with(|shared| {
+        shared
+            .borrow()
+            .clone()
+            .expect("Config not registered. Call config::user::set_shared() at startup.")
+    })
+}
+
 #[derive(Debug)]
 pub enum ConfigError {
     Io(std::io::Error),
     Serialize(toml::ser::Error),
+    NoBackup,
 }
 
 impl std::fmt::Display for ConfigError {
@@ -92,6 +415,156 @@ impl std::fmt::Display for ConfigError {
         match self {
             Self::Io(e) => write!(f, "IO error: {e}"),
             Self::Serialize(e) => write!(f, "Serialize error: {e}"),
+            Self::NoBackup => write!(f, "No usable config backup found"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> toml::Table {
+        toml::from_str(content).expect("test config should parse")
+    }
+
+    /// Create an empty directory for a test's config files.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "xero-toolkit-config-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_migrate_unversioned_config() {
+        let mut table = parse(
+            r#"
+            [general]
+            autostart = true
+
+            [warnings]
+            dismissed_generic_distro_notice = false
+            dismissed_kernel_install_warning = true
+            "#,
+        );
+        migrate(&mut table);
+
+        assert_eq!(table_version(&table), CONFIG_VERSION);
+        let config: Config = toml::Value::Table(table).try_into().unwrap();
+        assert!(config.general.autostart);
+        assert!(config.warnings.dismissed_kernel_install_warning);
+        assert_eq!(config.warnings.dismissed_generic_distro_for, None);
+    }
+
+    #[test]
+    fn test_migrate_v1_generic_distro_dismissal() {
+        let mut table = parse(
+            r#"
+            version = 1
+
+            [warnings]
+            dismissed_generic_distro_notice = true
+            "#,
+        );
+        migrate(&mut table);
+
+        let warnings = table["warnings"].as_table().unwrap();
+        assert!(!warnings.contains_key("dismissed_generic_distro_notice"));
+        assert_eq!(
+            warnings
+                .get("dismissed_generic_distro_for")
+                .and_then(|v| v.as_str()),
+            crate::core::get_distribution_name()
+                .map(|distro| distro.to_lowercase())
+                .as_deref()
+        );
+    }
+
+    #[test]
+    fn test_migrate_leaves_newer_config_alone() {
+        let newer = CONFIG_VERSION + 1;
+        let mut table = parse(&format!(
+            "version = {newer}\n\n[general]\nautostart = true\n"
+        ));
+        migrate(&mut table);
+
+        assert_eq!(table_version(&table), newer);
+        assert_eq!(table["general"]["autostart"].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn test_load_backs_up_config_before_migrating() {
+        let dir = test_dir("migrate-backup");
+        let path = dir.join("config.toml");
+        let old = "version = 1\n\n[general]\nautostart = true\n";
+        std::fs::write(&path, old).unwrap();
+
+        let config = Config::load_from(&path);
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert!(config.general.autostart);
+
+        let backups = backups(&path);
+        assert_eq!(backups.len(), 1);
+        assert_eq!(std::fs::read_to_string(&backups[0]).unwrap(), old);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_restores_unparsable_config_from_backup() {
+        let dir = test_dir("restore");
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "[general\nautostart = ").unwrap();
+        std::fs::write(
+            dir.join(format!("{BACKUP_PREFIX}20250101-000000")),
+            "version = 2\n\n[general]\nautostart = true\n",
+        )
+        .unwrap();
+        // The newest backup is broken too, so the older one is used
+        std::fs::write(dir.join(format!("{BACKUP_PREFIX}20250102-000000")), "[[").unwrap();
+
+        let config = Config::load_from(&path);
+        assert!(config.general.autostart);
+        assert!(toml::from_str::<toml::Table>(&std::fs::read_to_string(&path).unwrap()).is_ok());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_unparsable_config_without_backup_uses_defaults() {
+        let dir = test_dir("no-backup");
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "not toml at all [").unwrap();
+
+        assert!(matches!(
+            Config::restore_from_backup_at(&path),
+            Err(ConfigError::NoBackup)
+        ));
+        assert!(!Config::load_from(&path).general.autostart);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_backup_config_keeps_newest_backups() {
+        let dir = test_dir("prune");
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "version = 1\n").unwrap();
+        for day in 1..=MAX_BACKUPS {
+            std::fs::write(dir.join(format!("{BACKUP_PREFIX}2025010{day}-000000")), "").unwrap();
         }
+
+        let backup = backup_config(&path).unwrap();
+        let backups = backups(&path);
+        assert_eq!(backups.len(), MAX_BACKUPS);
+        assert_eq!(backups[0], backup);
+        assert!(!dir.join(format!("{BACKUP_PREFIX}20250101-000000")).exists());
+
+        std::fs::remove_dir_all(dir).unwrap();
     }
 }
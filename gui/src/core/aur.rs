@@ -1,23 +1,28 @@
 //! AUR helper detection and management.
 //!
-//! This module handles detection and access to AUR helpers (paru/yay)
-//! used for installing packages from the Arch User Repository.
+//! This module handles detection and access to AUR helpers (paru, yay,
+//! pikaur, trizen, aura) used for installing packages from the Arch User
+//! Repository, and translates commands for each helper's conventions.
 
-use log::debug;
+use log::{debug, warn};
+use std::collections::HashSet;
 use std::env;
 use std::os::unix::fs::PermissionsExt;
 use std::path::PathBuf;
-use std::sync::OnceLock;
+use std::sync::{Arc, Mutex};
 
-/// Global storage for the detected AUR helper.
-static AUR_HELPER: OnceLock<String> = OnceLock::new();
+/// Global storage for the active AUR helper.
+static AUR_HELPER: Mutex<Option<&'static str>> = Mutex::new(None);
+
+/// Cached names of the sync repository packages, used to split aura installs.
+static SYNC_PACKAGES: Mutex<Option<Arc<HashSet<String>>>> = Mutex::new(None);
 
 /// Priority order for AUR helper detection.
-const AUR_HELPERS: [&str; 2] = ["paru", "yay"];
+pub const AUR_HELPERS: [&str; 5] = ["paru", "yay", "pikaur", "trizen", "aura"];
 
 /// Detect and return the available AUR helper.
 ///
-/// Searches for AUR helpers in priority order (paru, then yay).
+/// Searches for AUR helpers in priority order (paru, yay, pikaur, trizen, aura).
 /// Returns the first found helper or None if none are available.
 pub fn detect() -> Option<&'static str> {
     AUR_HELPERS
@@ -37,24 +42,235 @@ pub fn detect() -> Option<&'static str> {
         })
 }
 
+/// List all supported AUR helpers that are installed, in priority order.
+pub fn installed_helpers() -> Vec<&'static str> {
+    AUR_HELPERS
+        .iter()
+        .copied()
+        .filter(|helper| is_executable_in_path(helper))
+        .collect()
+}
+
 /// Initialize the global AUR helper.
 ///
-/// Should be called once at startup after dependency checks pass.
+/// Uses `preferred` if it is a supported helper that is still installed,
+/// otherwise falls back to auto-detection. Can be called again to switch
+/// helpers at runtime.
 /// Returns true if an AUR helper was found and initialized.
-pub fn init() -> bool {
-    if let Some(helper) = detect() {
-        let _ = AUR_HELPER.set(helper.to_string());
-        true
-    } else {
-        false
+pub fn init(preferred: Option<&str>) -> bool {
+    let preferred = preferred.and_then(|name| {
+        let helper = AUR_HELPERS
+            .iter()
+            .copied()
+            .find(|&helper| helper == name && is_executable_in_path(helper));
+        if helper.is_none() {
+            warn!(
+                "Preferred AUR helper '{}' is not available, falling back to auto-detection",
+                name
+            );
+        }
+        helper
+    });
+
+    let helper = preferred.or_else(detect);
+    *AUR_HELPER.lock().unwrap() = helper;
+    if helper == Some("aura") {
+        refresh_sync_packages();
     }
+    helper.is_some()
 }
 
 /// Get the initialized AUR helper.
 ///
 /// Returns None if no helper has been initialized.
 pub fn get() -> Option<&'static str> {
-    AUR_HELPER.get().map(String::as_str)
+    *AUR_HELPER.lock().unwrap()
+}
+
+/// Build the program and arguments to run `args` through `helper`.
+///
+/// Commands are written with pacman-style arguments (`-S --needed pkg`), so
+/// this adapts them to each helper:
+/// - paru and yay are told to escalate through xero-auth with `--sudo`.
+/// - pikaur and trizen call `sudo` from PATH, which resolves to the xero-auth
+///   shim injected by the task runner.
+/// - aura only installs AUR packages with `-A` and forwards `-S` to pacman, so
+///   installs are split into a repo and an AUR part.
+pub fn build_command(helper: &str, args: &[String], auth_path: &str) -> (String, Vec<String>) {
+    match helper {
+        "paru" | "yay" => {
+            let mut full_args = Vec::with_capacity(args.len() + 2);
+            full_args.push("--sudo".to_string());
+            full_args.push(auth_path.to_string());
+            full_args.extend(args.iter().cloned());
+            (helper.to_string(), full_args)
+        }
+        "aura" => build_aura_command(args),
+        _ => (helper.to_string(), args.to_vec()),
+    }
+}
+
+/// Split an aura `-S` install into `aura -S` for repo packages and
+/// `aura -A` for AUR packages, run one after the other.
+fn build_aura_command(args: &[String]) -> (String, Vec<String>) {
+    let passthrough = || ("aura".to_string(), args.to_vec());
+
+    let Some((operation, rest)) = args.split_first() else {
+        return passthrough();
+    };
+    if operation != "-S" {
+        return passthrough();
+    }
+    let Some(repo_packages) = SYNC_PACKAGES.lock().unwrap().clone() else {
+        warn!("Sync package list not loaded yet, passing the install to aura unsplit");
+        return passthrough();
+    };
+
+    let (flags, targets): (Vec<&String>, Vec<&String>) =
+        rest.iter().partition(|arg| arg.starts_with('-'));
+    let (repo_targets, aur_targets): (Vec<&String>, Vec<&String>) = targets
+        .into_iter()
+        .partition(|target| repo_packages.contains(target.as_str()));
+
+    let quote_all = |items: &[&String]| {
+        items
+            .iter()
+            .map(|item| shell_quote(item))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let flags = quote_all(&flags);
+
+    let mut steps = Vec::new();
+    if !repo_targets.is_empty() {
+        steps.push(format!("aura -S {} {}", flags, quote_all(&repo_targets)));
+    }
+    if !aur_targets.is_empty() {
+        steps.push(format!("aura -A {} {}", flags, quote_all(&aur_targets)));
+    }
+    if steps.is_empty() {
+        return passthrough();
+    }
+
+    ("sh".to_string(), vec!["-c".to_string(), steps.join(" && ")])
+}
+
+/// Quote a string for safe use in a POSIX shell command.
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Reload the cached sync package list in a background thread.
+///
+/// `pacman -Slq` takes a moment on large repositories, so it is never run on
+/// the GTK main thread where commands are resolved.
+pub fn refresh_sync_packages() {
+    std::thread::spawn(|| match sync_packages() {
+        Some(packages) => *SYNC_PACKAGES.lock().unwrap() = Some(Arc::new(packages)),
+        None => warn!("Failed to list sync repository packages"),
+    });
+}
+
+/// Get the names of all packages available in the sync repositories.
+fn sync_packages() -> Option<HashSet<String>> {
+    let output = std::process::Command::new("pacman")
+        .arg("-Slq")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Check if the pacman database is locked by another package operation.
+pub fn is_db_locked() -> bool {
+    let locked = std::path::Path::new(crate::config::paths::PACMAN_DB_LOCK).exists();
+    if locked {
+        debug!("Pacman database lock is held");
+    }
+    locked
+}
+
+/// Count the repository packages that have updates available.
+///
+/// Uses `checkupdates` from pacman-contrib, which syncs a temporary copy of
+/// the databases and so doesn't cause a partial upgrade itself. Returns None
+/// if it isn't installed or the check fails.
+pub fn pending_update_count() -> Option<usize> {
+    let output = std::process::Command::new("checkupdates").output().ok()?;
+
+    // checkupdates exits with 2 when there are no updates
+    match output.status.code() {
+        Some(0) => Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .count(),
+        ),
+        Some(2) => Some(0),
+        _ => {
+            debug!(
+                "checkupdates failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            None
+        }
+    }
+}
+
+/// Estimate the download size in bytes for installing `packages`.
+///
+/// Uses `pacman -Sp --needed` so dependencies are included and packages that
+/// are already installed are skipped. Only packages from the sync repositories
+/// can be sized; AUR packages are ignored, making this a lower bound.
+/// Returns None if the size could not be determined.
+pub fn download_size(packages: &[&str]) -> Option<u64> {
+    let repo_packages = sync_packages()?;
+
+    let targets: Vec<&str> = packages
+        .iter()
+        .copied()
+        .filter(|pkg| repo_packages.contains(*pkg))
+        .collect();
+    if targets.is_empty() {
+        debug!("No repository packages to size");
+        return None;
+    }
+
+    let output = match std::process::Command::new("pacman")
+        .args(["-Sp", "--needed", "--print-format", "%s"])
+        .args(&targets)
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            warn!(
+                "pacman -Sp failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return None;
+        }
+        Err(e) => {
+            warn!("Failed to run pacman -Sp: {}", e);
+            return None;
+        }
+    };
+
+    let total = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.trim().parse::<u64>().ok())
+        .sum();
+    debug!(
+        "Estimated download size for {} packages: {}",
+        targets.len(),
+        total
+    );
+    Some(total)
 }
 
 /// Check if a command is executable in PATH.
@@ -9,17 +9,19 @@ use std::os::unix::fs::symlink;
 use std::path::PathBuf;
 use std::process::Command;
 
+/// User config directory, falling back to `$HOME/.config` from the validated environment.
+fn config_dir() -> PathBuf {
+    dirs::config_dir().unwrap_or_else(|| PathBuf::from(&config::env::get().home).join(".config"))
+}
+
 /// Get the autostart desktop file path
 pub fn get_autostart_path() -> PathBuf {
-    let config_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("~/.config"));
-    config_dir.join("autostart").join("xero-toolkit.desktop")
+    config_dir().join("autostart").join("xero-toolkit.desktop")
 }
 
 /// Enable autostart by creating a symlink to the desktop file in autostart directory
 pub fn enable() -> Result<(), std::io::Error> {
-    let autostart_dir = dirs::config_dir()
-        .unwrap_or_else(|| PathBuf::from("~/.config"))
-        .join("autostart");
+    let autostart_dir = config_dir().join("autostart");
 
     // Create autostart directory if it doesn't exist
     fs::create_dir_all(&autostart_dir)?;
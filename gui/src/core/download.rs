@@ -13,6 +13,8 @@ pub struct DownloadState {
     pub downloaded: u64,
     pub total: u64,
     pub speed: f64, // bytes per second
+    /// Bytes already on disk when the download was resumed (0 for a fresh start)
+    pub resumed_from: u64,
 }
 
 /// Fetch the latest Arch Linux ISO information
@@ -52,6 +54,11 @@ pub async fn fetch_arch_iso_info() -> Result<(String, String)> {
 }
 
 /// Download a file with progress tracking
+///
+/// Data is written to `<dest_path>.part`, which is renamed to `dest_path` once
+/// the download completes. A leftover `.part` file from an earlier attempt is
+/// resumed with a range request; servers without range support fall back to
+/// a clean restart. An existing file at `dest_path` is never appended to.
 pub async fn download_file<F>(
     url: String,
     dest_path: String,
@@ -73,17 +80,21 @@ where
         .build()
         .context("Failed to build HTTP client")?;
 
-    // Create file (truncate if exists)
-    let mut file = tokio::fs::File::create(&dest_path)
+    // Open in append mode so a partial file from an earlier attempt is resumed
+    let part_path = format!("{}.part", dest_path);
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&part_path)
         .await
-        .context("Failed to create destination file")?;
+        .context("Failed to open destination file")?;
 
-    let mut downloaded: u64 = 0;
+    let mut downloaded: u64 = file.metadata().await.map(|m| m.len()).unwrap_or(0);
     let mut total_size: u64 = 0;
 
     // Speed calculation variables
     let mut last_update = Instant::now();
-    let mut last_downloaded = 0u64;
+    let mut last_downloaded = downloaded;
     let mut speed_samples: Vec<f64> = Vec::with_capacity(20);
     let max_samples = 20;
 
@@ -95,12 +106,31 @@ where
         }
     }
 
+    // A partial file larger than the remote one can't be resumed
+    if total_size > 0 && downloaded > total_size {
+        info!("Existing file is larger than the download, starting over");
+        file.set_len(0).await?;
+        downloaded = 0;
+        last_downloaded = 0;
+    }
+
+    let mut resumed_from = downloaded;
+    if resumed_from > 0 {
+        info!("Found partial download of {} bytes", resumed_from);
+        progress_callback(DownloadState {
+            downloaded,
+            total: total_size,
+            speed: 0.0,
+            resumed_from,
+        });
+    }
+
     loop {
         // Check cancellation
         if cancel_flag.load(Ordering::Relaxed) {
             info!("Download cancelled");
             drop(file);
-            let _ = tokio::fs::remove_file(&dest_path).await;
+            let _ = tokio::fs::remove_file(&part_path).await;
             anyhow::bail!("Download cancelled");
         }
 
@@ -126,6 +156,18 @@ where
 
         match response_result {
             Ok(response) => {
+                let status = response.status();
+
+                // Servers without range support answer with the whole file
+                // (200 instead of 206), so start over from an empty file.
+                if downloaded > 0 && status == reqwest::StatusCode::OK {
+                    info!("Server does not support range requests, restarting download");
+                    file.set_len(0).await?;
+                    downloaded = 0;
+                    last_downloaded = 0;
+                    resumed_from = 0;
+                }
+
                 // Update total_size if we didn't have it
                 if total_size == 0 {
                     if let Some(len) = response.content_length() {
@@ -134,14 +176,21 @@ where
                     }
                 }
 
-                let status = response.status();
                 if !status.is_success() {
                     info!("Request failed with status: {}", status);
-                    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE
-                        && total_size > 0
-                        && downloaded >= total_size
-                    {
-                        break;
+                    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && downloaded > 0 {
+                        if total_size > 0 && downloaded >= total_size {
+                            break;
+                        }
+                        info!(
+                            "Cannot resume from byte {}, restarting download",
+                            downloaded
+                        );
+                        file.set_len(0).await?;
+                        downloaded = 0;
+                        last_downloaded = 0;
+                        resumed_from = 0;
+                        continue;
                     }
                     tokio::time::sleep(Duration::from_secs(2)).await;
                     continue;
@@ -154,7 +203,7 @@ where
                     if cancel_flag.load(Ordering::Relaxed) {
                         info!("Download cancelled");
                         drop(file);
-                        let _ = tokio::fs::remove_file(&dest_path).await;
+                        let _ = tokio::fs::remove_file(&part_path).await;
                         anyhow::bail!("Download cancelled");
                     }
 
@@ -190,6 +239,7 @@ where
                                     downloaded,
                                     total: total_size,
                                     speed: avg_speed,
+                                    resumed_from,
                                 };
 
                                 progress_callback(state);
@@ -228,11 +278,16 @@ where
     file.flush().await?;
     drop(file);
 
+    tokio::fs::rename(&part_path, &dest_path)
+        .await
+        .context("Failed to move finished download into place")?;
+
     // Final update
     let state = DownloadState {
         downloaded,
         total: total_size,
         speed: 0.0,
+        resumed_from,
     };
     progress_callback(state);
 
@@ -240,6 +295,120 @@ where
     Ok(())
 }
 
+/// Outcome of verifying a downloaded file's SHA256 checksum
+#[derive(Clone, Debug)]
+pub struct ChecksumResult {
+    /// Hash computed from the file on disk
+    pub computed: String,
+    /// Hash published by the mirror, if one could be found
+    pub expected: Option<String>,
+}
+
+impl ChecksumResult {
+    /// Whether the computed hash matches the published one (`None` if unknown)
+    pub fn matches(&self) -> Option<bool> {
+        self.expected
+            .as_deref()
+            .map(|expected| expected.eq_ignore_ascii_case(&self.computed))
+    }
+}
+
+/// Fetch the published SHA256 for a download URL.
+///
+/// Tries a `<url>.sha256` sibling first, then the `sha256sums.txt` listing
+/// in the same directory (which is what Arch mirrors provide).
+pub async fn fetch_expected_sha256(url: &str) -> Option<String> {
+    let file_name = url.rsplit('/').next()?;
+    let base_url = &url[..url.len() - file_name.len()];
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .ok()?;
+
+    for checksum_url in [
+        format!("{}.sha256", url),
+        format!("{}sha256sums.txt", base_url),
+    ] {
+        let response = match client.get(&checksum_url).send().await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                info!("No checksum at {} ({})", checksum_url, response.status());
+                continue;
+            }
+            Err(e) => {
+                info!("Failed to fetch {}: {}", checksum_url, e);
+                continue;
+            }
+        };
+
+        if let Some(hash) = response
+            .text()
+            .await
+            .ok()
+            .and_then(|listing| parse_sha256_listing(&listing, file_name))
+        {
+            info!(
+                "Found expected SHA256 for {} at {}",
+                file_name, checksum_url
+            );
+            return Some(hash);
+        }
+    }
+
+    None
+}
+
+/// Find the hash for `file_name` in `sha256sum`-style output.
+///
+/// Lines without a filename (a bare hash) are accepted as well.
+pub fn parse_sha256_listing(listing: &str, file_name: &str) -> Option<String> {
+    listing.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        match parts.next() {
+            Some(name) if name.trim_start_matches('*') != file_name => None,
+            _ => Some(hash.to_lowercase()),
+        }
+    })
+}
+
+/// Compute the SHA256 of a file using `sha256sum`
+pub async fn compute_sha256(path: &str) -> Result<String> {
+    info!("Computing SHA256 of {}", path);
+
+    let output = tokio::process::Command::new("sha256sum")
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to run sha256sum")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "sha256sum failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .split_whitespace()
+        .next()
+        .map(|hash| hash.to_lowercase())
+        .context("sha256sum produced no output")
+}
+
+/// Verify a downloaded file against the checksum published next to its URL
+pub async fn verify_sha256(url: &str, path: &str) -> Result<ChecksumResult> {
+    let expected = fetch_expected_sha256(url).await;
+    let computed = compute_sha256(path).await?;
+    Ok(ChecksumResult { computed, expected })
+}
+
 /// Format bytes to human-readable string
 pub fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
@@ -282,3 +451,37 @@ pub fn format_time_remaining(seconds: u64) -> String {
         format!("{}s", secs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "3f1d6c1e5e0b7c4f8d2a9b6e1c0f7a3d5b8e2c4a6f9d1b3e5c7a9f0b2d4e6a8c";
+
+    #[test]
+    fn test_parse_sha256_listing_finds_matching_file() {
+        let listing = format!(
+            "{}  archlinux-bootstrap-x86_64.tar.zst\n{}  archlinux-2025.01.01-x86_64.iso\n",
+            "0".repeat(64),
+            HASH
+        );
+        assert_eq!(
+            parse_sha256_listing(&listing, "archlinux-2025.01.01-x86_64.iso"),
+            Some(HASH.to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_sha256_listing_accepts_bare_hash() {
+        assert_eq!(
+            parse_sha256_listing(&format!("{}\n", HASH.to_uppercase()), "any.iso"),
+            Some(HASH.to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_sha256_listing_rejects_other_files() {
+        let listing = format!("{} *other.iso\nnot-a-hash  any.iso\n", HASH);
+        assert_eq!(parse_sha256_listing(&listing, "any.iso"), None);
+    }
+}
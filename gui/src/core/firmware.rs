@@ -0,0 +1,116 @@
+//! Device firmware updates through fwupd.
+
+use anyhow::{bail, Context, Result};
+use log::info;
+use serde_json::Value;
+use std::process::{Command, Stdio};
+
+/// Exit code `fwupdmgr` uses when there is nothing to do.
+const NOTHING_TO_DO: i32 = 2;
+
+/// A device with a firmware update available.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirmwareUpdate {
+    /// fwupd device id, passed to `fwupdmgr update`
+    pub device_id: String,
+    /// Device name shown to the user
+    pub name: String,
+    /// Firmware version currently installed
+    pub current_version: String,
+    /// Newest firmware version available
+    pub new_version: String,
+}
+
+/// Ask fwupd which devices have firmware updates. Blocks while fwupd answers.
+pub fn check_updates() -> Result<Vec<FirmwareUpdate>> {
+    let output = Command::new("fwupdmgr")
+        .args(["get-updates", "--json"])
+        .stdin(Stdio::null())
+        .output()
+        .context("Failed to run fwupdmgr, is fwupd installed?")?;
+
+    if output.status.code() == Some(NOTHING_TO_DO) {
+        info!("fwupd reports no firmware updates");
+        return Ok(Vec::new());
+    }
+    if !output.status.success() {
+        bail!(
+            "fwupdmgr failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let updates = parse_updates(&String::from_utf8_lossy(&output.stdout))?;
+    info!("fwupd reports {} firmware update(s)", updates.len());
+    Ok(updates)
+}
+
+/// Parse the output of `fwupdmgr get-updates --json`.
+///
+/// Releases are listed newest first; devices without a release are skipped.
+fn parse_updates(json: &str) -> Result<Vec<FirmwareUpdate>> {
+    let root: Value = serde_json::from_str(json).context("Invalid fwupdmgr output")?;
+    let text = |value: &Value, key: &str| value[key].as_str().unwrap_or_default().to_string();
+
+    let updates = root["Devices"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|device| {
+            let release = device["Releases"].as_array()?.first()?;
+            Some(FirmwareUpdate {
+                device_id: device["DeviceId"].as_str()?.to_string(),
+                name: text(device, "Name"),
+                current_version: text(device, "Version"),
+                new_version: text(release, "Version"),
+            })
+        })
+        .collect();
+
+    Ok(updates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_updates() {
+        let json = r#"{
+            "Devices": [
+                {
+                    "Name": "System Firmware",
+                    "DeviceId": "a45df35ac0e948ee180fe216a5f703f32dda163f",
+                    "Version": "0.1.20",
+                    "Releases": [
+                        { "Version": "0.1.23" },
+                        { "Version": "0.1.22" }
+                    ]
+                },
+                {
+                    "Name": "Up to date device",
+                    "DeviceId": "0123456789abcdef",
+                    "Version": "1.0",
+                    "Releases": []
+                }
+            ]
+        }"#;
+
+        assert_eq!(
+            parse_updates(json).unwrap(),
+            vec![FirmwareUpdate {
+                device_id: "a45df35ac0e948ee180fe216a5f703f32dda163f".to_string(),
+                name: "System Firmware".to_string(),
+                current_version: "0.1.20".to_string(),
+                new_version: "0.1.23".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_updates_without_devices() {
+        assert!(parse_updates("{}").unwrap().is_empty());
+        assert!(parse_updates("not json").is_err());
+    }
+}
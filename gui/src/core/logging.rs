@@ -0,0 +1,108 @@
+//! Application logging.
+//!
+//! Records are printed to the terminal as before and also kept in a bounded
+//! in-memory buffer, so the log viewer can show them without the app having
+//! been started from a terminal.
+
+use log::{Level, Log, Metadata, Record};
+use simple_logger::SimpleLogger;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Number of most recent records kept for the log viewer.
+const MAX_ENTRIES: usize = 2000;
+
+static BUFFER: Mutex<LogBuffer> = Mutex::new(LogBuffer {
+    entries: VecDeque::new(),
+    next_id: 0,
+});
+
+/// A captured log record.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Increasing number, used to fetch only records newer than the last seen
+    pub id: u64,
+    /// Local time the record was logged, as `HH:MM:SS`
+    pub time: String,
+    pub level: Level,
+    /// Module the record came from
+    pub target: String,
+    pub message: String,
+}
+
+impl LogEntry {
+    /// Format the entry as a single log line.
+    pub fn to_line(&self) -> String {
+        format!(
+            "{} {:<5} [{}] {}",
+            self.time, self.level, self.target, self.message
+        )
+    }
+}
+
+struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    next_id: u64,
+}
+
+/// Logger that forwards to `SimpleLogger` and keeps recent records.
+struct BufferedLogger {
+    inner: SimpleLogger,
+}
+
+impl Log for BufferedLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        self.inner.log(record);
+
+        let time = gtk4::glib::DateTime::now_local()
+            .and_then(|now| now.format("%H:%M:%S"))
+            .map(|time| time.to_string())
+            .unwrap_or_default();
+
+        // A poisoned lock only means another thread panicked mid-push
+        let mut buffer = BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+        let id = buffer.next_id;
+        buffer.next_id += 1;
+        if buffer.entries.len() == MAX_ENTRIES {
+            buffer.entries.pop_front();
+        }
+        buffer.entries.push_back(LogEntry {
+            id,
+            time,
+            level: record.level(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        });
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Install the logger. Must be called once at application startup.
+pub fn init() {
+    let inner = SimpleLogger::new();
+    let max_level = inner.max_level();
+
+    log::set_boxed_logger(Box::new(BufferedLogger { inner })).expect("Logger already initialized");
+    log::set_max_level(max_level);
+}
+
+/// Captured records with an id of at least `from_id`, oldest first.
+pub fn entries_since(from_id: u64) -> Vec<LogEntry> {
+    let buffer = BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+    buffer
+        .entries
+        .iter()
+        .filter(|entry| entry.id >= from_id)
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,174 @@
+//! Mirror ranking with reflector.
+//!
+//! Provides the countries offered for ranking and detection of the user's
+//! country from the locale, falling back to a GeoIP lookup.
+
+use anyhow::{Context, Result};
+use log::{debug, info};
+use std::time::Duration;
+
+/// Service that answers with the two-letter country code of the caller's IP.
+const GEOIP_URL: &str = "https://ipinfo.io/country";
+
+/// Countries with Arch mirrors, as (ISO 3166 code, name) pairs.
+///
+/// reflector accepts the codes for `--country`.
+pub const COUNTRIES: &[(&str, &str)] = &[
+    ("AU", "Australia"),
+    ("AT", "Austria"),
+    ("BD", "Bangladesh"),
+    ("BY", "Belarus"),
+    ("BE", "Belgium"),
+    ("BR", "Brazil"),
+    ("BG", "Bulgaria"),
+    ("CA", "Canada"),
+    ("CL", "Chile"),
+    ("CN", "China"),
+    ("CO", "Colombia"),
+    ("HR", "Croatia"),
+    ("CZ", "Czechia"),
+    ("DK", "Denmark"),
+    ("EC", "Ecuador"),
+    ("EE", "Estonia"),
+    ("FI", "Finland"),
+    ("FR", "France"),
+    ("GE", "Georgia"),
+    ("DE", "Germany"),
+    ("GR", "Greece"),
+    ("HK", "Hong Kong"),
+    ("HU", "Hungary"),
+    ("IS", "Iceland"),
+    ("IN", "India"),
+    ("ID", "Indonesia"),
+    ("IE", "Ireland"),
+    ("IL", "Israel"),
+    ("IT", "Italy"),
+    ("JP", "Japan"),
+    ("KZ", "Kazakhstan"),
+    ("KE", "Kenya"),
+    ("LV", "Latvia"),
+    ("LT", "Lithuania"),
+    ("LU", "Luxembourg"),
+    ("MY", "Malaysia"),
+    ("MX", "Mexico"),
+    ("MD", "Moldova"),
+    ("NL", "Netherlands"),
+    ("NZ", "New Zealand"),
+    ("MK", "North Macedonia"),
+    ("NO", "Norway"),
+    ("PK", "Pakistan"),
+    ("PY", "Paraguay"),
+    ("PL", "Poland"),
+    ("PT", "Portugal"),
+    ("RO", "Romania"),
+    ("RU", "Russia"),
+    ("RS", "Serbia"),
+    ("SG", "Singapore"),
+    ("SK", "Slovakia"),
+    ("SI", "Slovenia"),
+    ("ZA", "South Africa"),
+    ("KR", "South Korea"),
+    ("ES", "Spain"),
+    ("SE", "Sweden"),
+    ("CH", "Switzerland"),
+    ("TW", "Taiwan"),
+    ("TH", "Thailand"),
+    ("TR", "Turkey"),
+    ("UA", "Ukraine"),
+    ("GB", "United Kingdom"),
+    ("US", "United States"),
+    ("UZ", "Uzbekistan"),
+    ("VN", "Vietnam"),
+];
+
+/// Detect the user's country code, trying the locale before GeoIP.
+///
+/// Blocks while the GeoIP service answers. Returns None when neither knows
+/// a country from [`COUNTRIES`].
+pub fn detect_country() -> Option<&'static str> {
+    if let Some(code) = locale_country() {
+        info!("Detected country {} from locale", code);
+        return Some(code);
+    }
+
+    let runtime = tokio::runtime::Runtime::new().ok()?;
+    match runtime.block_on(geoip_country()) {
+        Ok(code) => {
+            let known = find_country(&code);
+            info!(
+                "GeoIP reports country {} (known: {})",
+                code,
+                known.is_some()
+            );
+            known
+        }
+        Err(e) => {
+            debug!("GeoIP lookup failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Country from the first locale variable that names one.
+fn locale_country() -> Option<&'static str> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|locale| country_from_locale(&locale))
+}
+
+/// Extract the country from a locale like `en_GB.UTF-8@euro`.
+fn country_from_locale(locale: &str) -> Option<&'static str> {
+    let territory = locale
+        .split(['.', '@'])
+        .next()?
+        .split_once('_')
+        .map(|(_, territory)| territory)?;
+    find_country(territory)
+}
+
+/// Look up a country code (case-insensitively) in [`COUNTRIES`].
+fn find_country(code: &str) -> Option<&'static str> {
+    COUNTRIES
+        .iter()
+        .map(|(known, _)| *known)
+        .find(|known| known.eq_ignore_ascii_case(code.trim()))
+}
+
+/// Ask the GeoIP service for the caller's country code.
+async fn geoip_country() -> Result<String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .context("Failed to build HTTP client")?;
+
+    let code = client
+        .get(GEOIP_URL)
+        .send()
+        .await
+        .context("Failed to reach GeoIP service")?
+        .error_for_status()
+        .context("GeoIP service returned an error")?
+        .text()
+        .await
+        .context("Failed to read GeoIP response")?;
+
+    Ok(code.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_country_from_locale() {
+        assert_eq!(country_from_locale("en_GB.UTF-8"), Some("GB"));
+        assert_eq!(country_from_locale("de_DE@euro"), Some("DE"));
+        assert_eq!(country_from_locale("pt_BR"), Some("BR"));
+        assert_eq!(country_from_locale("C.UTF-8"), None);
+        assert_eq!(country_from_locale("POSIX"), None);
+        // Territories without Arch mirrors aren't offered
+        assert_eq!(country_from_locale("en_AQ.UTF-8"), None);
+    }
+}
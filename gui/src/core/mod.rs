@@ -4,6 +4,9 @@
 //! - `aur`: AUR helper detection and management
 //! - `daemon`: Daemon management for xero-auth
 //! - `download`: File download functionality
+//! - `firmware`: Device firmware updates through fwupd
+//! - `logging`: Logger that keeps recent records for the log viewer
+//! - `mirrors`: Mirror ranking and country detection
 //! - `package`: Package and flatpak checking utilities
 //! - `system_check`: System dependency and distribution validation
 
@@ -11,10 +14,21 @@ pub mod aur;
 pub mod autostart;
 pub mod daemon;
 pub mod download;
+pub mod firmware;
+pub mod logging;
+pub mod mirrors;
 pub mod package;
 pub mod system_check;
 
 // Re-export commonly used items
 pub use aur::get as aur_helper;
-pub use package::{is_flatpak_installed, is_package_installed};
-pub use system_check::{check_dependencies, get_distribution_name, show_dependency_error_dialog};
+pub use package::{
+    are_packages_installed, is_flatpak_installed, is_package_installed, local_installed_size,
+    missing_packages, orphan_packages, pacman_cache_size, repo_installed_size,
+};
+pub use system_check::{
+    check_dependencies_async, detect_active_firewall, detect_camera, detect_cpu_virtualization,
+    detect_display_manager, detect_gpu_vendors, get_distribution_name, has_fingerprint_reader,
+    is_asus_hardware, is_flatpak_available, show_dependency_error_dialog, CameraKind,
+    CpuVirtualization, Firewall, GpuVendor,
+};
@@ -4,8 +4,11 @@
 //! flatpaks, and system operations.
 
 use super::aur;
-use anyhow::Result;
-use log::debug;
+use anyhow::{Context, Result};
+use gtk4::gio;
+use log::{debug, warn};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime};
 
 /// Check if a package is installed using AUR helper or pacman.
 pub fn is_package_installed(package: &str) -> bool {
@@ -86,11 +89,422 @@ pub fn is_flatpak_installed(package: &str) -> bool {
     installed
 }
 
-/// Open a URL in the default browser.
+/// Check many packages and/or flatpaks at once.
+///
+/// Runs a single `pacman -Q` and `flatpak list` instead of one process per
+/// name. Pacman package names and flatpak app ids are looked up in the same
+/// set, so both kinds can be mixed in `packages`.
+pub fn are_packages_installed(packages: &[&str]) -> HashMap<String, bool> {
+    debug!("Checking {} packages in one batch", packages.len());
+
+    let mut installed = HashSet::new();
+
+    if let Ok(output) = std::process::Command::new("pacman").arg("-Q").output() {
+        if output.status.success() {
+            installed.extend(
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter_map(|line| line.split_whitespace().next())
+                    .map(str::to_string),
+            );
+        }
+    }
+
+    if let Ok(output) = std::process::Command::new("flatpak")
+        .args(["list", "--columns=application"])
+        .output()
+    {
+        if output.status.success() {
+            installed.extend(
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .map(|line| line.trim().to_string()),
+            );
+        }
+    }
+
+    packages
+        .iter()
+        .map(|package| (package.to_string(), installed.contains(*package)))
+        .collect()
+}
+
+/// Keep the packages that `installed` doesn't report as installed.
+///
+/// Trims meta installs down to what's actually missing, so the progress log
+/// only lists packages that get installed.
+pub fn missing_packages<'a>(
+    packages: &[&'a str],
+    installed: &HashMap<String, bool>,
+) -> Vec<&'a str> {
+    packages
+        .iter()
+        .copied()
+        .filter(|package| !installed.get(*package).copied().unwrap_or(false))
+        .collect()
+}
+
+/// Query the combined installed size of repository packages, in bytes.
+///
+/// For the download size use [`super::aur::download_size`], which also
+/// accounts for dependencies and packages that are already installed.
+pub fn repo_installed_size(packages: &[&str]) -> Option<u64> {
+    query_installed_size("-Si", packages)
+}
+
+/// Query the combined installed size of locally installed packages, in bytes.
+pub fn local_installed_size(packages: &[&str]) -> Option<u64> {
+    query_installed_size("-Qi", packages)
+}
+
+/// Run `pacman <flag>` on the packages and sum the installed sizes it reports.
+fn query_installed_size(flag: &str, packages: &[&str]) -> Option<u64> {
+    // Force the C locale so field names and decimal separators are predictable
+    let output = std::process::Command::new("pacman")
+        .arg(flag)
+        .args(packages)
+        .env("LC_ALL", "C")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        debug!(
+            "pacman {} failed: {}",
+            flag,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(parse_installed_size(&stdout))
+}
+
+/// Sum the "Installed Size" fields of `pacman -Si/-Qi` output.
+fn parse_installed_size(info: &str) -> u64 {
+    info.lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(key, _)| key.trim() == "Installed Size")
+        .filter_map(|(_, value)| parse_pacman_size(value))
+        .sum()
+}
+
+/// Parse a pacman size such as "12.34 MiB" into bytes.
+fn parse_pacman_size(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let number: f64 = parts.next()?.replace(',', ".").parse().ok()?;
+    let multiplier = match parts.next()? {
+        "B" => 1u64,
+        "KiB" => 1 << 10,
+        "MiB" => 1 << 20,
+        "GiB" => 1 << 30,
+        "TiB" => 1 << 40,
+        _ => return None,
+    };
+
+    (number >= 0.0).then(|| (number * multiplier as f64).round() as u64)
+}
+
+/// Installed packages that installing `packages` would conflict with or replace.
+///
+/// Returns None when a package isn't in the repositories (e.g. AUR packages),
+/// since its conflicts can't be looked up without network access.
+pub fn installed_conflicts(packages: &[&str]) -> Option<Vec<String>> {
+    if packages.is_empty() {
+        return Some(Vec::new());
+    }
+
+    // Force the C locale so field names are predictable
+    let output = std::process::Command::new("pacman")
+        .arg("-Si")
+        .args(packages)
+        .env("LC_ALL", "C")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        debug!(
+            "pacman -Si failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+
+    let candidates: Vec<String> = parse_conflicts(&String::from_utf8_lossy(&output.stdout))
+        .into_iter()
+        .filter(|name| !packages.contains(&name.as_str()))
+        .collect();
+    if candidates.is_empty() {
+        return Some(Vec::new());
+    }
+
+    // -Qq prints the installed ones and exits with 1 if any aren't installed
+    let output = std::process::Command::new("pacman")
+        .arg("-Qq")
+        .args(&candidates)
+        .output()
+        .ok()?;
+
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Collect the package names in the "Conflicts With" and "Replaces" fields
+/// of `pacman -Si` output, without version constraints.
+fn parse_conflicts(info: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut in_field = false;
+
+    for line in info.lines() {
+        // Long values wrap onto indented continuation lines
+        let value = if line.starts_with(char::is_whitespace) {
+            if !in_field {
+                continue;
+            }
+            line
+        } else {
+            let Some((key, value)) = line.split_once(':') else {
+                in_field = false;
+                continue;
+            };
+            in_field = matches!(key.trim(), "Conflicts With" | "Replaces");
+            if !in_field {
+                continue;
+            }
+            value
+        };
+
+        names.extend(
+            value
+                .split_whitespace()
+                .filter(|dep| *dep != "None")
+                .filter_map(|dep| dep.split(['<', '>', '=']).next())
+                .map(str::to_string),
+        );
+    }
+
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// A package found by a repository search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageMatch {
+    /// Repository the package comes from, e.g. "extra"
+    pub repo: String,
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub installed: bool,
+}
+
+/// Check that a string is a valid pacman package name.
+///
+/// Package names may only contain lowercase alphanumerics and `@._+-`, and
+/// may not start with a hyphen or dot. Rejecting anything else also keeps
+/// user input from being read as a command-line option.
+pub fn is_valid_package_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(['-', '.'])
+        && name.chars().all(|c| {
+            c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '@' | '.' | '_' | '+' | '-')
+        })
+}
+
+/// Search the sync databases with `pacman -Ss`.
+///
+/// Results are ordered with the exact match first, then names starting with
+/// the query, then everything else.
+pub fn search_packages(query: &str) -> Vec<PackageMatch> {
+    if !is_valid_package_name(query) {
+        return Vec::new();
+    }
+
+    let output = match std::process::Command::new("pacman")
+        .args(["-Ss", query])
+        .env("LC_ALL", "C")
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            debug!("Failed to run pacman -Ss: {}", e);
+            return Vec::new();
+        }
+    };
+
+    // pacman exits with 1 when nothing matched
+    let mut matches = parse_search_results(&String::from_utf8_lossy(&output.stdout));
+    matches.sort_by_key(|package| {
+        if package.name == query {
+            0
+        } else if package.name.starts_with(query) {
+            1
+        } else {
+            2
+        }
+    });
+    matches
+}
+
+/// Parse `pacman -Ss` output into package matches.
+fn parse_search_results(output: &str) -> Vec<PackageMatch> {
+    let mut matches: Vec<PackageMatch> = Vec::new();
+
+    for line in output.lines() {
+        if line.starts_with(char::is_whitespace) {
+            // Indented lines hold the description of the previous package
+            if let Some(package) = matches.last_mut() {
+                package.description = line.trim().to_string();
+            }
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let Some((repo, name)) = fields.next().and_then(|id| id.split_once('/')) else {
+            continue;
+        };
+        let version = fields.next().unwrap_or_default();
+
+        matches.push(PackageMatch {
+            repo: repo.to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+            description: String::new(),
+            installed: line.contains("[installed"),
+        });
+    }
+
+    matches
+}
+
+/// Check whether a package exists in the repositories or the AUR.
+///
+/// Asks pacman first and only falls back to the AUR helper, which needs
+/// network access, when the repositories don't have it.
+pub fn package_exists(name: &str) -> bool {
+    if !is_valid_package_name(name) {
+        return false;
+    }
+
+    let found_with = |program: &str| {
+        std::process::Command::new(program)
+            .args(["-Si", name])
+            .output()
+            .is_ok_and(|output| output.status.success())
+    };
+
+    if found_with("pacman") {
+        debug!("Package '{}' found in the repositories", name);
+        return true;
+    }
+
+    match aur::get().or_else(aur::detect) {
+        Some(helper) if found_with(helper) => {
+            debug!("Package '{}' found via {}", name, helper);
+            true
+        }
+        _ => {
+            debug!("Package '{}' not found", name);
+            false
+        }
+    }
+}
+
+/// Directory pacman keeps downloaded packages in.
+const PACMAN_CACHE_DIR: &str = "/var/cache/pacman/pkg";
+
+/// Total size of the packages in pacman's cache, in bytes.
+///
+/// Unreadable entries are skipped, so this is 0 when the cache is missing.
+pub fn pacman_cache_size() -> u64 {
+    let Ok(entries) = std::fs::read_dir(PACMAN_CACHE_DIR) else {
+        warn!("Couldn't read {}", PACMAN_CACHE_DIR);
+        return 0;
+    };
+
+    entries
+        .filter_map(|entry| entry.ok()?.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Packages installed as dependencies that nothing depends on anymore.
+pub fn orphan_packages() -> Vec<String> {
+    // pacman exits with 1 and prints nothing when there are no orphans
+    let output = match std::process::Command::new("pacman").arg("-Qtdq").output() {
+        Ok(output) => output,
+        Err(e) => {
+            warn!("Failed to list orphaned packages: {}", e);
+            return Vec::new();
+        }
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Keyring file shipped by the `archlinux-keyring` package.
+const ARCH_KEYRING: &str = "/usr/share/pacman/keyrings/archlinux.gpg";
+
+/// Age after which the installed Arch keyring is considered stale.
+///
+/// New keyrings are released every few weeks, so one this old means the
+/// system has most likely not been updated in a long time.
+const KEYRING_STALE_AFTER: Duration = Duration::from_secs(60 * 24 * 60 * 60);
+
+/// Check whether the installed Arch keyring is old enough that a system
+/// update is likely to fail on unknown or expired signing keys.
+///
+/// pacman keeps the packaged modification time, so the keyring file's mtime
+/// tracks when the installed keyring release was built. Returns None when
+/// the keyring isn't installed or its age can't be determined.
+pub fn keyring_age_if_stale() -> Option<Duration> {
+    let modified = std::fs::metadata(ARCH_KEYRING)
+        .and_then(|metadata| metadata.modified())
+        .ok()?;
+    let age = SystemTime::now().duration_since(modified).ok()?;
+
+    debug!("Arch keyring is {} days old", age.as_secs() / 86_400);
+    (age > KEYRING_STALE_AFTER).then_some(age)
+}
+
+/// Open a URL in the user's default browser.
+///
+/// Tries `xdg-open` first and falls back to GIO's default handler when it
+/// isn't installed. Fails only if neither can open the URL; a handler that
+/// `xdg-open` can't find is logged once it exits.
 pub fn open_url(url: &str) -> Result<()> {
     debug!("Opening URL: {}", url);
-    std::process::Command::new("xdg-open").arg(url).spawn()?;
-    Ok(())
+
+    match std::process::Command::new("xdg-open").arg(url).spawn() {
+        Ok(mut child) => {
+            let url = url.to_string();
+            // Reap xdg-open in the background; it may block until the browser exits
+            std::thread::spawn(move || match child.wait() {
+                Ok(status) if !status.success() => {
+                    warn!("xdg-open failed to open {} ({})", url, status)
+                }
+                Err(e) => warn!("Failed to wait for xdg-open: {}", e),
+                Ok(_) => {}
+            });
+            Ok(())
+        }
+        Err(e) => {
+            debug!("xdg-open unavailable ({}), falling back to GIO", e);
+            gio::AppInfo::launch_default_for_uri(url, None::<&gio::AppLaunchContext>)
+                .with_context(|| format!("No application available to open {}", url))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -104,4 +518,115 @@ mod tests {
             "this-package-definitely-does-not-exist-12345"
         ));
     }
+
+    #[test]
+    fn test_are_packages_installed_nonexistent() {
+        let installed = are_packages_installed(&[
+            "this-package-definitely-does-not-exist-12345",
+            "com.example.DefinitelyNotInstalled",
+        ]);
+        assert_eq!(installed.len(), 2);
+        assert!(installed.values().all(|&found| !found));
+    }
+
+    #[test]
+    fn test_missing_packages() {
+        let installed = HashMap::from([
+            ("steam".to_string(), true),
+            ("gamescope".to_string(), false),
+        ]);
+        assert_eq!(
+            missing_packages(&["steam", "gamescope", "mangohud"], &installed),
+            vec!["gamescope", "mangohud"]
+        );
+        assert!(missing_packages(&["steam"], &installed).is_empty());
+    }
+
+    #[test]
+    fn test_is_valid_package_name() {
+        assert!(is_valid_package_name("linux-zen"));
+        assert!(is_valid_package_name("lib32-gcc-libs"));
+        assert!(is_valid_package_name("gtk4.0+extras@git_1"));
+        assert!(!is_valid_package_name(""));
+        assert!(!is_valid_package_name("--overwrite"));
+        assert!(!is_valid_package_name(".hidden"));
+        assert!(!is_valid_package_name("Firefox"));
+        assert!(!is_valid_package_name("foo bar"));
+        assert!(!is_valid_package_name("foo;rm"));
+    }
+
+    #[test]
+    fn test_parse_search_results() {
+        let output = "\
+extra/firefox 131.0-1 [installed]
+    Fast, Private & Safe Web Browser
+extra/firefox-developer-edition 132.0b5-1
+    Developer Edition of the popular Firefox web browser
+multilib/lib32-foo 1.0-2 (some-group) [installed: 0.9-1]
+    Example
+";
+        let matches = parse_search_results(output);
+        assert_eq!(matches.len(), 3);
+        assert_eq!(matches[0].repo, "extra");
+        assert_eq!(matches[0].name, "firefox");
+        assert_eq!(matches[0].version, "131.0-1");
+        assert_eq!(matches[0].description, "Fast, Private & Safe Web Browser");
+        assert!(matches[0].installed);
+        assert!(!matches[1].installed);
+        assert_eq!(matches[2].name, "lib32-foo");
+        assert!(matches[2].installed);
+    }
+
+    #[test]
+    fn test_parse_pacman_size() {
+        assert_eq!(parse_pacman_size("512.00 B"), Some(512));
+        assert_eq!(parse_pacman_size(" 1.50 KiB"), Some(1536));
+        assert_eq!(parse_pacman_size("12,5 MiB"), Some(13_107_200));
+        assert_eq!(parse_pacman_size("2.00 GiB"), Some(2 << 30));
+        assert_eq!(parse_pacman_size("None"), None);
+        assert_eq!(parse_pacman_size("12.34 parsecs"), None);
+    }
+
+    #[test]
+    fn test_parse_installed_size_sums_packages() {
+        let info = "\
+Name            : linux-zen
+Download Size   : 140.00 MiB
+Installed Size  : 150.00 MiB
+Build Date      : Sat 01 Feb 2025 10:00:00 AM UTC
+
+Name            : linux-zen-headers
+Download Size   : 35.00 MiB
+Installed Size  : 200.00 MiB
+";
+        assert_eq!(parse_installed_size(info), 350 << 20);
+    }
+
+    #[test]
+    fn test_parse_conflicts() {
+        let info = "\
+Name            : pipewire-pulse
+Description     : Low-latency audio/video router and processor - PulseAudio
+                  replacement
+Conflicts With  : pulseaudio  pulseaudio-bluetooth
+Replaces        : None
+
+Name            : iptables-nft
+Conflicts With  : ebtables  iptables  iptables-legacy  arptables
+                  iptables-nft-git>=1.8
+Replaces        : ebtables
+";
+        assert_eq!(
+            parse_conflicts(info),
+            vec![
+                "arptables",
+                "ebtables",
+                "iptables",
+                "iptables-legacy",
+                "iptables-nft-git",
+                "pulseaudio",
+                "pulseaudio-bluetooth",
+            ]
+        );
+    }
 }
@@ -1,33 +1,170 @@
 //! System dependency checks and validation.
 
+use crate::ui::task_runner::{self, Command, CommandSequence};
 use crate::ui::utils::extract_widget;
+use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::{ApplicationWindow, Builder, Button, Label};
 use log::{error, info, warn};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// File the built paru package is moved to inside the build directory.
+const PARU_PACKAGE_FILE: &str = "paru-bin.pkg.tar.zst";
+
+/// Moves the package makepkg just built to `PARU_PACKAGE_FILE`, skipping the
+/// debug package.
+const PARU_STAGE_SCRIPT: &str =
+    "mv -f -- \"$(makepkg --packagelist | grep -v -- '-debug-' | head -n 1)\" paru-bin.pkg.tar.zst";
+
+/// Flathub repository definition, added as the `flathub` remote.
+const FLATHUB_REPO: &str = "https://dl.flathub.org/repo/flathub.flatpakrepo";
+
+/// A tool the toolkit can't work without.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dependency {
+    Flatpak,
+    AurHelper,
+}
+
+impl Dependency {
+    /// Every dependency checked at startup.
+    pub const ALL: [Dependency; 2] = [Dependency::Flatpak, Dependency::AurHelper];
+
+    /// Name shown to the user.
+    pub fn name(self) -> &'static str {
+        match self {
+            Dependency::Flatpak => "flatpak",
+            Dependency::AurHelper => "an AUR helper (paru, yay, pikaur, trizen or aura)",
+        }
+    }
+
+    /// How to install the dependency, as Pango markup.
+    fn install_hint(self) -> &'static str {
+        match self {
+            Dependency::Flatpak => "Install flatpak: <tt>sudo pacman -S flatpak</tt>",
+            Dependency::AurHelper => "AUR Helper repositories:\n• Paru: <a href=\"https://github.com/Morganamilo/paru\">https://github.com/Morganamilo/paru</a>\n• Yay: <a href=\"https://github.com/Jguer/yay\">https://github.com/Jguer/yay</a>",
+        }
+    }
+
+    /// Commands that install the dependency.
+    ///
+    /// Flatpak also gets the Flathub remote most installs pull from. There's
+    /// no AUR helper to build one with, so paru is built from the AUR directly
+    /// with makepkg as the user, then installed with a privileged `pacman -U`
+    /// since makepkg's own `sudo` call has no terminal to prompt on. The build
+    /// happens in the user's cache directory, which other users can't write
+    /// to, and `pacman -U` only gets the exact package file that was built.
+    fn install_commands(self) -> Vec<Command> {
+        let build_dir = paru_build_dir().to_string_lossy().into_owned();
+        let package = format!("{}/{}", build_dir, PARU_PACKAGE_FILE);
+
+        match self {
+            Dependency::Flatpak => vec![
+                Command::builder()
+                    .privileged()
+                    .program("pacman")
+                    .args(&["-S", "--needed", "--noconfirm", "flatpak"])
+                    .description("Installing flatpak...")
+                    .build(),
+                Command::builder()
+                    .privileged()
+                    .program("flatpak")
+                    .args(&["remote-add", "--if-not-exists", "flathub", FLATHUB_REPO])
+                    .description("Adding the Flathub repository...")
+                    .retries(2)
+                    .build(),
+            ],
+            Dependency::AurHelper => vec![
+                Command::builder()
+                    .privileged()
+                    .program("pacman")
+                    .args(&["-S", "--needed", "--noconfirm", "base-devel", "git"])
+                    .description("Installing build tools...")
+                    .build(),
+                Command::builder()
+                    .normal()
+                    .program("rm")
+                    .args(&["-rf", &build_dir])
+                    .description("Cleaning up previous paru build...")
+                    .build(),
+                Command::builder()
+                    .normal()
+                    .program("git")
+                    .args(&[
+                        "clone",
+                        "--depth",
+                        "1",
+                        "https://aur.archlinux.org/paru-bin.git",
+                        &build_dir,
+                    ])
+                    .description("Downloading paru from the AUR...")
+                    .retries(2)
+                    .build(),
+                Command::builder()
+                    .normal()
+                    .program("makepkg")
+                    .args(&["--noconfirm"])
+                    .cwd(&build_dir)
+                    // Keep the package here even if makepkg.conf sets PKGDEST
+                    .env("PKGDEST", &build_dir)
+                    .env("PKGEXT", ".pkg.tar.zst")
+                    .description("Building paru...")
+                    .build(),
+                Command::builder()
+                    .normal()
+                    .program("sh")
+                    .args(&["-c", PARU_STAGE_SCRIPT])
+                    .cwd(&build_dir)
+                    .env("PKGDEST", &build_dir)
+                    .env("PKGEXT", ".pkg.tar.zst")
+                    .description("Locating the built package...")
+                    .build(),
+                Command::builder()
+                    .privileged()
+                    .program("pacman")
+                    .args(&["-U", "--noconfirm", &package])
+                    .description("Installing paru...")
+                    .build(),
+                Command::builder()
+                    .normal()
+                    .program("rm")
+                    .args(&["-rf", &build_dir])
+                    .description("Removing paru build files...")
+                    .optional()
+                    .build(),
+            ],
+        }
+    }
+
+    /// Check whether the dependency is available. May block on slow mounts.
+    fn is_available(self) -> bool {
+        match self {
+            Dependency::Flatpak => is_flatpak_available(),
+            Dependency::AurHelper => check_aur_helper(),
+        }
+    }
+}
 
 /// Result of dependency check containing missing dependencies.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct DependencyCheckResult {
-    pub flatpak_missing: bool,
-    pub aur_helper_missing: bool,
+    /// Dependencies that were checked and not found
+    pub missing: Vec<Dependency>,
+    /// Dependencies whose check didn't finish before the timeout
+    pub timed_out: Vec<Dependency>,
 }
 
 impl DependencyCheckResult {
     /// Check if any dependencies are missing.
     pub fn has_missing_dependencies(&self) -> bool {
-        self.flatpak_missing || self.aur_helper_missing
+        !self.missing.is_empty()
     }
 
     /// Get list of missing dependency names.
     pub fn missing_dependencies(&self) -> Vec<&str> {
-        let mut missing = Vec::new();
-        if self.flatpak_missing {
-            missing.push("flatpak");
-        }
-        if self.aur_helper_missing {
-            missing.push("paru or yay");
-        }
-        missing
+        self.missing.iter().map(|dep| dep.name()).collect()
     }
 
     /// Generate formatted list of missing dependencies for display.
@@ -41,25 +178,16 @@ impl DependencyCheckResult {
 
     /// Generate installation hint based on missing dependencies.
     pub fn generate_install_hint(&self) -> String {
-        let mut hints = Vec::new();
-
-        if self.flatpak_missing {
-            hints.push("Install flatpak: <tt>sudo pacman -S flatpak</tt>");
-        }
-        if self.aur_helper_missing {
-            hints.push("AUR Helper repositories:\n• Paru: <a href=\"https://github.com/Morganamilo/paru\">https://github.com/Morganamilo/paru</a>\n• Yay: <a href=\"https://github.com/Jguer/yay\">https://github.com/Jguer/yay</a>");
-        }
-
-        if hints.is_empty() {
-            return String::new();
-        }
-
-        hints.join("\n\n")
+        self.missing
+            .iter()
+            .map(|dep| dep.install_hint())
+            .collect::<Vec<_>>()
+            .join("\n\n")
     }
 }
 
 /// Check if flatpak is installed and available.
-fn check_flatpak() -> bool {
+pub fn is_flatpak_available() -> bool {
     info!("Checking for flatpak availability");
     match std::process::Command::new("flatpak")
         .arg("--version")
@@ -81,28 +209,23 @@ fn check_flatpak() -> bool {
     }
 }
 
-/// Check if an AUR helper (paru or yay) is installed.
+/// Check if a supported AUR helper is installed.
 fn check_aur_helper() -> bool {
     info!("Checking for AUR helper availability");
 
-    if let Ok(output) = std::process::Command::new("paru").arg("--version").output() {
-        if output.status.success() {
-            let version = String::from_utf8_lossy(&output.stdout);
-            info!("paru found: {}", version.trim());
-            return true;
+    match super::aur::detect() {
+        Some(helper) => {
+            info!("AUR helper found: {}", helper);
+            true
         }
-    }
-
-    if let Ok(output) = std::process::Command::new("yay").arg("--version").output() {
-        if output.status.success() {
-            let version = String::from_utf8_lossy(&output.stdout);
-            info!("yay found: {}", version.trim());
-            return true;
+        None => {
+            warn!(
+                "No AUR helper ({}) found in PATH",
+                super::aur::AUR_HELPERS.join(", ")
+            );
+            false
         }
     }
-
-    warn!("No AUR helper (paru or yay) found in PATH");
-    false
 }
 
 /// Get distribution name from os-release files.
@@ -182,26 +305,422 @@ fn parse_os_release_name(content: &str) -> Option<String> {
     None
 }
 
-/// Perform all dependency checks and return results.
-pub fn check_dependencies() -> DependencyCheckResult {
-    info!("Performing system dependency checks");
+/// GPU vendors with vendor-specific drivers or tools.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuVendor {
+    Nvidia,
+    Amd,
+    Intel,
+}
+
+impl GpuVendor {
+    /// Map a PCI vendor id (e.g. `0x10de`) to a GPU vendor.
+    fn from_pci_vendor_id(id: &str) -> Option<Self> {
+        match id.trim().trim_start_matches("0x").to_lowercase().as_str() {
+            "10de" => Some(Self::Nvidia),
+            "1002" => Some(Self::Amd),
+            "8086" => Some(Self::Intel),
+            _ => None,
+        }
+    }
+
+    /// Display name of the vendor.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Nvidia => "NVIDIA",
+            Self::Amd => "AMD",
+            Self::Intel => "Intel",
+        }
+    }
+}
+
+/// Detect which GPU vendors are present.
+///
+/// Hybrid (Optimus/PRIME) laptops report every vendor found. Reads PCI
+/// devices from sysfs, falling back to `lspci` if that yields nothing.
+pub fn detect_gpu_vendors() -> Vec<GpuVendor> {
+    let mut vendors = gpu_vendors_from_sysfs();
+    if vendors.is_empty() {
+        vendors = gpu_vendors_from_lspci();
+    }
+
+    let names: Vec<&str> = vendors.iter().map(|vendor| vendor.name()).collect();
+    info!("Detected GPU vendors: {}", names.join(", "));
+    vendors
+}
+
+/// Collect GPU vendors from display-class PCI devices in sysfs.
+fn gpu_vendors_from_sysfs() -> Vec<GpuVendor> {
+    use std::fs;
+
+    let Ok(entries) = fs::read_dir("/sys/bus/pci/devices") else {
+        return Vec::new();
+    };
+
+    let mut devices: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+    devices.sort();
+
+    let mut vendors = Vec::new();
+    for device in devices {
+        // PCI class 0x03xxxx covers VGA, 3D and other display controllers
+        let is_display = fs::read_to_string(device.join("class"))
+            .map(|class| class.trim().starts_with("0x03"))
+            .unwrap_or(false);
+        if !is_display {
+            continue;
+        }
+
+        if let Some(vendor) = fs::read_to_string(device.join("vendor"))
+            .ok()
+            .and_then(|id| GpuVendor::from_pci_vendor_id(&id))
+        {
+            if !vendors.contains(&vendor) {
+                vendors.push(vendor);
+            }
+        }
+    }
+
+    vendors
+}
+
+/// Collect GPU vendors from `lspci -nn` output.
+fn gpu_vendors_from_lspci() -> Vec<GpuVendor> {
+    let output = match std::process::Command::new("lspci").arg("-nn").output() {
+        Ok(output) if output.status.success() => output,
+        _ => {
+            warn!("Failed to run lspci for GPU detection");
+            return Vec::new();
+        }
+    };
+
+    let Ok(re) = regex::Regex::new(r"\[([0-9a-fA-F]{4}):[0-9a-fA-F]{4}\]") else {
+        return Vec::new();
+    };
+
+    let mut vendors = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let is_display = [
+            "VGA compatible controller",
+            "3D controller",
+            "Display controller",
+        ]
+        .iter()
+        .any(|class| line.contains(class));
+        if !is_display {
+            continue;
+        }
+
+        if let Some(vendor) = re
+            .captures_iter(line)
+            .last()
+            .and_then(|caps| GpuVendor::from_pci_vendor_id(&caps[1]))
+        {
+            if !vendors.contains(&vendor) {
+                vendors.push(vendor);
+            }
+        }
+    }
+
+    vendors
+}
+
+/// Check whether the machine is made by ASUS, from its DMI vendor strings.
+///
+/// Checks both the system and board vendor, since some models only report
+/// ASUS for one of them.
+pub fn is_asus_hardware() -> bool {
+    let is_asus = ["sys_vendor", "board_vendor"].iter().any(|field| {
+        std::fs::read_to_string(format!("/sys/class/dmi/id/{}", field))
+            .is_ok_and(|vendor| vendor.to_lowercase().contains("asus"))
+    });
+
+    info!("ASUS hardware detected: {}", is_asus);
+    is_asus
+}
+
+/// CPU vendor, as far as KVM is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuVendor {
+    Intel,
+    Amd,
+}
+
+impl CpuVendor {
+    /// KVM kernel module for this vendor.
+    pub fn kvm_module(&self) -> &'static str {
+        match self {
+            Self::Intel => "kvm-intel",
+            Self::Amd => "kvm-amd",
+        }
+    }
+
+    /// Firmware name of the vendor's virtualization extension.
+    pub fn virtualization_name(&self) -> &'static str {
+        match self {
+            Self::Intel => "Intel VT-x",
+            Self::Amd => "AMD-V (SVM)",
+        }
+    }
+}
+
+/// Hardware virtualization support reported by the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuVirtualization {
+    pub vendor: Option<CpuVendor>,
+    /// Whether the `vmx`/`svm` flag is present; it is hidden when disabled in firmware
+    pub hardware_support: bool,
+}
+
+/// Detect the CPU vendor and virtualization flags from `/proc/cpuinfo`.
+pub fn detect_cpu_virtualization() -> CpuVirtualization {
+    let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+
+    let field = |name: &str| {
+        cpuinfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == name).then(|| value.trim().to_string())
+        })
+    };
+
+    let vendor = match field("vendor_id").as_deref() {
+        Some("GenuineIntel") => Some(CpuVendor::Intel),
+        Some("AuthenticAMD") => Some(CpuVendor::Amd),
+        _ => None,
+    };
+    let hardware_support = field("flags")
+        .map(|flags| {
+            flags
+                .split_whitespace()
+                .any(|flag| flag == "vmx" || flag == "svm")
+        })
+        .unwrap_or(false);
+
+    info!(
+        "CPU virtualization: vendor={:?}, hardware_support={}",
+        vendor, hardware_support
+    );
+    CpuVirtualization {
+        vendor,
+        hardware_support,
+    }
+}
+
+/// Kind of camera available for face unlock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraKind {
+    /// An infrared camera, as used by Windows Hello style face unlock
+    Infrared,
+    /// A regular webcam only
+    Webcam,
+}
+
+/// Detect the best camera available by enumerating `/dev/video*`.
+///
+/// A device counts as infrared when its V4L2 name mentions "IR" or "Infrared".
+pub fn detect_camera() -> Option<CameraKind> {
+    use std::fs;
+
+    let Ok(entries) = fs::read_dir("/dev") else {
+        return None;
+    };
+
+    let mut found = None;
+    for entry in entries.flatten() {
+        let file_name = entry.file_name();
+        let Some(device) = file_name.to_str().filter(|name| name.starts_with("video")) else {
+            continue;
+        };
+
+        let name = fs::read_to_string(format!("/sys/class/video4linux/{}/name", device))
+            .unwrap_or_default();
+        let is_infrared = name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| word.eq_ignore_ascii_case("ir") || word.eq_ignore_ascii_case("infrared"));
+
+        info!("Found video device {}: {}", device, name.trim());
+        if is_infrared {
+            return Some(CameraKind::Infrared);
+        }
+        found = Some(CameraKind::Webcam);
+    }
 
-    let flatpak_missing = !check_flatpak();
-    let aur_helper_missing = !check_aur_helper();
+    found
+}
+
+/// Systemd unit that points at the enabled display manager.
+const DISPLAY_MANAGER_UNIT: &str = "/etc/systemd/system/display-manager.service";
+
+/// Detect the enabled display manager, e.g. "sddm" or "gdm".
+///
+/// Follows the `display-manager.service` alias that `systemctl enable`
+/// creates. Returns None when no display manager is enabled.
+pub fn detect_display_manager() -> Option<String> {
+    let target = std::fs::read_link(DISPLAY_MANAGER_UNIT).ok()?;
+    let name = target.file_stem()?.to_string_lossy().into_owned();
+    info!("Enabled display manager: {}", name);
+    Some(name)
+}
+
+/// Firewall frontends the toolkit can open ports in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Firewall {
+    Firewalld,
+    Ufw,
+}
+
+impl Firewall {
+    /// Systemd unit of the firewall.
+    fn unit(&self) -> &'static str {
+        match self {
+            Self::Firewalld => "firewalld.service",
+            Self::Ufw => "ufw.service",
+        }
+    }
+
+    /// Display name of the firewall.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Firewalld => "firewalld",
+            Self::Ufw => "UFW",
+        }
+    }
+}
+
+/// Detect which firewall is running, if any.
+pub fn detect_active_firewall() -> Option<Firewall> {
+    [Firewall::Firewalld, Firewall::Ufw]
+        .into_iter()
+        .find(|firewall| {
+            std::process::Command::new("systemctl")
+                .args(["is-active", "--quiet", firewall.unit()])
+                .status()
+                .is_ok_and(|status| status.success())
+        })
+        .inspect(|firewall| info!("Active firewall: {}", firewall.name()))
+}
+
+/// USB vendor ids that only make fingerprint readers.
+const FINGERPRINT_VENDOR_IDS: &[&str] = &[
+    "06cb", // Synaptics (their touchpads are not USB)
+    "08ff", // AuthenTec
+    "10a5", // FPC
+    "138a", // Validity
+    "147e", // Upek
+    "1c7a", // LighTuning
+    "27c6", // Goodix
+    "2808", // FocalTech
+    "298d", // Next Biometrics
+];
+
+/// Check whether a fingerprint reader is present.
+///
+/// Asks fprintd over DBus when it is running, and otherwise scans USB devices
+/// for known fingerprint vendors or products named as fingerprint readers.
+pub fn has_fingerprint_reader() -> bool {
+    use std::fs;
+
+    if let Ok(output) = std::process::Command::new("gdbus")
+        .args([
+            "call",
+            "--system",
+            "--dest",
+            "net.reactivated.Fprint",
+            "--object-path",
+            "/net/reactivated/Fprint/Manager",
+            "--method",
+            "net.reactivated.Fprint.Manager.GetDevices",
+        ])
+        .output()
+    {
+        if output.status.success() {
+            let devices = String::from_utf8_lossy(&output.stdout);
+            info!("fprintd devices: {}", devices.trim());
+            return devices.contains("/net/reactivated/Fprint/Device/");
+        }
+    }
 
-    let result = DependencyCheckResult {
-        flatpak_missing,
-        aur_helper_missing,
+    let Ok(entries) = fs::read_dir("/sys/bus/usb/devices") else {
+        return false;
     };
 
-    if result.has_missing_dependencies() {
-        let issues = result.missing_dependencies();
-        error!("Issues detected: {}", issues.join(", "));
-    } else {
-        info!("All required dependencies are available");
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        let vendor = fs::read_to_string(path.join("idVendor")).unwrap_or_default();
+        let product = fs::read_to_string(path.join("product")).unwrap_or_default();
+
+        let found = FINGERPRINT_VENDOR_IDS.contains(&vendor.trim())
+            || product.to_lowercase().contains("fingerprint");
+        if found {
+            info!(
+                "Found fingerprint reader: {} ({})",
+                product.trim(),
+                vendor.trim()
+            );
+        }
+        found
+    })
+}
+
+/// How long startup waits for the dependency checks before giving up on them.
+pub const DEPENDENCY_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Check all dependencies in the background and report on the main thread.
+///
+/// Each dependency is checked on its own thread. Checks still running after
+/// `timeout` (e.g. a lookup stuck on a stalled mount) are reported as timed
+/// out instead of holding up startup, alongside whatever did finish.
+pub fn check_dependencies_async(
+    timeout: Duration,
+    on_done: impl FnOnce(DependencyCheckResult) + 'static,
+) {
+    info!("Performing system dependency checks");
+
+    let (tx, rx) = mpsc::channel::<(Dependency, bool)>();
+    for dep in Dependency::ALL {
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send((dep, dep.is_available()));
+        });
     }
+    drop(tx);
+
+    let started = Instant::now();
+    let mut pending = Dependency::ALL.to_vec();
+    let mut result = DependencyCheckResult::default();
+    let mut on_done = Some(on_done);
+    glib::timeout_add_local(Duration::from_millis(50), move || {
+        while let Ok((dep, available)) = rx.try_recv() {
+            pending.retain(|&d| d != dep);
+            if !available {
+                result.missing.push(dep);
+            }
+        }
 
-    result
+        if !pending.is_empty() && started.elapsed() < timeout {
+            return glib::ControlFlow::Continue;
+        }
+
+        result.timed_out = std::mem::take(&mut pending);
+        if !result.timed_out.is_empty() {
+            let names: Vec<&str> = result.timed_out.iter().map(|dep| dep.name()).collect();
+            warn!(
+                "Dependency checks timed out after {:?}: {}",
+                timeout,
+                names.join(", ")
+            );
+        }
+        if result.has_missing_dependencies() {
+            let issues = result.missing_dependencies();
+            error!("Issues detected: {}", issues.join(", "));
+        } else if result.timed_out.is_empty() {
+            info!("All required dependencies are available");
+        }
+
+        if let Some(on_done) = on_done.take() {
+            on_done(std::mem::take(&mut result));
+        }
+        glib::ControlFlow::Break
+    });
 }
 
 /// Show generic distribution notice dialog with "don't show again" checkbox.
@@ -236,7 +755,7 @@ pub fn show_generic_distro_notice(
             config_clone
                 .borrow_mut()
                 .warnings
-                .dismissed_generic_distro_notice = true;
+                .dismissed_generic_distro_for = Some(distro_name.clone());
             info!("User dismissed generic distro notice (in-memory); will be saved on shutdown");
         }
         notice_window_clone.close();
@@ -275,5 +794,92 @@ pub fn show_dependency_error_dialog(
         std::process::exit(1);
     });
 
+    let install_button: Button = extract_widget(&builder, "install_button");
+    let missing = check_result.missing.clone();
+    let main_window_clone = main_window.clone();
+    let error_window_clone = error_window.clone();
+    install_button.connect_clicked(move |_| {
+        info!("Installing missing dependencies: {:?}", missing);
+        error_window_clone.close();
+        install_missing_dependencies(&main_window_clone, &missing);
+    });
+
     error_window.present();
 }
+
+/// Where paru is built when no AUR helper is installed.
+///
+/// This is under the user's cache directory rather than `/tmp`, so no other
+/// user can swap the package before it is installed as root.
+fn paru_build_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from(&crate::config::env::get().home).join(".cache"))
+        .join("xero-toolkit")
+        .join("paru-bin")
+}
+
+/// Install the missing dependencies, then check again.
+///
+/// Shows the dependency dialog again if anything is still missing, otherwise
+/// initializes the freshly installed AUR helper.
+fn install_missing_dependencies(main_window: &ApplicationWindow, missing: &[Dependency]) {
+    let mut commands = CommandSequence::new();
+    for dep in missing {
+        for command in dep.install_commands() {
+            commands = commands.then(command);
+        }
+    }
+
+    let main_window_clone = main_window.clone();
+    task_runner::run_with_on_complete(
+        main_window.upcast_ref(),
+        commands.build(),
+        "Install Missing Dependencies",
+        move |_| {
+            check_dependencies_async(DEPENDENCY_CHECK_TIMEOUT, move |result| {
+                if result.has_missing_dependencies() {
+                    show_dependency_error_dialog(&main_window_clone, &result);
+                    return;
+                }
+
+                if init_preferred_aur_helper() {
+                    info!("AUR helper initialized after installing dependencies");
+                }
+            });
+        },
+    );
+}
+
+/// Build and install paru, then use it for AUR steps.
+///
+/// Only uses the official repos and makepkg, so it works on systems without
+/// any AUR helper.
+pub fn install_aur_helper(parent: &gtk4::Window) {
+    let mut commands = CommandSequence::new();
+    for command in Dependency::AurHelper.install_commands() {
+        commands = commands.then(command);
+    }
+
+    task_runner::run_with_on_complete(parent, commands.build(), "Install AUR Helper", |success| {
+        if success && init_preferred_aur_helper() {
+            info!("AUR helper initialized after installing paru");
+        } else {
+            warn!("No AUR helper available after installing paru");
+        }
+    });
+}
+
+/// Steps that install flatpak and add the Flathub remote.
+pub fn flatpak_install_commands() -> Vec<Command> {
+    Dependency::Flatpak.install_commands()
+}
+
+/// Initialize the AUR helper, honouring the user's preferred helper.
+fn init_preferred_aur_helper() -> bool {
+    let preferred = crate::config::user::shared()
+        .borrow()
+        .general
+        .preferred_aur_helper
+        .clone();
+    super::aur::init(preferred.as_deref())
+}
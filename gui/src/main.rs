@@ -9,7 +9,7 @@ mod core;
 mod ui;
 
 fn main() {
-    simple_logger::SimpleLogger::new().init().unwrap();
+    core::logging::init();
 
     info!(
         "Starting {} v{}",
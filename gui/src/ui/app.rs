@@ -6,7 +6,7 @@ use crate::core;
 use crate::ui::context::AppContext;
 use crate::ui::context::UiComponents;
 use crate::ui::navigation;
-use crate::ui::utils::extract_widget;
+use crate::ui::utils::{disable_action_buttons, extract_widget};
 use adw::prelude::*;
 use adw::Application;
 use gtk4::glib;
@@ -22,6 +22,7 @@ pub fn setup_application_ui(app: &Application) {
     setup_resources_and_theme();
 
     let config = Rc::new(RefCell::new(Config::load()));
+    config::user::set_shared(Rc::clone(&config));
     info!("User configuration loaded");
 
     // Persist configuration once on application shutdown to avoid IO during interaction.
@@ -47,7 +48,7 @@ pub fn setup_application_ui(app: &Application) {
         crate::ui::dialogs::error::show_error(
             &window,
             &format!(
-                "Failed to initialize environment variables: {}\n\nRequired environment variables (USER, HOME) are not set.",
+                "Failed to initialize environment variables: {}\n\nUSER and HOME must be set to your user name and the absolute path of your home directory.",
                 e
             ),
         );
@@ -66,7 +67,13 @@ pub fn setup_application_ui(app: &Application) {
             );
             warn!("Some features may not work correctly on non-XeroLinux systems");
 
-            if !config.borrow().warnings.dismissed_generic_distro_notice {
+            // Show again if the user has since moved to a different distribution
+            let dismissed_for = config
+                .borrow()
+                .warnings
+                .dismissed_generic_distro_for
+                .clone();
+            if dismissed_for.as_deref() != Some(distribution_name.as_str()) {
                 core::system_check::show_generic_distro_notice(
                     &window,
                     config.clone(),
@@ -81,30 +88,127 @@ pub fn setup_application_ui(app: &Application) {
     let stack = navigation::create_stack_and_tabs(&tabs_container, &builder);
 
     let ctx = setup_ui_components(&builder, stack, &window, config.clone());
-
-    info!("Setting initial view to first page");
-    if let Some(first_page) = navigation::PAGES.first() {
-        ctx.navigate_to_page(first_page.id);
+    setup_action_button_lock(&ctx.ui.stack);
+
+    // Restore the last visited page, falling back to the first one if it no longer exists
+    let last_page = config.borrow().general.last_page.clone();
+    let initial_page = last_page
+        .as_deref()
+        .and_then(|id| navigation::PAGES.iter().find(|page| page.id == id))
+        .or_else(|| navigation::PAGES.first());
+    if let Some(page) = initial_page {
+        info!("Setting initial view to page '{}'", page.id);
+        ctx.navigate_to_page(page.id);
     }
 
-    crate::ui::seasonal::apply_seasonal_effects(&window);
+    crate::ui::search::setup_global_search(&builder, &ctx);
+    setup_keyboard_shortcuts(app, &window, &builder, &ctx);
 
-    info!("Running dependency checks");
-    let dependency_result = core::check_dependencies();
-    if dependency_result.has_missing_dependencies() {
-        core::show_dependency_error_dialog(&window, &dependency_result);
-        return;
-    }
+    crate::ui::seasonal::apply_seasonal_effects(&window);
 
-    if core::aur::init() {
+    let preferred_helper = config.borrow().general.preferred_aur_helper.clone();
+    if core::aur::init(preferred_helper.as_deref()) {
         info!("AUR helper initialized successfully");
     } else {
         warn!("No AUR helper detected");
     }
 
+    info!("Running dependency checks");
+    let window_clone = window.clone();
+    core::check_dependencies_async(
+        core::system_check::DEPENDENCY_CHECK_TIMEOUT,
+        move |dependency_result| {
+            if dependency_result.has_missing_dependencies() {
+                core::show_dependency_error_dialog(&window_clone, &dependency_result);
+            }
+        },
+    );
+
     info!("Xero Toolkit application startup complete");
 }
 
+/// Disable action buttons while a task runs and restore them once it finishes.
+fn setup_action_button_lock(stack: &Stack) {
+    let stack = stack.clone();
+    let disabled = RefCell::new(Vec::new());
+    crate::ui::task_runner::connect_running_changed(move |running| {
+        if running {
+            *disabled.borrow_mut() = disable_action_buttons(stack.upcast_ref());
+        } else {
+            for button in disabled.borrow_mut().drain(..) {
+                button.set_sensitive(true);
+            }
+        }
+    });
+}
+
+/// Register Ctrl+1..9 page navigation, Ctrl+F search and Escape to close dialogs.
+fn setup_keyboard_shortcuts(
+    app: &Application,
+    window: &ApplicationWindow,
+    builder: &Builder,
+    ctx: &AppContext,
+) {
+    let navigate_action = gio::SimpleAction::new("navigate", Some(glib::VariantTy::STRING));
+    let ctx_clone = ctx.clone();
+    navigate_action.connect_activate(move |_, parameter| {
+        if let Some(page_id) = parameter.and_then(|p| p.get::<String>()) {
+            info!("Keyboard shortcut: navigating to page '{}'", page_id);
+            ctx_clone.navigate_to_page(&page_id);
+        }
+    });
+    window.add_action(&navigate_action);
+
+    for (i, page) in navigation::PAGES.iter().take(9).enumerate() {
+        app.set_accels_for_action(
+            &format!("win.navigate::{}", page.id),
+            &[&format!("<Control>{}", i + 1)],
+        );
+    }
+
+    let search_entry: gtk4::SearchEntry = extract_widget(builder, "global_search_entry");
+    let focus_search_action = gio::SimpleAction::new("focus-search", None);
+    focus_search_action.connect_activate(move |_, _| {
+        search_entry.grab_focus();
+    });
+    window.add_action(&focus_search_action);
+    app.set_accels_for_action("win.focus-search", &["<Control>f"]);
+
+    setup_escape_to_close(window);
+}
+
+/// Let Escape close any dialog window.
+///
+/// The task runner dialog swallows Escape itself while its task is running.
+fn setup_escape_to_close(main_window: &ApplicationWindow) {
+    let main_window: gtk4::Window = main_window.clone().upcast();
+    let toplevels = gtk4::Window::toplevels();
+
+    toplevels.connect_items_changed(move |model, position, _removed, added| {
+        for i in position..position + added {
+            let Some(window) = model.item(i).and_downcast::<gtk4::Window>() else {
+                continue;
+            };
+            if window == main_window {
+                continue;
+            }
+
+            let action = gtk4::CallbackAction::new(|widget, _| {
+                if let Some(window) = widget.downcast_ref::<gtk4::Window>() {
+                    window.close();
+                }
+                glib::Propagation::Stop
+            });
+            let trigger =
+                gtk4::KeyvalTrigger::new(gtk4::gdk::Key::Escape, gtk4::gdk::ModifierType::empty());
+
+            let controller = gtk4::ShortcutController::new();
+            controller.add_shortcut(gtk4::Shortcut::new(Some(trigger), Some(action)));
+            window.add_controller(controller);
+        }
+    });
+}
+
 fn setup_resources_and_theme() {
     info!("Setting up resources and theme");
 
@@ -140,9 +244,46 @@ fn create_main_window(app: &Application, builder: &Builder) -> ApplicationWindow
     window.set_icon_name(Some("xero-toolkit"));
     info!("Main application window created from UI resource");
 
+    restore_window_state(&window);
+
     window
 }
 
+/// Smallest saved window size that is restored, to recover from bogus values.
+const MIN_RESTORED_SIZE: i32 = 400;
+
+/// Apply the saved window size and maximized state, and save them again on close.
+///
+/// Position isn't restored since Wayland doesn't let clients place windows.
+fn restore_window_state(window: &ApplicationWindow) {
+    let config = config::user::shared();
+    {
+        let general = &config.borrow().general;
+        if let (Some(width), Some(height)) = (general.window_width, general.window_height) {
+            if width >= MIN_RESTORED_SIZE && height >= MIN_RESTORED_SIZE {
+                info!("Restoring window size {}x{}", width, height);
+                window.set_default_size(width, height);
+            }
+        }
+        if general.maximized {
+            window.maximize();
+        }
+    }
+
+    window.connect_close_request(move |window| {
+        // default_size tracks the unmaximized size, so it's safe to save while maximized
+        let (width, height) = window.default_size();
+
+        // Update in-memory config; actual persistence happens on app shutdown.
+        let general = &mut config.borrow_mut().general;
+        general.window_width = Some(width);
+        general.window_height = Some(height);
+        general.maximized = window.is_maximized();
+
+        glib::Propagation::Proceed
+    });
+}
+
 fn setup_ui_components(
     builder: &Builder,
     stack: Stack,
@@ -153,47 +294,27 @@ fn setup_ui_components(
     let main_split_view = extract_widget(builder, "main_split_view");
     let sidebar_toggle = extract_widget(builder, "sidebar_toggle_button");
 
-    setup_autostart_toggle(builder, config.clone());
     setup_about_button(builder, window);
-    setup_seasonal_effects_toggle(builder, window);
+    setup_logs_button(builder, window);
+    setup_seasonal_effects_toggle(builder);
 
     info!("All UI components successfully initialized from UI builder");
 
     let ui = UiComponents::new(stack, tabs_container, main_split_view, sidebar_toggle);
 
     ui.configure_sidebar(config::sidebar::MIN_WIDTH, config::sidebar::MAX_WIDTH);
+    restore_sidebar_state(&ui.main_split_view, config.clone());
 
     AppContext::new(ui, config)
 }
 
-fn setup_autostart_toggle(builder: &Builder, config: Rc<RefCell<Config>>) {
-    let switch = extract_widget::<gtk4::Switch>(builder, "switch_autostart");
-    switch.set_active(config.borrow().general.autostart);
-
-    let config_clone = Rc::clone(&config);
-    switch.connect_state_set(move |_switch, state| {
-        info!("Autostart toggle changed to: {}", state);
+/// Show or hide the sidebar as it was last left, and remember changes.
+fn restore_sidebar_state(split_view: &adw::OverlaySplitView, config: Rc<RefCell<Config>>) {
+    split_view.set_show_sidebar(!config.borrow().general.sidebar_hidden);
 
+    split_view.connect_show_sidebar_notify(move |split_view| {
         // Update in-memory config; actual persistence happens on app shutdown.
-        config_clone.borrow_mut().general.autostart = state;
-
-        let result = if state {
-            core::autostart::enable()
-        } else {
-            core::autostart::disable()
-        };
-
-        if let Err(e) = result {
-            warn!(
-                "Failed to {} autostart: {}",
-                if state { "enable" } else { "disable" },
-                e
-            );
-            // Prevent the switch from updating its state on failure
-            return glib::Propagation::Stop;
-        }
-
-        glib::Propagation::Proceed
+        config.borrow_mut().general.sidebar_hidden = !split_view.shows_sidebar();
     });
 }
 
@@ -208,13 +329,24 @@ fn setup_about_button(builder: &Builder, window: &ApplicationWindow) {
     });
 }
 
-fn setup_seasonal_effects_toggle(builder: &Builder, _window: &ApplicationWindow) {
+fn setup_logs_button(builder: &Builder, window: &ApplicationWindow) {
+    use crate::ui::dialogs::log_viewer;
+
+    let button = extract_widget::<gtk4::Button>(builder, "logs_button");
+    let window_clone = window.clone();
+    button.connect_clicked(move |_| {
+        info!("Logs button clicked");
+        log_viewer::show_log_viewer(window_clone.upcast_ref());
+    });
+}
+
+fn setup_seasonal_effects_toggle(builder: &Builder) {
     use crate::ui::seasonal;
 
     let toggle = extract_widget::<gtk4::ToggleButton>(builder, "seasonal_effects_toggle");
 
-    let has_active = seasonal::has_active_effect();
-    toggle.set_visible(has_active);
+    let in_season = seasonal::has_in_season_effect();
+    toggle.set_visible(in_season);
     toggle.set_active(seasonal::are_effects_enabled());
 
     toggle.connect_toggled(move |btn| {
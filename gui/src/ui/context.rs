@@ -5,7 +5,7 @@
 
 use crate::config::user::Config;
 use adw::prelude::*;
-use gtk4::{Box as GtkBox, Stack, ToggleButton};
+use gtk4::{Box as GtkBox, Button, Stack, ToggleButton};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -24,7 +24,22 @@ impl AppContext {
     }
 
     /// Navigate to a specific page in the stack.
+    ///
+    /// Goes through the page's tab so it gets lazy-loaded, highlighted and
+    /// remembered just like a click in the sidebar.
     pub fn navigate_to_page(&self, page_name: &str) {
+        let mut child = self.ui.tabs_container.first_child();
+        while let Some(widget) = child {
+            if widget.widget_name() == page_name {
+                if let Ok(button) = widget.downcast::<Button>() {
+                    button.emit_clicked();
+                    return;
+                }
+                break;
+            }
+            child = widget.next_sibling();
+        }
+
         self.ui.stack.set_visible_child_name(page_name);
     }
 }
@@ -33,8 +48,6 @@ impl AppContext {
 #[derive(Clone)]
 pub struct UiComponents {
     pub stack: Stack,
-    #[allow(dead_code)]
-    // Stored for potential future use, currently only used during initialization
     pub tabs_container: GtkBox,
     pub main_split_view: adw::OverlaySplitView,
     pub sidebar_toggle: ToggleButton,
@@ -1,6 +1,7 @@
 //! About dialog showing creator information and credits.
 
-use crate::core::package;
+use crate::config::constants::app_info;
+use crate::core::{self, package};
 use crate::ui::utils::extract_widget;
 use gtk4::glib;
 use gtk4::prelude::*;
@@ -35,10 +36,26 @@ pub fn show_about_dialog(parent: &Window) {
 
     let version_label = extract_widget::<Label>(&builder, "version_label");
     version_label.set_label(&format!(
-        "Version {}",
-        crate::config::constants::app_info::VERSION
+        "Version {} ({})",
+        app_info::VERSION,
+        app_info::GIT_HASH
     ));
 
+    let distro = core::get_distribution_name().unwrap_or_else(|| "Unknown".to_string());
+    let aur_helper = core::aur_helper().unwrap_or("None");
+
+    let system_info_label = extract_widget::<Label>(&builder, "system_info_label");
+    system_info_label.set_label(&format!("{}\nAUR helper: {}", distro, aur_helper));
+
+    let info = system_info(&distro, aur_helper);
+    let copy_info_button: Button = extract_widget(&builder, "copy_info_button");
+    copy_info_button.connect_clicked(move |_| {
+        if let Some(display) = gtk4::gdk::Display::default() {
+            display.clipboard().set(&info);
+            log::info!("Copied system info to clipboard");
+        }
+    });
+
     // Set dialog as transient for parent
     dialog.set_transient_for(Some(parent));
 
@@ -51,3 +68,14 @@ pub fn show_about_dialog(parent: &Window) {
     // Show the dialog
     dialog.present();
 }
+
+/// Plain-text summary of the build and system, for pasting into bug reports.
+fn system_info(distro: &str, aur_helper: &str) -> String {
+    format!(
+        "Xero Toolkit: {} ({})\nDistribution: {}\nAUR helper: {}",
+        app_info::VERSION,
+        app_info::GIT_HASH,
+        distro,
+        aur_helper
+    )
+}
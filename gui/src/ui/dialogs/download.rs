@@ -2,7 +2,7 @@
 
 use crate::core::download::{
     download_file, fetch_arch_iso_info, format_bytes, format_speed, format_time_remaining,
-    DownloadState,
+    verify_sha256, ChecksumResult, DownloadState,
 };
 use crate::ui::utils::extract_widget;
 use gtk4::glib;
@@ -204,6 +204,8 @@ fn start_download(parent: &Window, iso_name: String, download_url: String, save_
     let time_remaining_label: Label = extract_widget(&builder, "time_remaining_label");
     let pause_button: Button = extract_widget(&builder, "pause_button");
     let cancel_button: Button = extract_widget(&builder, "cancel_button");
+    let checksum_box: gtk4::Box = extract_widget(&builder, "checksum_box");
+    let checksum_label: Label = extract_widget(&builder, "checksum_label");
 
     window.set_transient_for(Some(parent));
 
@@ -247,6 +249,7 @@ fn start_download(parent: &Window, iso_name: String, download_url: String, save_
     let progress_bar_clone = progress_bar.clone();
     let speed_label_clone = speed_label.clone();
     let time_remaining_label_clone = time_remaining_label.clone();
+    let save_path_clone = save_path.clone();
 
     // Set up a timer to check for messages
     glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
@@ -261,7 +264,16 @@ fn start_download(parent: &Window, iso_name: String, download_url: String, save_
                         0.0
                     };
                     progress_bar.set_fraction(fraction);
-                    progress_bar.set_text(Some(&format!("{:.1}%", fraction * 100.0)));
+                    let progress_text = if state.resumed_from > 0 {
+                        format!(
+                            "{:.1}% (Resuming from {})",
+                            fraction * 100.0,
+                            format_bytes(state.resumed_from)
+                        )
+                    } else {
+                        format!("{:.1}%", fraction * 100.0)
+                    };
+                    progress_bar.set_text(Some(&progress_text));
 
                     // Update speed
                     speed_label.set_text(&format_speed(state.speed));
@@ -299,10 +311,53 @@ fn start_download(parent: &Window, iso_name: String, download_url: String, save_
                     speed_label_clone.set_text("-");
                     speed_label_clone.remove_css_class("success");
 
-                    time_remaining_label_clone.set_text("Completed");
-                    time_remaining_label_clone.add_css_class("success");
+                    time_remaining_label_clone.set_text("Verifying...");
+                    time_remaining_label_clone.remove_css_class("success");
 
                     pause_button_clone.set_sensitive(false);
+                    checksum_box.set_visible(true);
+                }
+                DownloadMessage::Verified(result) => {
+                    checksum_label.set_text(&result.computed);
+
+                    match result.matches() {
+                        Some(true) => {
+                            info!("Checksum verified: {}", result.computed);
+                            time_remaining_label_clone.set_text("Verified");
+                            time_remaining_label_clone.add_css_class("success");
+                        }
+                        Some(false) => {
+                            error!(
+                                "Checksum mismatch: expected {:?}, got {}",
+                                result.expected, result.computed
+                            );
+                            time_remaining_label_clone.set_text("Checksum mismatch");
+                            time_remaining_label_clone.add_css_class("error");
+                            show_checksum_mismatch_dialog(
+                                window_clone.upcast_ref(),
+                                &result,
+                                save_path_clone.clone(),
+                            );
+                        }
+                        None => {
+                            info!("No published checksum found; skipping comparison");
+                            time_remaining_label_clone.set_text("Completed (not verified)");
+                            time_remaining_label_clone.add_css_class("success");
+                        }
+                    }
+
+                    cancel_button_clone.set_label("Close");
+                    cancel_button_clone.add_css_class("suggested-action");
+
+                    return glib::ControlFlow::Break;
+                }
+                DownloadMessage::VerifyFailed(e) => {
+                    error!("Checksum verification failed: {}", e);
+                    checksum_label.set_text(&format!("Could not compute checksum: {}", e));
+
+                    time_remaining_label_clone.set_text("Completed (not verified)");
+                    time_remaining_label_clone.add_css_class("success");
+
                     cancel_button_clone.set_label("Close");
                     cancel_button_clone.add_css_class("suggested-action");
 
@@ -329,7 +384,7 @@ fn start_download(parent: &Window, iso_name: String, download_url: String, save_
             let tx_progress = tx.clone();
 
             let result = download_file(
-                download_url,
+                download_url.clone(),
                 save_path.clone(),
                 move |state: DownloadState| {
                     let _ = tx_progress.send(DownloadMessage::Progress(state));
@@ -343,6 +398,12 @@ fn start_download(parent: &Window, iso_name: String, download_url: String, save_
             match result {
                 Ok(_) => {
                     let _ = tx.send(DownloadMessage::Completed);
+
+                    let message = match verify_sha256(&download_url, &save_path).await {
+                        Ok(result) => DownloadMessage::Verified(result),
+                        Err(e) => DownloadMessage::VerifyFailed(e.to_string()),
+                    };
+                    let _ = tx.send(message);
                 }
                 Err(e) => {
                     let _ = tx.send(DownloadMessage::Error(e.to_string()));
@@ -358,9 +419,44 @@ fn start_download(parent: &Window, iso_name: String, download_url: String, save_
 enum DownloadMessage {
     Progress(DownloadState),
     Completed,
+    Verified(ChecksumResult),
+    VerifyFailed(String),
     Error(String),
 }
 
+/// Warn about a checksum mismatch and offer to delete the corrupt file
+fn show_checksum_mismatch_dialog(parent: &Window, result: &ChecksumResult, path: String) {
+    use adw::prelude::*;
+
+    let message = format!(
+        "The downloaded file does not match the published checksum and may be corrupt.\n\nExpected: {}\nActual: {}",
+        result.expected.as_deref().unwrap_or("unknown"),
+        result.computed
+    );
+
+    let dialog = adw::AlertDialog::new(Some("Checksum Mismatch"), Some(&message));
+    dialog.add_response("keep", "Keep File");
+    dialog.add_response("delete", "Delete File");
+    dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+    dialog.set_default_response(Some("delete"));
+    dialog.set_close_response("keep");
+
+    let parent_clone = parent.clone();
+    dialog.connect_response(Some("delete"), move |_, _| {
+        info!("Deleting corrupt download: {}", path);
+        if let Err(e) = std::fs::remove_file(&path) {
+            error!("Failed to delete {}: {}", path, e);
+            show_error_dialog(
+                &parent_clone,
+                "Delete Failed",
+                &format!("Could not delete {}: {}", path, e),
+            );
+        }
+    });
+
+    dialog.present(Some(parent));
+}
+
 /// Show an error dialog
 fn show_error_dialog(parent: &Window, title: &str, message: &str) {
     use adw::prelude::*;
@@ -368,5 +464,6 @@ fn show_error_dialog(parent: &Window, title: &str, message: &str) {
     let dialog = adw::AlertDialog::new(Some(title), Some(message));
     dialog.add_response("ok", "OK");
     dialog.set_default_response(Some("ok"));
+    super::error::add_copy_button(&dialog, message);
     dialog.present(Some(parent));
 }
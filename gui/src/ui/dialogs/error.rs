@@ -1,15 +1,122 @@
 //! Shared dialog helpers used across the UI pages.
 
+use crate::config;
+use crate::core;
 use adw::prelude::*;
 use adw::AlertDialog;
-use gtk4::ApplicationWindow;
+use gtk4::{glib, ApplicationWindow, Button};
+use log::{info, warn};
+
+/// Open a URL in the default browser, showing an error dialog on failure.
+pub fn open_url_or_show_error(window: &ApplicationWindow, url: &str) {
+    if let Err(e) = core::package::open_url(url) {
+        warn!("Failed to open URL {}: {}", url, e);
+        show_error(window, &format!("Could not open {}: {}", url, e));
+    }
+}
 
 /// Show an error message dialog transient for the provided window.
+///
+/// Offers a "Report Issue" button that opens a pre-filled GitHub issue and a
+/// button to copy the error text.
 pub fn show_error(window: &ApplicationWindow, message: &str) {
     let dialog = AlertDialog::builder()
         .heading("Error")
         .body(message)
         .build();
 
+    dialog.add_responses(&[("report", "Report Issue"), ("close", "Close")]);
+    dialog.set_default_response(Some("close"));
+    dialog.set_close_response("close");
+    add_copy_button(&dialog, message);
+
+    let message = message.to_string();
+    dialog.connect_response(Some("report"), move |_, _| {
+        info!("Opening issue report for error");
+        if let Err(e) = core::package::open_url(&issue_url(&message)) {
+            warn!("Failed to open issue URL: {}", e);
+        }
+    });
+
     dialog.present(Some(window));
 }
+
+/// Add a button below the message that copies `text` to the clipboard
+/// without closing the dialog.
+pub fn add_copy_button(dialog: &AlertDialog, text: &str) {
+    let button = Button::builder()
+        .label("Copy Error")
+        .icon_name("copy-symbolic")
+        .halign(gtk4::Align::Center)
+        .build();
+    button.add_css_class("flat");
+
+    let text = text.to_string();
+    button.connect_clicked(move |_| {
+        if let Some(display) = gtk4::gdk::Display::default() {
+            display.clipboard().set(&text);
+            info!("Copied error text to clipboard");
+        }
+    });
+
+    dialog.set_extra_child(Some(&button));
+}
+
+/// Longest error text put in an issue URL, in bytes. Browsers and GitHub
+/// reject URLs much past 8 KB, and the text grows when it is escaped.
+const MAX_ISSUE_ERROR_LEN: usize = 4096;
+
+/// Build a GitHub new-issue URL pre-filled with the error, distribution and
+/// app version.
+fn issue_url(message: &str) -> String {
+    let distro = core::get_distribution_name().unwrap_or_else(|| "Unknown".to_string());
+    let title = format!(
+        "Error: {}",
+        truncate_error(message.lines().next().unwrap_or_default(), 200)
+    );
+    let body = format!(
+        "**Error**\n```\n{}\n```\n\n**Distribution:** {}\n**Xero Toolkit version:** {} ({})\n\n**Steps to reproduce**\n",
+        truncate_error(message, MAX_ISSUE_ERROR_LEN),
+        distro,
+        config::app_info::VERSION,
+        config::app_info::GIT_HASH
+    );
+
+    format!(
+        "{}?title={}&body={}",
+        config::links::NEW_ISSUE,
+        glib::Uri::escape_string(&title, None, true),
+        glib::Uri::escape_string(&body, None, true)
+    )
+}
+
+/// Cut `message` to at most `max_len` bytes on a character boundary, noting
+/// that the rest was left out.
+fn truncate_error(message: &str, max_len: usize) -> String {
+    if message.len() <= max_len {
+        return message.to_string();
+    }
+
+    let mut end = max_len;
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}\n[... truncated, use Copy Error for the full text]",
+        &message[..end]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_error() {
+        assert_eq!(truncate_error("short", 10), "short");
+
+        let truncated = truncate_error("ééééé", 5);
+        assert!(truncated.starts_with("éé\n"));
+        assert!(truncated.ends_with("truncated, use Copy Error for the full text]"));
+    }
+}
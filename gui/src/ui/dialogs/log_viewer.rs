@@ -0,0 +1,109 @@
+//! Window showing the application's own log.
+//!
+//! Displays the records captured by `core::logging` and keeps appending new
+//! ones while it is open, so users can see what a button did without
+//! starting the toolkit from a terminal.
+
+use crate::core::logging::{self, LogEntry};
+use crate::ui::utils::extract_widget;
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::{Builder, Button, ScrolledWindow, TextBuffer, TextTag, TextView, Window};
+use log::{info, Level};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// How often new log records are appended.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Show the log viewer window.
+pub fn show_log_viewer(parent: &Window) {
+    info!("Opening log viewer");
+
+    let builder = Builder::from_resource(crate::config::resources::dialogs::LOG_VIEWER);
+
+    let window: adw::Window = extract_widget(&builder, "log_viewer_window");
+    let text_view: TextView = extract_widget(&builder, "log_text_view");
+    let scrolled: ScrolledWindow = extract_widget(&builder, "log_scrolled");
+    let copy_button: Button = extract_widget(&builder, "copy_logs_button");
+    let close_button: Button = extract_widget(&builder, "close_button");
+
+    window.set_transient_for(Some(parent));
+
+    let buffer = text_view.buffer();
+    add_level_tags(&buffer);
+
+    // Id of the oldest record not shown yet
+    let next_id = Rc::new(Cell::new(0));
+    append_new_entries(&buffer, &scrolled, &next_id);
+
+    let window_weak = window.downgrade();
+    glib::timeout_add_local(REFRESH_INTERVAL, move || {
+        // Stop refreshing once the window is gone
+        if window_weak.upgrade().is_none() {
+            return glib::ControlFlow::Break;
+        }
+        append_new_entries(&buffer, &scrolled, &next_id);
+        glib::ControlFlow::Continue
+    });
+
+    let text_view_clone = text_view.clone();
+    copy_button.connect_clicked(move |_| {
+        let buffer = text_view_clone.buffer();
+        let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+        if let Some(display) = gtk4::gdk::Display::default() {
+            display.clipboard().set(&text);
+            info!("Copied application log to clipboard");
+        }
+    });
+
+    let window_clone = window.clone();
+    close_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    window.present();
+}
+
+/// Register the tags used to color warnings and errors.
+fn add_level_tags(buffer: &TextBuffer) {
+    let tag_table = buffer.tag_table();
+
+    let warn_tag = TextTag::new(Some("warn"));
+    warn_tag.set_property("foreground", "rgb(255, 140, 0)");
+    tag_table.add(&warn_tag);
+
+    let error_tag = TextTag::new(Some("error"));
+    error_tag.set_property("foreground", "rgb(231, 76, 60)");
+    error_tag.set_property("weight", 700);
+    tag_table.add(&error_tag);
+}
+
+/// Append records logged since the last refresh and scroll to the newest.
+fn append_new_entries(buffer: &TextBuffer, scrolled: &ScrolledWindow, next_id: &Cell<u64>) {
+    let entries = logging::entries_since(next_id.get());
+    let Some(last) = entries.last() else {
+        return;
+    };
+    next_id.set(last.id + 1);
+
+    for entry in &entries {
+        append_entry(buffer, entry);
+    }
+
+    let adjustment = scrolled.vadjustment();
+    adjustment.set_value(adjustment.upper());
+}
+
+/// Append one record, colored by its level.
+fn append_entry(buffer: &TextBuffer, entry: &LogEntry) {
+    let line = format!("{}\n", entry.to_line());
+    let mut end = buffer.end_iter();
+
+    match entry.level {
+        Level::Error => buffer.insert_with_tags_by_name(&mut end, &line, &["error"]),
+        Level::Warn => buffer.insert_with_tags_by_name(&mut end, &line, &["warn"]),
+        _ => buffer.insert(&mut end, &line),
+    }
+}
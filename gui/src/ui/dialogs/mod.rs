@@ -5,11 +5,15 @@
 //! - `error`: Simple error message dialogs
 //! - `selection`: Multi-choice selection dialogs
 //! - `download`: ISO download dialogs
+//! - `log_viewer`: Window showing the application's own log
+//! - `package_install`: Install an arbitrary package by name
 //! - `terminal`: Interactive terminal dialogs
 
 pub mod about;
 pub mod download;
 pub mod error;
+pub mod log_viewer;
+pub mod package_install;
 pub mod selection;
 pub mod terminal;
 pub mod warning;
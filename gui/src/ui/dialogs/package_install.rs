@@ -0,0 +1,237 @@
+//! Dialog for installing an arbitrary package by name.
+//!
+//! An escape hatch for tools the toolkit has no dedicated button for. The
+//! name is autocompleted from `pacman -Ss`, checked against the repositories
+//! and the AUR, then installed through the AUR helper in a terminal so any
+//! prompts can be answered.
+
+use crate::core::package::{self, PackageMatch};
+use crate::ui::task_runner::{self, Command, CommandSequence};
+use crate::ui::utils::extract_widget;
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::{Builder, Button, Label, ListBox, SearchEntry, Window};
+use log::{info, warn};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::Duration;
+
+/// Maximum number of suggestions shown under the entry.
+const MAX_SUGGESTIONS: usize = 20;
+
+/// Minimum query length before the repositories are searched.
+const MIN_QUERY_LEN: usize = 2;
+
+/// How often background search and validation results are polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Show the "Install a Package" dialog.
+pub fn show_package_install_dialog(parent: &Window) {
+    info!("Opening package install dialog");
+
+    let builder = Builder::from_resource(crate::config::resources::dialogs::PACKAGE_INSTALL);
+
+    let window: adw::Window = extract_widget(&builder, "package_install_window");
+    let entry: SearchEntry = extract_widget(&builder, "package_entry");
+    let suggestions: ListBox = extract_widget(&builder, "suggestions_list");
+    let status_label: Label = extract_widget(&builder, "status_label");
+    let cancel_button: Button = extract_widget(&builder, "cancel_button");
+    let install_button: Button = extract_widget(&builder, "install_button");
+
+    window.set_transient_for(Some(parent));
+
+    let window_clone = window.clone();
+    cancel_button.connect_clicked(move |_| {
+        window_clone.close();
+    });
+
+    // Bumped on every search so results for an older query are dropped
+    let generation = Rc::new(Cell::new(0u32));
+
+    let suggestions_clone = suggestions.clone();
+    let status_label_clone = status_label.clone();
+    let install_button_clone = install_button.clone();
+    entry.connect_search_changed(move |entry| {
+        let query = entry.text().trim().to_string();
+        let current = generation.get().wrapping_add(1);
+        generation.set(current);
+
+        clear_suggestions(&suggestions_clone);
+        set_status(&status_label_clone, "", false);
+
+        let valid = package::is_valid_package_name(&query);
+        install_button_clone.set_sensitive(valid);
+
+        if query.is_empty() {
+            return;
+        }
+        if !valid {
+            set_status(
+                &status_label_clone,
+                "Package names may only contain lowercase letters, digits and @._+-",
+                true,
+            );
+            return;
+        }
+        if query.len() < MIN_QUERY_LEN {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel::<Vec<PackageMatch>>();
+        std::thread::spawn(move || {
+            let _ = tx.send(package::search_packages(&query));
+        });
+
+        let generation = generation.clone();
+        let suggestions = suggestions_clone.clone();
+        let entry = entry.clone();
+        glib::timeout_add_local(POLL_INTERVAL, move || match rx.try_recv() {
+            Ok(matches) => {
+                if generation.get() == current {
+                    populate_suggestions(&suggestions, &entry, &matches);
+                }
+                glib::ControlFlow::Break
+            }
+            Err(TryRecvError::Empty) => glib::ControlFlow::Continue,
+            Err(TryRecvError::Disconnected) => {
+                warn!("Package search thread disconnected");
+                glib::ControlFlow::Break
+            }
+        });
+    });
+
+    let install_button_clone = install_button.clone();
+    entry.connect_activate(move |_| {
+        if install_button_clone.is_sensitive() {
+            install_button_clone.emit_clicked();
+        }
+    });
+
+    let parent = parent.clone();
+    let window_clone = window.clone();
+    let entry_clone = entry.clone();
+    install_button.connect_clicked(move |button| {
+        let name = entry_clone.text().trim().to_string();
+        if !package::is_valid_package_name(&name) {
+            return;
+        }
+
+        button.set_sensitive(false);
+        set_status(&status_label, &format!("Looking up {}…", name), false);
+
+        let (tx, rx) = mpsc::channel::<bool>();
+        let lookup_name = name.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(package::package_exists(&lookup_name));
+        });
+
+        let button = button.clone();
+        let status_label = status_label.clone();
+        let window = window_clone.clone();
+        let parent = parent.clone();
+        glib::timeout_add_local(POLL_INTERVAL, move || {
+            let exists = match rx.try_recv() {
+                Ok(exists) => exists,
+                Err(TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                Err(TryRecvError::Disconnected) => {
+                    warn!("Package lookup thread disconnected");
+                    false
+                }
+            };
+
+            if exists {
+                window.close();
+                install_package(&parent, &name);
+            } else {
+                info!("Package '{}' not found", name);
+                set_status(
+                    &status_label,
+                    &format!(
+                        "No package named \"{}\" was found in the repositories or the AUR.",
+                        name
+                    ),
+                    true,
+                );
+                button.set_sensitive(true);
+            }
+            glib::ControlFlow::Break
+        });
+    });
+
+    window.present();
+}
+
+/// Install a single package with the AUR helper.
+///
+/// Runs interactively since an arbitrary package may conflict with installed
+/// ones or ask which provider to use.
+fn install_package(parent: &Window, name: &str) {
+    info!("Installing package '{}'", name);
+
+    let commands = CommandSequence::new()
+        .then(
+            Command::builder()
+                .aur()
+                .args(&["-S", "--needed", name])
+                .description(&format!("Installing {}...", name))
+                .interactive()
+                .build(),
+        )
+        .build();
+
+    task_runner::run(parent, commands, &format!("Install {}", name));
+}
+
+/// Fill the suggestion list; activating a row puts its name in the entry.
+fn populate_suggestions(list: &ListBox, entry: &SearchEntry, matches: &[PackageMatch]) {
+    clear_suggestions(list);
+
+    for package in matches.iter().take(MAX_SUGGESTIONS) {
+        let row = adw::ActionRow::new();
+        row.set_use_markup(false);
+        row.set_title(&package.name);
+        row.set_subtitle(&package.description);
+        row.set_subtitle_lines(1);
+
+        let details = if package.installed {
+            format!("{} · {} · installed", package.repo, package.version)
+        } else {
+            format!("{} · {}", package.repo, package.version)
+        };
+        let details_label = Label::new(Some(&details));
+        details_label.add_css_class("dim-label");
+        details_label.add_css_class("caption");
+        row.add_suffix(&details_label);
+
+        row.set_activatable(true);
+
+        let entry = entry.clone();
+        let name = package.name.clone();
+        row.connect_activated(move |_| {
+            entry.set_text(&name);
+            entry.set_position(-1);
+        });
+
+        list.append(&row);
+    }
+}
+
+/// Remove every row from the suggestion list.
+fn clear_suggestions(list: &ListBox) {
+    while let Some(row) = list.first_child() {
+        list.remove(&row);
+    }
+}
+
+/// Show a message under the suggestions, styled as an error if needed.
+fn set_status(label: &Label, text: &str, is_error: bool) {
+    label.set_text(text);
+    if is_error {
+        label.remove_css_class("dim-label");
+        label.add_css_class("error");
+    } else {
+        label.remove_css_class("error");
+        label.add_css_class("dim-label");
+    }
+}
@@ -17,6 +17,10 @@ pub struct SelectionOption {
     pub label: String,
     pub description: String,
     pub installed: bool,
+    /// Whether the option starts out selected (it stays changeable)
+    pub selected: bool,
+    /// IDs of options that must also be selected for this one to work
+    pub requires: Vec<String>,
 }
 
 impl SelectionOption {
@@ -27,8 +31,25 @@ impl SelectionOption {
             label: label.to_string(),
             description: description.to_string(),
             installed,
+            selected: false,
+            requires: Vec::new(),
         }
     }
+
+    /// Start with this option selected, e.g. to restore a previous choice.
+    pub fn selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Declare options this one depends on (multi selection only).
+    ///
+    /// Selecting this option selects its prerequisites, and deselecting a
+    /// prerequisite deselects this option.
+    pub fn requires(mut self, ids: &[&str]) -> Self {
+        self.requires = ids.iter().map(|id| id.to_string()).collect();
+        self
+    }
 }
 
 /// Selection type for the dialog
@@ -48,6 +69,7 @@ pub struct SelectionDialogConfig {
     pub confirm_label: String,
     pub selection_type: SelectionType,
     pub selection_required: bool,
+    pub bulk_toggle: bool,
 }
 
 impl SelectionDialogConfig {
@@ -60,6 +82,7 @@ impl SelectionDialogConfig {
             confirm_label: "Install".to_string(),
             selection_type: SelectionType::Multi,
             selection_required: true,
+            bulk_toggle: false,
         }
     }
 
@@ -86,6 +109,12 @@ impl SelectionDialogConfig {
         self.selection_required = required;
         self
     }
+
+    /// Show "Select All"/"Select None" buttons (multi selection only)
+    pub fn with_bulk_toggle(mut self, enabled: bool) -> Self {
+        self.bulk_toggle = enabled;
+        self
+    }
 }
 
 /// Show a selection dialog and call the callback with selected option IDs
@@ -135,7 +164,7 @@ where
         match selection_type {
             SelectionType::Multi => {
                 let checkbox = CheckButton::new();
-                checkbox.set_active(option.installed);
+                checkbox.set_active(option.installed || option.selected);
                 checkbox.set_sensitive(!option.installed);
                 checkboxes
                     .borrow_mut()
@@ -173,7 +202,7 @@ where
                     first_radio = Some(radio.clone());
                     radio
                 };
-                radio.set_active(option.installed);
+                radio.set_active(option.installed || option.selected);
                 radio.set_sensitive(!option.installed);
                 radio_buttons
                     .borrow_mut()
@@ -266,6 +295,80 @@ where
         connect_toggle_handler(radio);
     }
 
+    // Enforce dependencies between options as they are toggled
+    if selection_type == SelectionType::Multi {
+        let requirements: Rc<Vec<(String, Vec<String>)>> = Rc::new(
+            config
+                .options
+                .iter()
+                .filter(|option| !option.requires.is_empty())
+                .map(|option| (option.id.clone(), option.requires.clone()))
+                .collect(),
+        );
+
+        if !requirements.is_empty() {
+            for (id, checkbox) in checkboxes.borrow().iter() {
+                let id = id.clone();
+                let checkboxes_clone = checkboxes.clone();
+                let requirements = requirements.clone();
+
+                checkbox.connect_toggled(move |checkbox| {
+                    let find = |wanted: &str| {
+                        checkboxes_clone
+                            .borrow()
+                            .iter()
+                            .find(|(option_id, button)| {
+                                option_id == wanted && button.is_sensitive()
+                            })
+                            .map(|(_, button)| button.clone())
+                    };
+
+                    if checkbox.is_active() {
+                        // Select everything this option requires
+                        let prerequisites = requirements
+                            .iter()
+                            .filter(|(dependent, _)| *dependent == id)
+                            .flat_map(|(_, prerequisites)| prerequisites.iter());
+                        for prerequisite in prerequisites {
+                            if let Some(button) = find(prerequisite) {
+                                button.set_active(true);
+                            }
+                        }
+                    } else {
+                        // Deselect everything that requires this option
+                        let dependents = requirements
+                            .iter()
+                            .filter(|(_, prerequisites)| prerequisites.contains(&id));
+                        for (dependent, _) in dependents {
+                            if let Some(button) = find(dependent) {
+                                button.set_active(false);
+                            }
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    // Bulk toggle buttons - flip every option that isn't already installed
+    if config.bulk_toggle && selection_type == SelectionType::Multi {
+        let select_all_button: Button = extract_widget(&builder, "select_all_button");
+        let select_none_button: Button = extract_widget(&builder, "select_none_button");
+        select_all_button.set_visible(true);
+        select_none_button.set_visible(true);
+
+        for (button, active) in [(select_all_button, true), (select_none_button, false)] {
+            let checkboxes_clone = checkboxes.clone();
+            button.connect_clicked(move |_| {
+                for (_, checkbox) in checkboxes_clone.borrow().iter() {
+                    if checkbox.is_sensitive() {
+                        checkbox.set_active(active);
+                    }
+                }
+            });
+        }
+    }
+
     // Confirm button - collect selected options and call callback
     let dialog_clone = dialog.clone();
     let checkboxes_clone = checkboxes.clone();
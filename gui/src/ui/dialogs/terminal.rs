@@ -44,6 +44,20 @@ fn update_terminal_style(terminal: &Terminal) {
     terminal.set_colors(Some(&fg_color), Some(&bg_color), &palette_refs);
 }
 
+/// Copy the whole terminal buffer, scrollback included, to the clipboard.
+///
+/// Selecting everything makes VTE include rows that have scrolled out of
+/// view, which a plain visible-text grab would miss.
+fn copy_all_output(terminal: &Terminal) {
+    terminal.select_all();
+    terminal.copy_clipboard_format(vte4::Format::Text);
+    terminal.unselect_all();
+    info!("Terminal: Copied all output to clipboard");
+}
+
+/// Callback run once the terminal's process is done, with its exit status.
+type OnExit = Box<dyn FnOnce(Option<i32>)>;
+
 /// Shows an interactive terminal window for the given command.
 pub fn show_terminal_dialog(
     parent: &Window,
@@ -51,6 +65,46 @@ pub fn show_terminal_dialog(
     command: &str,
     args: &[&str],
     close_on_exit: bool,
+) {
+    open_terminal(parent, title, command, args, &[], None, close_on_exit, None);
+}
+
+/// Shows an interactive terminal window and reports when the command is done.
+///
+/// `on_exit` runs exactly once: with the exit status when the process exits,
+/// or with `None` if it failed to spawn or the window was closed first. The
+/// window closes by itself when the command succeeds.
+pub fn show_terminal_dialog_with_on_exit(
+    parent: &Window,
+    title: &str,
+    command: &str,
+    args: &[&str],
+    env: &[(String, String)],
+    cwd: Option<&str>,
+    on_exit: impl FnOnce(Option<i32>) + 'static,
+) {
+    open_terminal(
+        parent,
+        title,
+        command,
+        args,
+        env,
+        cwd,
+        true,
+        Some(Box::new(on_exit)),
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn open_terminal(
+    parent: &Window,
+    title: &str,
+    command: &str,
+    args: &[&str],
+    env: &[(String, String)],
+    cwd: Option<&str>,
+    close_on_exit: bool,
+    on_exit: Option<OnExit>,
 ) {
     // Load the UI
     let builder = Builder::from_resource(crate::config::resources::dialogs::TERMINAL);
@@ -58,10 +112,20 @@ pub fn show_terminal_dialog(
     let window: adw::Window = extract_widget(&builder, "terminal_window");
     let terminal: Terminal = extract_widget(&builder, "terminal");
     let close_button: Button = extract_widget(&builder, "close_button");
+    let copy_output_button: Button = extract_widget(&builder, "copy_output_button");
 
     window.set_transient_for(Some(parent));
     window.set_title(Some(title));
 
+    // Shared so whichever of exit, spawn failure or close comes first reports it
+    let on_exit = Rc::new(RefCell::new(on_exit));
+    let finish = Rc::new(move |status: Option<i32>| {
+        let callback = on_exit.borrow_mut().take();
+        if let Some(callback) = callback {
+            callback(status);
+        }
+    });
+
     // Set a nice monospace font
     let font_desc = gtk4::pango::FontDescription::from_string("Monospace 11");
     terminal.set_font(Some(&font_desc));
@@ -92,6 +156,19 @@ pub fn show_terminal_dialog(
         window_clone.close();
     });
 
+    // Closing the window before the process exits counts as no exit status
+    let finish_close = finish.clone();
+    window.connect_close_request(move |_| {
+        finish_close(None);
+        gtk4::glib::Propagation::Proceed
+    });
+
+    // Setup copy button
+    let terminal_copy = terminal.clone();
+    copy_output_button.connect_clicked(move |_| {
+        copy_all_output(&terminal_copy);
+    });
+
     // Spawn the command
     let mut argv = vec![command.to_string()];
     argv.extend(args.iter().map(|s| s.to_string()));
@@ -102,11 +179,16 @@ pub fn show_terminal_dialog(
     let close_button_clone = close_button.clone();
     let close_button_error = close_button.clone();
     let terminal_error = terminal.clone();
-    let env_vars: Vec<String> = std::env::vars().map(|(k, v)| format!("{k}={v}")).collect();
+    let finish_error = finish.clone();
+    let env_vars: Vec<String> = std::env::vars()
+        .filter(|(k, _)| !env.iter().any(|(key, _)| key == k))
+        .chain(env.iter().cloned())
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect();
     let env_refs: Vec<&str> = env_vars.iter().map(|s| s.as_str()).collect();
     terminal.spawn_async(
         vte4::PtyFlags::DEFAULT,
-        None,
+        cwd,
         &argv_refs,
         &env_refs,
         gtk4::glib::SpawnFlags::SEARCH_PATH,
@@ -122,6 +204,7 @@ pub fn show_terminal_dialog(
                 // Enable close button and make it blue on error
                 close_button_error.add_css_class("suggested-action");
                 close_button_error.set_sensitive(true);
+                finish_error(None);
             }
         },
     );
@@ -143,6 +226,8 @@ pub fn show_terminal_dialog(
         close_button_clone.add_css_class("suggested-action");
         close_button_clone.set_sensitive(true);
 
+        finish(Some(exit_code));
+
         if close_on_exit && exit_code == 0 {
             window_for_exit.close();
         }
@@ -1,8 +1,9 @@
 //! Warning confirmation dialog for experimental features.
 
+use crate::config::user::DismissableWarning;
 use crate::ui::utils::extract_widget;
 use gtk4::prelude::*;
-use gtk4::{Builder, Button, Label, Window};
+use gtk4::{Builder, Button, CheckButton, Label, Window};
 use log::info;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -12,6 +13,60 @@ use std::rc::Rc;
 pub fn show_warning_confirmation<F>(parent: &Window, heading: &str, message: &str, on_confirm: F)
 where
     F: FnOnce() + 'static,
+{
+    show_warning_confirmation_with_label(parent, heading, message, None, on_confirm);
+}
+
+/// Show a warning confirmation dialog with a "don't show again" checkbox.
+///
+/// If the user already dismissed `warning`, on_confirm is called right away
+/// without showing the dialog.
+pub fn show_dismissable_warning_confirmation<F>(
+    parent: &Window,
+    heading: &str,
+    message: &str,
+    warning: DismissableWarning,
+    on_confirm: F,
+) where
+    F: FnOnce() + 'static,
+{
+    let dismissed = *crate::config::user::shared()
+        .borrow_mut()
+        .warnings
+        .flag_mut(warning);
+
+    if dismissed {
+        info!("Skipping dismissed warning: {}", heading);
+        on_confirm();
+    } else {
+        show_warning_dialog(parent, heading, message, None, Some(warning), on_confirm);
+    }
+}
+
+/// Show a warning confirmation dialog with a custom label on the continue button.
+///
+/// Passing None keeps the default label from the UI file.
+pub fn show_warning_confirmation_with_label<F>(
+    parent: &Window,
+    heading: &str,
+    message: &str,
+    confirm_label: Option<&str>,
+    on_confirm: F,
+) where
+    F: FnOnce() + 'static,
+{
+    show_warning_dialog(parent, heading, message, confirm_label, None, on_confirm);
+}
+
+fn show_warning_dialog<F>(
+    parent: &Window,
+    heading: &str,
+    message: &str,
+    confirm_label: Option<&str>,
+    dismiss_warning: Option<DismissableWarning>,
+    on_confirm: F,
+) where
+    F: FnOnce() + 'static,
 {
     info!("Showing warning confirmation dialog: {}", heading);
 
@@ -29,6 +84,7 @@ where
     let warning_message: Label = extract_widget(&builder, "warning_message");
     let cancel_button: Button = extract_widget(&builder, "cancel_button");
     let continue_button: Button = extract_widget(&builder, "continue_button");
+    let dont_show_again: CheckButton = extract_widget(&builder, "dont_show_again_checkbox");
 
     // Set heading (remove emoji from heading since we have an icon now)
     heading_label.set_label(heading);
@@ -36,6 +92,12 @@ where
     // Set message with Pango markup
     warning_message.set_markup(message);
 
+    if let Some(label) = confirm_label {
+        continue_button.set_label(label);
+    }
+
+    dont_show_again.set_visible(dismiss_warning.is_some());
+
     // Setup callbacks
     let dialog_clone = dialog.clone();
     cancel_button.connect_clicked(move |_| {
@@ -48,6 +110,17 @@ where
 
     continue_button.connect_clicked(move |_| {
         info!("Warning dialog confirmed");
+        if let Some(warning) = dismiss_warning.filter(|_| dont_show_again.is_active()) {
+            // Update in-memory flag only; persistence happens on app shutdown.
+            *crate::config::user::shared()
+                .borrow_mut()
+                .warnings
+                .flag_mut(warning) = true;
+            info!(
+                "User dismissed warning {:?}; will be saved on shutdown",
+                warning
+            );
+        }
         if let Some(on_confirm) = on_confirm_rc.borrow_mut().take() {
             on_confirm();
         }
@@ -7,12 +7,14 @@
 //! - `dialogs`: Dialog windows (error, selection, download)
 //! - `task_runner`: Command execution with progress UI
 //! - `pages`: Page-specific button handlers
+//! - `search`: Global search across all tool buttons
 
 pub mod app;
 pub mod context;
 pub mod dialogs;
 pub mod navigation;
 pub mod pages;
+pub mod search;
 pub mod seasonal;
 pub mod task_runner;
 pub mod utils;
@@ -67,6 +67,13 @@ pub const PAGES: &[PageConfig] = &[
         ui_resource: crate::config::resources::tabs::GAMESCOPE,
         setup_handler: Some(pages::gamescope::setup_handlers),
     },
+    PageConfig {
+        id: "mangohud",
+        title: "MangoHud",
+        icon: "gauge-symbolic",
+        ui_resource: crate::config::resources::tabs::MANGOHUD,
+        setup_handler: Some(pages::mangohud::setup_handlers),
+    },
     PageConfig {
         id: "containers_vms",
         title: "Containers/VMs",
@@ -95,6 +102,13 @@ pub const PAGES: &[PageConfig] = &[
         ui_resource: crate::config::resources::tabs::SERVICING_SYSTEM_TWEAKS,
         setup_handler: Some(pages::servicing::setup_handlers),
     },
+    PageConfig {
+        id: "settings",
+        title: "Settings",
+        icon: "gears-symbolic",
+        ui_resource: crate::config::resources::tabs::SETTINGS,
+        setup_handler: Some(pages::settings::setup_handlers),
+    },
 ];
 
 /// Tracks which pages have been loaded or are currently loading.
@@ -274,6 +288,8 @@ impl Tab {
             .build();
 
         button.set_child(Some(&content_box));
+        // Lets AppContext find the tab for a page id
+        button.set_widget_name(page_name);
 
         Tab {
             page_name: page_name.to_string(),
@@ -297,6 +313,9 @@ impl Tab {
 
             stack_clone.set_visible_child_name(&page_name);
             update_active_tab(&tabs_clone, &button_clone);
+
+            // Update in-memory config; actual persistence happens on app shutdown.
+            crate::config::user::shared().borrow_mut().general.last_page = Some(page_name.clone());
         });
     }
 }
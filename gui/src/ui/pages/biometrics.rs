@@ -5,12 +5,15 @@
 //! - Howdy facial recognition setup (xero-howdy-qt)
 
 use crate::core;
+use crate::core::CameraKind;
 use crate::ui::task_runner::{self, Command, CommandSequence};
-use crate::ui::utils::extract_widget;
+use crate::ui::utils::try_extract_widget;
 use gtk4::prelude::*;
-use gtk4::{ApplicationWindow, Builder};
+use gtk4::{glib, ApplicationWindow, Builder};
 use log::{error, info};
 use std::process::{Command as StdCommand, Stdio};
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::Duration;
 
 /// Set up all button handlers for the biometrics page
 pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &ApplicationWindow) {
@@ -41,10 +44,16 @@ fn update_button_state(
 
 fn setup_fingerprint(page_builder: &Builder, window: &ApplicationWindow) {
     // Both buttons are expected to be defined in the UI; we will simply toggle visibility.
-    let btn_fingerprint_setup =
-        extract_widget::<gtk4::Button>(page_builder, "btn_fingerprint_setup");
-    let btn_fingerprint_uninstall =
-        extract_widget::<gtk4::Button>(page_builder, "btn_fingerprint_uninstall");
+    let Some(btn_fingerprint_setup) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_fingerprint_setup")
+    else {
+        return;
+    };
+    let Some(btn_fingerprint_uninstall) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_fingerprint_uninstall")
+    else {
+        return;
+    };
 
     // Initial check
     let is_installed = core::is_package_installed("xfprintd-gui");
@@ -54,6 +63,22 @@ fn setup_fingerprint(page_builder: &Builder, window: &ApplicationWindow) {
         is_installed,
     );
 
+    // Detection may ask fprintd over DBus, so run it once in the background;
+    // readers are built in and don't come and go while the app is open
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(core::has_fingerprint_reader());
+    });
+    let btn_clone = btn_fingerprint_setup.clone();
+    glib::timeout_add_local(Duration::from_millis(50), move || {
+        match rx.try_recv() {
+            Ok(has_reader) => update_fingerprint_hardware_state(&btn_clone, has_reader),
+            Err(TryRecvError::Empty) => return glib::ControlFlow::Continue,
+            Err(TryRecvError::Disconnected) => {}
+        }
+        glib::ControlFlow::Break
+    });
+
     // Update on window focus (e.g. after installation completes)
     let btn_clone = btn_fingerprint_setup.clone();
     let uninstall_clone = btn_fingerprint_uninstall.clone();
@@ -122,56 +147,100 @@ fn setup_fingerprint(page_builder: &Builder, window: &ApplicationWindow) {
     });
 }
 
-fn setup_howdy(page_builder: &Builder, _window: &ApplicationWindow) {
-    let btn_howdy_setup = extract_widget::<gtk4::Button>(page_builder, "btn_howdy_setup");
+/// Only allow fingerprint setup when a reader is present, explaining why otherwise
+fn update_fingerprint_hardware_state(button: &gtk4::Button, has_reader: bool) {
+    button.set_sensitive(has_reader);
+    button.set_tooltip_text((!has_reader).then_some("No fingerprint reader detected."));
+}
+
+fn setup_howdy(page_builder: &Builder, window: &ApplicationWindow) {
+    let Some(btn_howdy_setup) = try_extract_widget::<gtk4::Button>(page_builder, "btn_howdy_setup")
+    else {
+        return;
+    };
+    let Some(btn_howdy_uninstall) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_howdy_uninstall")
+    else {
+        return;
+    };
+
+    // Initial check
+    let is_installed = core::is_package_installed("xero-howdy-qt");
+    update_button_state(&btn_howdy_setup, &btn_howdy_uninstall, is_installed);
+    update_howdy_hardware_state(&btn_howdy_setup, core::detect_camera());
+
+    // Update on window focus (e.g. after installation completes or a camera is plugged in)
+    let btn_clone = btn_howdy_setup.clone();
+    let uninstall_clone = btn_howdy_uninstall.clone();
+    window.connect_is_active_notify(move |window| {
+        if window.is_active() {
+            let is_installed = core::is_package_installed("xero-howdy-qt");
+            update_button_state(&btn_clone, &uninstall_clone, is_installed);
+            update_howdy_hardware_state(&btn_clone, core::detect_camera());
+        }
+    });
+
+    let window_clone = window.clone();
+    btn_howdy_setup.connect_clicked(move |_| {
+        info!("Biometrics: Howdy setup button clicked");
+
+        // Check again at click time
+        if core::is_package_installed("xero-howdy-qt") {
+            info!("Launching xero-howdy-qt...");
+            if let Err(e) = StdCommand::new("xero-howdy-qt")
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+            {
+                error!("Failed to launch xero-howdy-qt: {}", e);
+            }
+        } else {
+            let commands = CommandSequence::new()
+                .then(
+                    Command::builder()
+                        .aur()
+                        .args(&["-S", "--noconfirm", "--needed", "xero-howdy-qt"])
+                        .description("Installing Xero Howdy Qt...")
+                        .build(),
+                )
+                .build();
+
+            task_runner::run(window_clone.upcast_ref(), commands, "Install Xero Howdy Qt");
+        }
+    });
 
-    // Disable Howdy setup as it's not ready yet
-    btn_howdy_setup.set_sensitive(false);
+    // Uninstall action (button is defined in UI; visibility is toggled by update_button_state)
+    let window_uninstall = window.clone();
+    btn_howdy_uninstall.connect_clicked(move |_| {
+        info!("Biometrics: Howdy uninstall clicked");
+
+        let commands = CommandSequence::new()
+            .then(
+                Command::builder()
+                    .aur()
+                    .args(&["-R", "--noconfirm", "xero-howdy-qt"])
+                    .description("Removing Xero Howdy Qt...")
+                    .build(),
+            )
+            .build();
+
+        task_runner::run(
+            window_uninstall.upcast_ref(),
+            commands,
+            "Remove Xero Howdy Qt",
+        );
+    });
 }
 
-// fn setup_howdy(page_builder: &Builder, window: &ApplicationWindow) {
-//     let btn_howdy_setup = extract_widget::<gtk4::Button>(page_builder, "btn_howdy_setup");
-
-//     // Initial check
-//     let is_installed = core::is_package_installed("xero-howdy-qt");
-//     update_button_state(&btn_howdy_setup, is_installed);
-
-//     // Update on window focus (e.g. after installation completes)
-//     let btn_clone = btn_howdy_setup.clone();
-//     window.connect_is_active_notify(move |window| {
-//         if window.is_active() {
-//             let is_installed = core::is_package_installed("xero-howdy-qt");
-//             update_button_state(&btn_clone, is_installed);
-//         }
-//     });
-
-//     let window = window.clone();
-//     btn_howdy_setup.connect_clicked(move |_| {
-//         info!("Biometrics: Howdy setup button clicked");
-
-//         // Check again at click time
-//         if core::is_package_installed("xero-howdy-qt") {
-//             info!("Launching xero-howdy-qt...");
-//             if let Err(e) = StdCommand::new("xero-howdy-qt")
-//                 .stdin(Stdio::null())
-//                 .stdout(Stdio::null())
-//                 .stderr(Stdio::null())
-//                 .spawn()
-//             {
-//                 error!("Failed to launch xero-howdy-qt: {}", e);
-//             }
-//         } else {
-//             let commands = CommandSequence::new()
-//                 .then(
-//                     Command::builder()
-//                         .aur()
-//                         .args(&["-S", "--noconfirm", "--needed", "xero-howdy-qt"])
-//                         .description("Installing Xero Howdy Qt...")
-//                         .build(),
-//                 )
-//                 .build();
-
-//             task_runner::run(window.upcast_ref(), commands, "Install Xero Howdy Qt");
-//         }
-//     });
-// }
+/// Only allow Howdy setup when a camera is present, explaining why otherwise
+fn update_howdy_hardware_state(button: &gtk4::Button, camera: Option<CameraKind>) {
+    button.set_sensitive(camera.is_some());
+    button.set_tooltip_text(match camera {
+        Some(CameraKind::Infrared) => None,
+        Some(CameraKind::Webcam) => Some(
+            "No IR camera detected. Howdy will fall back to your webcam, which is less secure.",
+        ),
+        None => Some("No camera detected. Howdy needs an IR camera or webcam."),
+    });
+}
@@ -6,17 +6,19 @@
 //! - VirtualBox installation
 //! - DistroBox installation
 //! - KVM/QEMU virtualization setup
+//! - Incus system containers (with optional web UI)
 //! - iOS iPA Sideloader (Plume Impactor from Flathub)
 
 use crate::core;
 use crate::ui::dialogs::selection::{
     show_selection_dialog, SelectionDialogConfig, SelectionOption, SelectionType,
 };
+use crate::ui::dialogs::warning::show_warning_confirmation;
 use crate::ui::task_runner::{self, Command, CommandSequence};
-use crate::ui::utils::extract_widget;
+use crate::ui::utils::try_extract_widget;
 use gtk4::prelude::*;
 use gtk4::{ApplicationWindow, Builder, Button};
-use log::info;
+use log::{info, warn};
 
 /// Set up all button handlers for the containers/VMs page.
 pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &ApplicationWindow) {
@@ -25,20 +27,43 @@ pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &
     setup_vbox(page_builder, window);
     setup_distrobox(page_builder, window);
     setup_kvm(page_builder, window);
+    setup_incus(page_builder, window);
     setup_ipa_sideloader(page_builder, window);
 }
 
 fn setup_docker(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_docker");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_docker") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
         info!("Docker button clicked");
 
-        let user = crate::config::env::get().user.clone();
+        let config = SelectionDialogConfig::new(
+            "Docker Installation",
+            "Docker will be installed. Optionally run the daemon rootless as your user.",
+        )
+        .selection_type(SelectionType::Single)
+        .selection_required(false)
+        .add_option(
+            SelectionOption::new(
+                "rootless",
+                "Rootless Mode",
+                "Run the Docker daemon as your user instead of root",
+                false,
+            )
+            // Preselect when rootless Docker is already set up, so a reinstall keeps it
+            .selected(core::is_package_installed("docker-rootless-extras")),
+        )
+        .confirm_label("Install");
 
-        let commands = CommandSequence::new()
-            .then(
+        let window_for_closure = window.clone();
+        show_selection_dialog(window.upcast_ref(), config, move |selected| {
+            let user = crate::config::env::get().user.clone();
+            let rootless = selected.iter().any(|s| s == "rootless");
+
+            let mut commands = CommandSequence::new().then(
                 Command::builder()
                     .aur()
                     .args(&[
@@ -51,39 +76,155 @@ fn setup_docker(builder: &Builder, window: &ApplicationWindow) {
                     ])
                     .description("Installing Docker engine and tools...")
                     .build(),
-            )
-            .then(
-                Command::builder()
-                    .privileged()
-                    .program("systemctl")
-                    .args(&["enable", "--now", "docker.service"])
-                    .description("Enabling Docker service...")
-                    .build(),
-            )
-            .then(
-                Command::builder()
-                    .privileged()
-                    .program("groupadd")
-                    .args(&["-f", "docker"])
-                    .description("Ensuring docker group exists...")
-                    .build(),
-            )
-            .then(
-                Command::builder()
-                    .privileged()
-                    .program("usermod")
-                    .args(&["-aG", "docker", &user])
-                    .description("Adding your user to docker group...")
-                    .build(),
-            )
-            .build();
+            );
+
+            if rootless {
+                commands = commands
+                    .then(
+                        Command::builder()
+                            .aur()
+                            .args(&["-S", "--noconfirm", "--needed", "docker-rootless-extras"])
+                            .description("Installing rootless Docker extras...")
+                            .build(),
+                    )
+                    .then(
+                        Command::builder()
+                            .normal()
+                            .program("dockerd-rootless-setuptool.sh")
+                            .args(&["install"])
+                            .description("Setting up rootless Docker for your user...")
+                            .build(),
+                    )
+                    .then(
+                        Command::builder()
+                            .normal()
+                            .program("systemctl")
+                            .args(&["--user", "enable", "--now", "docker"])
+                            .description("Enabling rootless Docker user service...")
+                            .build(),
+                    );
+            } else {
+                commands = commands
+                    .then(
+                        Command::builder()
+                            .privileged()
+                            .program("systemctl")
+                            .args(&["enable", "--now", "docker.service"])
+                            .description("Enabling Docker service...")
+                            .build(),
+                    )
+                    .then(
+                        Command::builder()
+                            .privileged()
+                            .program("groupadd")
+                            .args(&["-f", "docker"])
+                            .description("Ensuring docker group exists...")
+                            .build(),
+                    )
+                    .then(
+                        Command::builder()
+                            .privileged()
+                            .program("usermod")
+                            .args(&["-aG", "docker", &user])
+                            .description("Adding your user to docker group...")
+                            .requires_relogin()
+                            .build(),
+                    );
+            }
+
+            task_runner::run(
+                window_for_closure.upcast_ref(),
+                commands.build(),
+                "Docker Setup",
+            );
+        });
+    });
+}
+
+fn setup_incus(builder: &Builder, window: &ApplicationWindow) {
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_incus") else {
+        return;
+    };
+    let window = window.clone();
+
+    button.connect_clicked(move |_| {
+        info!("Incus button clicked");
+
+        let config = SelectionDialogConfig::new(
+            "Incus Installation",
+            "Incus will be installed. Optionally include its web interface.",
+        )
+        .selection_type(SelectionType::Single)
+        .selection_required(false)
+        .add_option(SelectionOption::new(
+            "incus_ui",
+            "Incus Web UI",
+            "Browser-based interface for managing instances",
+            core::is_package_installed("incus-ui-canonical"),
+        ))
+        .confirm_label("Install");
+
+        let window_for_closure = window.clone();
+        show_selection_dialog(window.upcast_ref(), config, move |selected| {
+            let user = crate::config::env::get().user.clone();
+
+            let mut commands = CommandSequence::new()
+                .then(
+                    Command::builder()
+                        .aur()
+                        .args(&["-S", "--noconfirm", "--needed", "incus"])
+                        .description("Installing Incus...")
+                        .build(),
+                )
+                .then(
+                    Command::builder()
+                        .privileged()
+                        .program("systemctl")
+                        .args(&["enable", "--now", "incus.socket", "incus.service"])
+                        .description("Enabling Incus service...")
+                        .build(),
+                )
+                .then(
+                    Command::builder()
+                        .privileged()
+                        .program("groupadd")
+                        .args(&["-f", "incus-admin"])
+                        .description("Ensuring incus-admin group exists...")
+                        .build(),
+                )
+                .then(
+                    Command::builder()
+                        .privileged()
+                        .program("usermod")
+                        .args(&["-aG", "incus-admin", &user])
+                        .description("Adding your user to incus-admin group...")
+                        .requires_relogin()
+                        .build(),
+                );
+
+            if selected.iter().any(|s| s == "incus_ui") {
+                commands = commands.then(
+                    Command::builder()
+                        .aur()
+                        .args(&["-S", "--noconfirm", "--needed", "incus-ui-canonical"])
+                        .description("Installing Incus web UI...")
+                        .build(),
+                );
+            }
 
-        task_runner::run(window.upcast_ref(), commands, "Docker Setup");
+            task_runner::run(
+                window_for_closure.upcast_ref(),
+                commands.build(),
+                "Incus Setup",
+            );
+        });
     });
 }
 
 fn setup_podman(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_podman");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_podman") else {
+        return;
+    };
     let window = window.clone();
     button.connect_clicked(move |_| {
         info!("Podman button clicked");
@@ -149,28 +290,35 @@ fn setup_podman(builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_vbox(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_vbox");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_vbox") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
         info!("VirtualBox button clicked");
 
-        let commands = CommandSequence::new()
-            .then(
-                Command::builder()
-                    .aur()
-                    .args(&["-S", "--noconfirm", "--needed", "virtualbox-meta"])
-                    .description("Installing VirtualBox...")
-                    .build(),
-            )
-            .build();
+        let commands = CommandSequence::new().then(
+            Command::builder()
+                .aur()
+                .args(&["-S", "--noconfirm", "--needed", "virtualbox-meta"])
+                .description("Installing VirtualBox...")
+                .build(),
+        );
 
-        task_runner::run(window.upcast_ref(), commands, "VirtualBox Setup");
+        run_with_virtualization_check(
+            &window,
+            core::detect_cpu_virtualization(),
+            commands,
+            "VirtualBox Setup",
+        );
     });
 }
 
 fn setup_distrobox(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_distrobox");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_distrobox") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -199,7 +347,9 @@ fn setup_distrobox(builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_kvm(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_kvm");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_kvm") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -242,17 +392,24 @@ fn setup_kvm(builder: &Builder, window: &ApplicationWindow) {
                 .build(),
         );
 
-        commands = commands.then(
-            Command::builder()
-                .privileged()
-                .program("sh")
-                .args(&[
-                    "-c",
-                    "echo 'options kvm-intel nested=1' > /etc/modprobe.d/kvm-intel.conf",
-                ])
-                .description("Enabling nested virtualization...")
-                .build(),
-        );
+        let cpu = core::detect_cpu_virtualization();
+        if let Some(vendor) = cpu.vendor {
+            let module = vendor.kvm_module();
+            let nested_conf = format!(
+                "echo 'options {} nested=1' > /etc/modprobe.d/{}.conf",
+                module, module
+            );
+            commands = commands.then(
+                Command::builder()
+                    .privileged()
+                    .program("sh")
+                    .args(&["-c", &nested_conf])
+                    .description("Enabling nested virtualization...")
+                    .build(),
+            );
+        } else {
+            warn!("Unknown CPU vendor, skipping nested virtualization setup");
+        }
 
         commands = commands.then(
             Command::builder()
@@ -263,12 +420,54 @@ fn setup_kvm(builder: &Builder, window: &ApplicationWindow) {
                 .build(),
         );
 
-        task_runner::run(window.upcast_ref(), commands.build(), "KVM / QEMU Setup");
+        run_with_virtualization_check(&window, cpu, commands, "KVM / QEMU Setup");
     });
 }
 
+/// Run a virtualization install, first warning if the CPU doesn't report
+/// VT-x/AMD-V support.
+///
+/// The flag is hidden when virtualization is disabled in firmware, so the user
+/// is told to enable it in the BIOS but may still go ahead with the install.
+fn run_with_virtualization_check(
+    window: &ApplicationWindow,
+    cpu: core::CpuVirtualization,
+    commands: CommandSequence,
+    title: &'static str,
+) {
+    if cpu.hardware_support {
+        task_runner::run(window.upcast_ref(), commands.build(), title);
+        return;
+    }
+
+    let extension = cpu
+        .vendor
+        .map(|vendor| vendor.virtualization_name())
+        .unwrap_or("VT-x / AMD-V");
+    let message = format!(
+        "Your CPU does not report hardware virtualization support. {} is probably disabled \
+         in your firmware (BIOS/UEFI) settings, so virtual machines will run without \
+         acceleration, or not at all, until it is enabled.\n\n\
+         See <a href=\"{}\">how to check and enable virtualization</a>.\n\n\
+         Continue with the installation anyway?",
+        extension,
+        crate::config::links::VIRTUALIZATION_GUIDE
+    );
+    let window_clone = window.clone();
+    show_warning_confirmation(
+        window.upcast_ref(),
+        "Hardware Virtualization Disabled",
+        &message,
+        move || {
+            task_runner::run(window_clone.upcast_ref(), commands.build(), title);
+        },
+    );
+}
+
 fn setup_ipa_sideloader(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_ipa_sideloader");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_ipa_sideloader") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -6,14 +6,105 @@
 //! - GRUB theme installation
 //! - Plymouth Manager
 //! - Update Layan Theme
+//! - SDDM theme installation
+//! - Removing installed themes
 //! - Config/Rice reset
 
+use crate::core;
+use crate::ui::dialogs::error::show_error;
+use crate::ui::dialogs::selection::{
+    show_selection_dialog, SelectionDialogConfig, SelectionOption, SelectionType,
+};
 use crate::ui::dialogs::terminal;
+use crate::ui::dialogs::warning::show_warning_confirmation;
 use crate::ui::task_runner::{self, Command, CommandSequence};
-use crate::ui::utils::extract_widget;
-use gtk4::prelude::*;
-use gtk4::{ApplicationWindow, Builder, Button};
-use log::info;
+use crate::ui::utils::{run_command, try_extract_widget};
+use adw::prelude::*;
+use gtk4::{glib, ApplicationWindow, Builder, Button};
+use log::{info, warn};
+use std::path::Path;
+
+/// Oh My Zsh plugins set up by ZSH AiO: (directory name, repository, label).
+const ZSH_PLUGINS: &[(&str, &str, &str)] = &[
+    (
+        "zsh-completions",
+        "https://github.com/zsh-users/zsh-completions",
+        "completions",
+    ),
+    (
+        "zsh-autosuggestions",
+        "https://github.com/zsh-users/zsh-autosuggestions",
+        "autosuggestions",
+    ),
+    (
+        "zsh-syntax-highlighting",
+        "https://github.com/zsh-users/zsh-syntax-highlighting.git",
+        "syntax highlighting",
+    ),
+];
+
+/// Layan GTK color variants: (install.sh `-c` value, label, description).
+const LAYAN_COLORS: &[(&str, &str, &str)] = &[
+    ("dark", "Dark", "Dark windows and panels, for dark mode"),
+    ("light", "Light", "Light windows and panels, for light mode"),
+    (
+        "standard",
+        "Standard",
+        "Light windows with dark title bars and panels",
+    ),
+];
+
+/// Layan color variant used until the user picks one.
+const LAYAN_DEFAULT_COLOR: &str = "dark";
+
+/// Repository of the XeroLinux SDDM theme.
+const SDDM_THEME_REPO: &str = "https://github.com/XeroLinuxDev/xero-sddm-theme.git";
+
+/// Directory name of the SDDM theme under `/usr/share/sddm/themes`.
+const SDDM_THEME_NAME: &str = "xero-sddm-theme";
+
+/// Drop-in file that selects the SDDM theme.
+const SDDM_THEME_CONF: &str = "/etc/sddm.conf.d/10-xero-theme.conf";
+
+/// Where the Layan installers put the theme, as (directory, entry name
+/// prefix) pairs; a leading `~` is the user's home.
+const LAYAN_PATHS: &[(&str, &str)] = &[
+    ("~/.themes", "Layan"),
+    ("/usr/share/aurorae/themes", "Layan"),
+    ("/usr/share/color-schemes", "Layan"),
+    ("/usr/share/plasma/desktoptheme", "Layan"),
+    (
+        "/usr/share/plasma/look-and-feel",
+        "com.github.vinceliuice.Layan",
+    ),
+    ("/usr/share/Kvantum", "Layan"),
+    ("/usr/share/wallpapers", "Layan"),
+];
+
+/// Directories GRUB themes are installed into.
+const GRUB_THEME_DIRS: &[&str] = &["/boot/grub/themes", "/usr/share/grub/themes"];
+
+/// GRUB settings file the theme installer modifies.
+const GRUB_DEFAULT: &str = "/etc/default/grub";
+
+/// Removable themes: (id, label, description).
+const REMOVABLE_THEMES: &[(&str, &str, &str)] = &[
+    (
+        "layan",
+        "Layan Theme",
+        "KDE and GTK theme files installed by Update Layan Theme",
+    ),
+    (
+        "sddm",
+        "SDDM Theme",
+        "Login screen theme and its SDDM setting",
+    ),
+    (
+        "grub",
+        "GRUB Theme",
+        "Boot menu theme; the GRUB settings from before it are restored",
+    ),
+];
 
 /// Set up all button handlers for the customization page.
 pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &ApplicationWindow) {
@@ -22,11 +113,15 @@ pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &
     setup_grub_theme(page_builder, window);
     setup_plymouth_manager(page_builder, window);
     setup_layan_patch(page_builder, window);
+    setup_sddm_theme(page_builder, window);
+    setup_remove_theme(page_builder, window);
     setup_config_reset(page_builder, window);
 }
 
 fn setup_zsh_aio(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_zsh_aio");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_zsh_aio") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -36,7 +131,9 @@ fn setup_zsh_aio(builder: &Builder, window: &ApplicationWindow) {
         let home = env.home.clone();
         let user = env.user.clone();
 
-        let commands = CommandSequence::new()
+        let oh_my_zsh_dir = format!("{}/.oh-my-zsh", home);
+
+        let mut commands = CommandSequence::new()
             .then(Command::builder()
                 .aur()
                 .args(&[
@@ -48,16 +145,26 @@ fn setup_zsh_aio(builder: &Builder, window: &ApplicationWindow) {
                     "fastfetch",
                 ])
                 .description("Installing ZSH and dependencies...")
-                .build())
-            .then(Command::builder()
+                .build());
+
+        // The installer refuses to run over an existing install, so skip it on re-runs
+        if Path::new(&oh_my_zsh_dir).exists() {
+            info!("Oh My Zsh already installed, skipping installer");
+        } else {
+            commands = commands.then(Command::builder()
                 .normal()
                 .program("sh")
                 .args(&[
                     "-c",
-                    "curl -fsSL https://raw.githubusercontent.com/ohmyzsh/ohmyzsh/master/tools/install.sh | sh -s -- --unattended",
+                    "curl -fsSL https://raw.githubusercontent.com/ohmyzsh/ohmyzsh/master/tools/install.sh | sh",
                 ])
+                .env("RUNZSH", "no")
+                .env("CHSH", "no")
                 .description("Installing Oh My Zsh framework...")
-                .build())
+                .build());
+        }
+
+        commands = commands
             .then(Command::builder()
                 .aur()
                 .args(&[
@@ -78,45 +185,43 @@ fn setup_zsh_aio(builder: &Builder, window: &ApplicationWindow) {
                     "oh-my-posh-bin",
                 ])
                 .description("Installing fonts and terminal enhancements...")
-                .build())
-            .then(Command::builder()
-                .normal()
-                .program("git")
-                .args(&[
-                    "clone",
-                    "https://github.com/zsh-users/zsh-completions",
-                    &format!("{}/.oh-my-zsh/custom/plugins/zsh-completions", home),
-                ])
-                .description("Installing ZSH completions plugin...")
-                .build())
-            .then(Command::builder()
-                .normal()
-                .program("git")
-                .args(&[
-                    "clone",
-                    "https://github.com/zsh-users/zsh-autosuggestions",
-                    &format!("{}/.oh-my-zsh/custom/plugins/zsh-autosuggestions", home),
-                ])
-                .description("Installing ZSH autosuggestions plugin...")
-                .build())
-            .then(Command::builder()
-                .normal()
-                .program("git")
-                .args(&[
-                    "clone",
-                    "https://github.com/zsh-users/zsh-syntax-highlighting.git",
-                    &format!("{}/.oh-my-zsh/custom/plugins/zsh-syntax-highlighting", home),
-                ])
-                .description("Installing ZSH syntax highlighting plugin...")
-                .build())
+                .build());
+
+        // Clone missing plugins and update existing ones so re-running acts as an update
+        for (name, repo, label) in ZSH_PLUGINS {
+            let plugin_dir = format!("{}/custom/plugins/{}", oh_my_zsh_dir, name);
+            let command = if Path::new(&plugin_dir).exists() {
+                Command::builder()
+                    .normal()
+                    .program("git")
+                    .args(&["-C", &plugin_dir, "pull", "--ff-only"])
+                    .description(&format!("Updating ZSH {} plugin...", label))
+                    .build()
+            } else {
+                Command::builder()
+                    .normal()
+                    .program("git")
+                    .args(&["clone", repo, &plugin_dir])
+                    .description(&format!("Installing ZSH {} plugin...", label))
+                    .build()
+            };
+            commands = commands.then(command);
+        }
+
+        let commands = commands
             .then(Command::builder()
                 .normal()
                 .program("sh")
                 .args(&[
                     "-c",
+                    // Only the first backup goes to .zshrc.user; later runs
+                    // would otherwise overwrite it with the XeroLinux config
                     &format!(
-                        "mv -f {}/.zshrc {}/.zshrc.user 2>/dev/null || true",
-                        home, home
+                        "if [ -e {home}/.zshrc ]; then \
+                         if [ -e {home}/.zshrc.user ]; then \
+                         mv -f {home}/.zshrc {home}/.zshrc.bak-$(date +%Y.%m.%d-%H.%M.%S); \
+                         else mv -f {home}/.zshrc {home}/.zshrc.user; fi; fi",
+                        home = home
                     ),
                 ])
                 .description("Backing up existing ZSH configuration...")
@@ -149,6 +254,7 @@ fn setup_zsh_aio(builder: &Builder, window: &ApplicationWindow) {
                 .program("chsh")
                 .args(&[&user, "-s", "/bin/zsh"])
                 .description("Setting ZSH as default shell...")
+                .requires_relogin()
                 .build())
             .build();
 
@@ -161,7 +267,9 @@ fn setup_zsh_aio(builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_save_desktop(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_save_desktop");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_save_desktop") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -187,12 +295,16 @@ fn setup_save_desktop(builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_grub_theme(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_grub_theme");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_grub_theme") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
         info!("GRUB Theme button clicked");
 
+        backup_grub_settings();
+
         let install_command = "cd /tmp && curl -fsSL 'https://xerolinux.xyz/script/grubs/xero-grubs.py' -o xero-grubs.py && setsid -f python3 /tmp/xero-grubs.py > /dev/null 2>&1 < /dev/null";
 
         terminal::show_terminal_dialog(
@@ -206,7 +318,9 @@ fn setup_grub_theme(builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_plymouth_manager(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_plymouth_manager");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_plymouth_manager") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -223,60 +337,534 @@ fn setup_plymouth_manager(builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_layan_patch(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_layan_patch");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_layan_patch") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
         info!("Update Layan Theme button clicked");
 
+        let config = crate::config::user::shared();
+        let last_color = config.borrow().themes.layan_color.clone();
+        let last_color = last_color.as_deref().unwrap_or(LAYAN_DEFAULT_COLOR);
+
+        let mut dialog_config = SelectionDialogConfig::new(
+            "Update Layan Theme",
+            "Choose the color variant for the Layan GTK theme.",
+        )
+        .selection_type(SelectionType::Single)
+        .confirm_label("Install");
+        for (id, label, description) in LAYAN_COLORS {
+            dialog_config = dialog_config.add_option(
+                SelectionOption::new(id, label, description, false).selected(*id == last_color),
+            );
+        }
+
+        let window_clone = window.clone();
+        show_selection_dialog(window.upcast_ref(), dialog_config, move |selected| {
+            let Some(color) = selected.first() else {
+                return;
+            };
+
+            // Update in-memory config; actual persistence happens on app shutdown.
+            config.borrow_mut().themes.layan_color = Some(color.clone());
+
+            install_layan_theme(&window_clone, color);
+        });
+    });
+}
+
+/// Install the Layan KDE theme and the Layan GTK theme in the given color.
+fn install_layan_theme(window: &ApplicationWindow, color: &str) {
+    info!("Installing Layan theme with the {} color variant", color);
+
+    // Entries that already exist may come from a package or an earlier manual
+    // install, so only the ones this install adds are recorded
+    let existing = layan_paths();
+
+    let home = crate::config::env::get().home.clone();
+    let kde_dir = format!("{}/Layan-kde", home);
+    let gtk_dir = format!("{}/Layan-gtk-theme", home);
+
+    let commands = CommandSequence::new()
+        .then(
+            Command::builder()
+                .normal()
+                .program("git")
+                .args(&[
+                    "clone",
+                    "--depth",
+                    "1",
+                    "https://github.com/vinceliuice/Layan-kde.git",
+                    &kde_dir,
+                ])
+                .description("Downloading Layan KDE theme...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .privileged()
+                .program("sh")
+                .args(&["install.sh"])
+                .cwd(&kde_dir)
+                .description("Installing Layan KDE theme...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .normal()
+                .program("git")
+                .args(&[
+                    "clone",
+                    "--depth",
+                    "1",
+                    "https://github.com/vinceliuice/Layan-gtk-theme.git",
+                    &gtk_dir,
+                ])
+                .description("Downloading Layan GTK theme...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .normal()
+                .program("sh")
+                .args(&["install.sh", "-c", color])
+                .cwd(&gtk_dir)
+                .description("Installing Layan GTK theme...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .normal()
+                .program("rm")
+                .args(&["-rf", &kde_dir, &gtk_dir])
+                .description("Cleaning up theme files...")
+                .build(),
+        )
+        .build();
+
+    task_runner::run_with_on_complete(
+        window.upcast_ref(),
+        commands,
+        "Update Layan Theme",
+        move |success| {
+            if success {
+                let mut paths = layan_paths();
+                paths.retain(|path| !existing.contains(path));
+                record_theme_paths("layan", paths);
+            }
+        },
+    );
+}
+
+fn setup_sddm_theme(builder: &Builder, window: &ApplicationWindow) {
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_sddm_theme") else {
+        return;
+    };
+    let window = window.clone();
+
+    button.connect_clicked(move |_| {
+        info!("SDDM Theme button clicked");
+
+        let display_manager = core::detect_display_manager();
+        if display_manager.as_deref() == Some("sddm") {
+            install_sddm_theme(&window);
+            return;
+        }
+
+        // The theme only shows up on the login screen when SDDM is in use
+        let active = match display_manager {
+            Some(name) => format!(
+                "The active display manager is <span weight=\"bold\">{}</span>, not SDDM.",
+                name
+            ),
+            None => "No display manager is enabled.".to_string(),
+        };
+        let window_clone = window.clone();
+        show_warning_confirmation(
+            window.upcast_ref(),
+            "SDDM Not Active",
+            &format!(
+                "{}\n\nThe theme can still be installed, but it will only be used once \
+                 SDDM is enabled as the display manager.",
+                active
+            ),
+            move || install_sddm_theme(&window_clone),
+        );
+    });
+}
+
+/// Clone the SDDM theme, install it system-wide and select it.
+fn install_sddm_theme(window: &ApplicationWindow) {
+    // Root copies the theme out of the checkout, so it must live in a fresh
+    // directory no other user can write to
+    let Some(clone_dir) = run_command("mktemp", &["-d", "-t", "xero-sddm-theme.XXXXXX"]) else {
+        warn!("Failed to create a temporary directory for the SDDM theme");
+        show_error(
+            window,
+            "Could not create a temporary directory for the SDDM theme.",
+        );
+        return;
+    };
+    let theme_dir = format!("/usr/share/sddm/themes/{}", SDDM_THEME_NAME);
+
+    let commands = CommandSequence::new()
+        .then(
+            Command::builder()
+                .normal()
+                .program("git")
+                .args(&["clone", "--depth", "1", SDDM_THEME_REPO, &clone_dir])
+                .description("Downloading XeroLinux SDDM theme...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .privileged()
+                .program("sh")
+                .args(&[
+                    "-c",
+                    &format!(
+                        "rm -rf '{theme}' && mkdir -p '{theme}' && cp -r '{src}'/. '{theme}' && rm -rf '{theme}/.git'",
+                        theme = theme_dir,
+                        src = clone_dir
+                    ),
+                ])
+                .description("Installing SDDM theme...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .privileged()
+                .program("sh")
+                .args(&[
+                    "-c",
+                    &format!(
+                        "mkdir -p /etc/sddm.conf.d && printf '[Theme]\\nCurrent=%s\\n' '{}' > '{}'",
+                        SDDM_THEME_NAME, SDDM_THEME_CONF
+                    ),
+                ])
+                .description("Setting SDDM theme...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .normal()
+                .program("rm")
+                .args(&["-rf", &clone_dir])
+                .description("Cleaning up SDDM theme files...")
+                .build(),
+        )
+        .build();
+
+    task_runner::run_with_on_complete(
+        window.upcast_ref(),
+        commands,
+        "XeroLinux SDDM Theme",
+        move |success| {
+            if success {
+                record_theme_paths("sddm", vec![theme_dir, SDDM_THEME_CONF.to_string()]);
+            }
+        },
+    );
+}
+
+fn setup_remove_theme(builder: &Builder, window: &ApplicationWindow) {
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_remove_theme") else {
+        return;
+    };
+    let window = window.clone();
+
+    button.connect_clicked(move |_| {
+        info!("Remove Theme button clicked");
+
+        let removable: Vec<_> = REMOVABLE_THEMES
+            .iter()
+            .filter(|(id, _, _)| is_theme_removable(id))
+            .collect();
+
+        if removable.is_empty() {
+            let dialog = adw::AlertDialog::builder()
+                .heading("No Themes to Remove")
+                .body(
+                    "Only themes installed with this toolkit can be removed here, \
+                     and none were found.",
+                )
+                .build();
+            dialog.add_response("close", "Close");
+            dialog.present(Some(&window));
+            return;
+        }
+
+        let mut config = SelectionDialogConfig::new(
+            "Remove Theme",
+            "Select the themes to remove. Files that existed before the toolkit \
+             installed a theme are left untouched.",
+        )
+        .confirm_label("Remove");
+        for (id, label, description) in removable {
+            config = config.add_option(SelectionOption::new(id, label, description, false));
+        }
+
+        let window_clone = window.clone();
+        show_selection_dialog(window.upcast_ref(), config, move |selected| {
+            confirm_remove_themes(&window_clone, selected);
+        });
+    });
+}
+
+/// List the paths that will be deleted and remove the themes once confirmed.
+fn confirm_remove_themes(window: &ApplicationWindow, themes: Vec<String>) {
+    let removes_grub = themes.iter().any(|id| id == "grub");
+    let mut paths = tracked_theme_paths(&themes);
+    if removes_grub {
+        paths.extend(installed_grub_theme_dir());
+    }
+
+    // Everything was already deleted by hand, so only the records remain
+    if paths.is_empty() && !removes_grub {
+        forget_themes(&themes);
+        return;
+    }
+
+    let mut message = String::from("The following will be permanently deleted:\n\n");
+    for path in &paths {
+        message.push_str(&format!("<tt>{}</tt>\n", glib::markup_escape_text(path)));
+    }
+    if removes_grub {
+        message.push_str(&format!(
+            "\n<tt>{}</tt> will be restored from the backup taken before the theme was installed.",
+            GRUB_DEFAULT
+        ));
+    }
+
+    let window_clone = window.clone();
+    show_warning_confirmation(window.upcast_ref(), "Remove Theme", &message, move || {
         let home = crate::config::env::get().home.clone();
+        let mut commands = CommandSequence::new();
 
-        let commands = CommandSequence::new()
-            .then(
-                Command::builder()
-                    .normal()
-                    .program("git")
-                    .args(&[
-                        "clone",
-                        "--depth",
-                        "1",
-                        "https://github.com/vinceliuice/Layan-kde.git",
-                        &format!("{}/Layan-kde", home),
-                    ])
-                    .description("Downloading Layan KDE theme...")
-                    .build(),
-            )
-            .then(
-                Command::builder()
-                    .privileged()
-                    .program("sh")
-                    .args(&["-c", &format!("cd {}/Layan-kde && sh install.sh", home)])
-                    .description("Installing Layan KDE theme...")
-                    .build(),
-            )
-            .then(
-                Command::builder()
-                    .normal()
+        for path in &paths {
+            let builder = if path.starts_with(&home) {
+                Command::builder().normal()
+            } else {
+                Command::builder().privileged()
+            };
+            commands = commands.then(
+                builder
                     .program("rm")
-                    .args(&["-rf", &format!("{}/Layan-kde", home)])
-                    .description("Cleaning up KDE theme files...")
+                    .args(&["-rf", path])
+                    .description(&format!("Removing {}...", path))
                     .build(),
-            )
-            .build();
+            );
+        }
+
+        let grub_backup = crate::config::user::shared()
+            .borrow()
+            .themes
+            .grub_backup
+            .clone();
+        if let Some(backup) = grub_backup.filter(|_| removes_grub) {
+            commands = commands
+                .then(
+                    Command::builder()
+                        .privileged()
+                        .program("cp")
+                        .args(&["--", &backup, GRUB_DEFAULT])
+                        .description("Restoring previous GRUB settings...")
+                        .build(),
+                )
+                .then(
+                    Command::builder()
+                        .privileged()
+                        .program("grub-mkconfig")
+                        .args(&["-o", "/boot/grub/grub.cfg"])
+                        .description("Regenerating GRUB configuration...")
+                        .build(),
+                );
+        }
 
-        task_runner::run(window.upcast_ref(), commands, "Update Layan Theme");
+        let themes = themes.clone();
+        task_runner::run_with_on_complete(
+            window_clone.upcast_ref(),
+            commands.build(),
+            "Remove Theme",
+            move |success| {
+                if success {
+                    forget_themes(&themes);
+                }
+            },
+        );
     });
 }
 
+/// Check whether the toolkit has something to undo for a theme.
+fn is_theme_removable(theme: &str) -> bool {
+    if theme == "grub" {
+        return installed_grub_theme_dir().is_some();
+    }
+    crate::config::user::shared()
+        .borrow()
+        .themes
+        .installed_paths
+        .get(theme)
+        .is_some_and(|paths| !paths.is_empty())
+}
+
+/// Paths recorded for the given themes that still exist.
+fn tracked_theme_paths(themes: &[String]) -> Vec<String> {
+    let config = crate::config::user::shared();
+    let config = config.borrow();
+    themes
+        .iter()
+        .filter_map(|theme| config.themes.installed_paths.get(theme))
+        .flatten()
+        .filter(|path| Path::new(path).exists())
+        .cloned()
+        .collect()
+}
+
+/// Drop the records of removed themes.
+fn forget_themes(themes: &[String]) {
+    let config = crate::config::user::shared();
+    // Update in-memory config; actual persistence happens on app shutdown.
+    let mut config = config.borrow_mut();
+    for theme in themes {
+        config.themes.installed_paths.remove(theme);
+    }
+    if themes.iter().any(|id| id == "grub") {
+        if let Some(backup) = config.themes.grub_backup.take() {
+            let _ = std::fs::remove_file(backup);
+        }
+    }
+    drop(config);
+
+    info!("Removed themes: {}", themes.join(", "));
+}
+
+/// List the installed Layan theme entries.
+fn layan_paths() -> Vec<String> {
+    let home = crate::config::env::get().home.clone();
+    LAYAN_PATHS
+        .iter()
+        .flat_map(|(dir, prefix)| {
+            let dir = match dir.strip_prefix('~') {
+                Some(rest) => format!("{}{}", home, rest),
+                None => dir.to_string(),
+            };
+            std::fs::read_dir(dir)
+                .into_iter()
+                .flat_map(|entries| entries.flatten())
+                .filter(|entry| entry.file_name().to_string_lossy().starts_with(prefix))
+                .map(|entry| entry.path().to_string_lossy().into_owned())
+        })
+        .collect()
+}
+
+/// Remember `paths` as written by the installer of `theme`.
+///
+/// Only the paths each installer is known to write are recorded, so removing
+/// the theme never touches unrelated files in shared directories.
+fn record_theme_paths(theme: &str, mut paths: Vec<String>) {
+    paths.retain(|path| Path::new(path).exists());
+    if paths.is_empty() {
+        return;
+    }
+    paths.sort();
+    info!("Theme '{}' installed {} path(s)", theme, paths.len());
+
+    let config = crate::config::user::shared();
+    // Update in-memory config; actual persistence happens on app shutdown.
+    let mut config = config.borrow_mut();
+    let recorded = config
+        .themes
+        .installed_paths
+        .entry(theme.to_string())
+        .or_default();
+    for path in paths {
+        if !recorded.contains(&path) {
+            recorded.push(path);
+        }
+    }
+}
+
+/// Back up the GRUB settings before a theme installer changes them.
+///
+/// Only the settings from before the first install are kept, so removing the
+/// theme restores the file from before any toolkit theme was installed.
+fn backup_grub_settings() {
+    let config = crate::config::user::shared();
+    if config.borrow().themes.grub_backup.is_some() {
+        return;
+    }
+
+    let backup = crate::config::user::config_path().with_file_name("grub.bak");
+    if let Some(parent) = backup.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(e) = std::fs::copy(GRUB_DEFAULT, &backup) {
+        warn!("Failed to back up {}: {}", GRUB_DEFAULT, e);
+        return;
+    }
+    info!("Backed up {} to {}", GRUB_DEFAULT, backup.display());
+
+    // Update in-memory config; actual persistence happens on app shutdown.
+    config.borrow_mut().themes.grub_backup = Some(backup.to_string_lossy().into_owned());
+}
+
+/// Find the active `GRUB_THEME=` line in GRUB settings.
+fn grub_theme_line(content: &str) -> Option<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("GRUB_THEME="))
+        .last()
+        .map(str::to_string)
+}
+
+/// Read the theme path from a `GRUB_THEME=` line.
+fn grub_theme_setting(line: &str) -> Option<String> {
+    line.strip_prefix("GRUB_THEME=")
+        .map(|value| value.trim_matches(['"', '\'']).to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Find the directory of the GRUB theme the toolkit installed.
+///
+/// Returns None when no backup was taken, the theme setting is unchanged
+/// since then, or the theme lives outside the known GRUB theme directories.
+fn installed_grub_theme_dir() -> Option<String> {
+    let backup = crate::config::user::shared()
+        .borrow()
+        .themes
+        .grub_backup
+        .clone()?;
+    let previous = std::fs::read_to_string(backup).ok()?;
+    let content = std::fs::read_to_string(GRUB_DEFAULT).ok()?;
+    let current = grub_theme_setting(&grub_theme_line(&content)?)?;
+    let previous = grub_theme_line(&previous).and_then(|line| grub_theme_setting(&line));
+    if previous.as_deref() == Some(current.as_str()) {
+        return None;
+    }
+
+    // GRUB_THEME points at theme.txt inside the theme's directory
+    let theme_dir = Path::new(&current).parent()?;
+    GRUB_THEME_DIRS
+        .iter()
+        .any(|dir| theme_dir.parent() == Some(Path::new(dir)))
+        .then(|| theme_dir.to_string_lossy().into_owned())
+}
+
 fn setup_config_reset(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_config_reset");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_config_reset") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
         info!("Config/Rice Reset button clicked");
 
         let window_clone = window.clone();
-        crate::ui::dialogs::warning::show_warning_confirmation(
+        show_warning_confirmation(
             window.upcast_ref(),
             "Config/Rice Reset",
             "A backup of <span foreground=\"cyan\" weight=\"bold\">~/.config</span> will be created.\n\
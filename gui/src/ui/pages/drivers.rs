@@ -5,37 +5,82 @@
 //! - ASUS ROG laptop tools
 //! - OpenRazer drivers
 //! - Cooler Control daemon tools
+//! - Device firmware updates (fwupd)
+//! - AMD GPU tools (RADV + CoreCtrl)
 
 use crate::core;
+use crate::core::firmware::{self, FirmwareUpdate};
+use crate::core::GpuVendor;
+use crate::ui::dialogs::error::show_error;
 use crate::ui::dialogs::selection::{
     show_selection_dialog, SelectionDialogConfig, SelectionOption, SelectionType,
 };
+use crate::ui::dialogs::terminal;
 use crate::ui::dialogs::warning::show_warning_confirmation;
 use crate::ui::task_runner::{self, Command, CommandSequence};
-use crate::ui::utils::extract_widget;
-use gtk4::prelude::*;
-use gtk4::{ApplicationWindow, Builder, Button};
-use log::info;
+use crate::ui::utils::{run_command, set_button_spinning, try_extract_widget};
+use adw::prelude::*;
+use gtk4::{glib, ApplicationWindow, Builder, Button};
+use log::{info, warn};
+use regex::Regex;
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::Duration;
 
 /// Set up all button handlers for the drivers page.
 pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &ApplicationWindow) {
+    let gpus = core::detect_gpu_vendors();
+    let has_nvidia = gpus.contains(&GpuVendor::Nvidia);
+
+    setup_gpu_hints(page_builder, &gpus);
     setup_tailscale(page_builder, window);
-    setup_asus_rog(page_builder, window);
+    setup_asus_rog(page_builder, window, core::is_asus_hardware());
     setup_openrazer(page_builder, window);
     setup_cooler_control(page_builder, window);
+    setup_firmware_updates(page_builder, window);
     setup_zenergy(page_builder, window);
-    setup_nvidia_legacy(page_builder, window);
+    setup_nvidia_legacy(page_builder, window, has_nvidia);
     setup_rocm(page_builder, window);
-    setup_cuda(page_builder, window);
+    setup_cuda(page_builder, window, has_nvidia);
+    setup_amd_gpu_tools(page_builder, window, gpus.contains(&GpuVendor::Amd));
+}
+
+/// Notice appended to NVIDIA-only dialogs when no NVIDIA GPU is present.
+const NO_NVIDIA_NOTICE: &str = "No NVIDIA GPU was detected on this system.";
+
+/// Emphasize GPU-specific buttons only when a matching GPU is present.
+fn setup_gpu_hints(builder: &Builder, gpus: &[GpuVendor]) {
+    let vendor_buttons = [
+        ("btn_nvidia_legacy", GpuVendor::Nvidia),
+        ("btn_cuda", GpuVendor::Nvidia),
+        ("btn_rocm", GpuVendor::Amd),
+    ];
+
+    for (id, vendor) in vendor_buttons {
+        if gpus.contains(&vendor) {
+            continue;
+        }
+
+        let Some(button) = try_extract_widget::<Button>(builder, id) else {
+            continue;
+        };
+        button.remove_css_class("suggested-action");
+        button.set_tooltip_text(Some(&format!(
+            "No {} GPU detected on this system",
+            vendor.name()
+        )));
+    }
 }
 
 fn setup_tailscale(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_tailscale");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_tailscale") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
         info!("Tailscale VPN button clicked");
 
+        let operator = format!("--operator={}", crate::config::env::get().user);
         let commands = CommandSequence::new()
             .then(Command::builder()
                 .privileged()
@@ -46,14 +91,72 @@ fn setup_tailscale(builder: &Builder, window: &ApplicationWindow) {
                 ])
                 .description("Installing Tailscale VPN...")
                 .build())
+            .then(Command::builder()
+                .privileged()
+                .program("systemctl")
+                .args(&["enable", "--now", "tailscaled.service"])
+                .description("Enabling Tailscale service...")
+                .build())
+            // Lets the user run `tailscale up` and other commands without root
+            .then(Command::builder()
+                .privileged()
+                .program("tailscale")
+                .args(&["set", &operator])
+                .description("Allowing your user to manage Tailscale...")
+                .build())
             .build();
 
-        task_runner::run(window.upcast_ref(), commands, "Install Tailscale VPN");
+        let window_clone = window.clone();
+        task_runner::run_with_on_complete(
+            window.upcast_ref(),
+            commands,
+            "Install Tailscale VPN",
+            move |success| {
+                if !success {
+                    return;
+                }
+                if is_tailscale_authenticated() {
+                    info!("Tailscale already authenticated, skipping login");
+                    return;
+                }
+
+                // `tailscale up` prints a login URL and waits for the browser sign-in
+                terminal::show_terminal_dialog(
+                    window_clone.upcast_ref(),
+                    "Tailscale Login",
+                    "tailscale",
+                    &["up"],
+                    true,
+                );
+            },
+        );
     });
 }
 
-fn setup_asus_rog(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_asus_rog");
+/// Check whether this machine is already logged in to a tailnet.
+fn is_tailscale_authenticated() -> bool {
+    run_command("tailscale", &["status", "--json"])
+        .and_then(|status| {
+            Regex::new(r#""BackendState":\s*"(\w+)""#)
+                .ok()?
+                .captures(&status)
+                .map(|caps| &caps[1] == "Running")
+        })
+        .unwrap_or(false)
+}
+
+fn setup_asus_rog(builder: &Builder, window: &ApplicationWindow, is_asus: bool) {
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_asus_rog") else {
+        return;
+    };
+
+    // Keep the button in place so the layout doesn't shift between machines
+    if !is_asus {
+        button.set_sensitive(false);
+        button.set_tooltip_text(Some("No ASUS hardware detected on this system"));
+        return;
+    }
+
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -89,7 +192,9 @@ fn setup_asus_rog(builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_openrazer(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_openrazer");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_openrazer") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -129,7 +234,9 @@ fn setup_openrazer(builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_cooler_control(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_cooler_control");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_cooler_control") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -185,6 +292,7 @@ fn build_openrazer_commands(selected_frontends: &[String]) -> CommandSequence {
             .program("usermod")
             .args(&["-aG", "plugdev", &user])
             .description("Adding user to plugdev group...")
+            .requires_relogin()
             .build(),
     );
 
@@ -212,8 +320,126 @@ fn build_openrazer_commands(selected_frontends: &[String]) -> CommandSequence {
     commands
 }
 
+fn setup_firmware_updates(builder: &Builder, window: &ApplicationWindow) {
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_firmware_updates") else {
+        return;
+    };
+    let window = window.clone();
+
+    button.connect_clicked(move |button| {
+        info!("Firmware Updates button clicked");
+
+        // fwupd may take a while to answer, so ask it off the main thread
+        set_button_spinning(button, true);
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(firmware::check_updates());
+        });
+
+        let button = button.clone();
+        let window = window.clone();
+        glib::timeout_add_local(Duration::from_millis(50), move || {
+            let result = match rx.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                Err(TryRecvError::Disconnected) => {
+                    Err(anyhow::anyhow!("Firmware check stopped unexpectedly"))
+                }
+            };
+            set_button_spinning(&button, false);
+
+            match result {
+                Ok(updates) if updates.is_empty() => show_no_firmware_updates(&window),
+                Ok(updates) => show_firmware_selection(&window, updates),
+                Err(e) => {
+                    warn!("Failed to check for firmware updates: {:#}", e);
+                    show_error(
+                        &window,
+                        &format!("Couldn't check for firmware updates.\n\n{:#}", e),
+                    );
+                }
+            }
+            glib::ControlFlow::Break
+        });
+    });
+}
+
+/// Tell the user every device is already up to date.
+fn show_no_firmware_updates(window: &ApplicationWindow) {
+    let dialog = adw::AlertDialog::builder()
+        .heading("No Firmware Updates")
+        .body("All supported devices are running the latest firmware available.")
+        .build();
+    dialog.add_response("close", "Close");
+    dialog.present(Some(window));
+}
+
+/// Let the user pick which devices to update, then update them.
+fn show_firmware_selection(window: &ApplicationWindow, updates: Vec<FirmwareUpdate>) {
+    let mut config =
+        SelectionDialogConfig::new("Firmware Updates", "Select the devices to update.")
+            .selection_type(SelectionType::Multi)
+            .selection_required(true)
+            .confirm_label("Update");
+
+    for update in &updates {
+        config = config.add_option(
+            SelectionOption::new(
+                &update.device_id,
+                &update.name,
+                &format!("{} → {}", update.current_version, update.new_version),
+                false,
+            )
+            .selected(true),
+        );
+    }
+
+    let window_clone = window.clone();
+    show_selection_dialog(window.upcast_ref(), config, move |selected| {
+        let chosen: Vec<FirmwareUpdate> = updates
+            .iter()
+            .filter(|update| selected.contains(&update.device_id))
+            .cloned()
+            .collect();
+
+        let window = window_clone.clone();
+        show_warning_confirmation(
+            window_clone.upcast_ref(),
+            "Update Firmware",
+            "Do <span weight=\"bold\">not</span> power off, unplug or suspend the computer \
+             while firmware is being written. Laptops should be plugged in.\n\n\
+             Some devices finish updating on the next reboot.",
+            move || {
+                let mut commands = CommandSequence::new();
+                for update in &chosen {
+                    commands = commands.then(
+                        Command::builder()
+                            .privileged()
+                            .program("fwupdmgr")
+                            .args(&[
+                                "update",
+                                &update.device_id,
+                                "--assume-yes",
+                                "--no-reboot-check",
+                            ])
+                            .description(&format!(
+                                "Updating {} firmware to {}...",
+                                update.name, update.new_version
+                            ))
+                            .build(),
+                    );
+                }
+
+                task_runner::run(window.upcast_ref(), commands.build(), "Firmware Updates");
+            },
+        );
+    });
+}
+
 fn setup_zenergy(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_zenergy");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_zenergy") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -233,20 +459,52 @@ fn setup_zenergy(builder: &Builder, window: &ApplicationWindow) {
     });
 }
 
-fn setup_nvidia_legacy(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_nvidia_legacy");
+/// Packages of the NVIDIA legacy (580xx) driver branch.
+const NVIDIA_LEGACY_PACKAGES: &[&str] = &[
+    "lib32-nvidia-580xx-utils",
+    "lib32-opencl-nvidia-580xx",
+    "nvidia-580xx-dkms",
+    "nvidia-580xx-utils",
+    "opencl-nvidia-580xx",
+];
+
+fn setup_nvidia_legacy(builder: &Builder, window: &ApplicationWindow, has_nvidia: bool) {
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_nvidia_legacy") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
         info!("Nvidia Legacy Drivers button clicked");
 
+        let mut message = String::from(
+            "This is only intended for <span foreground=\"red\" weight=\"bold\">GTX900/1000</span> Series Legacy GPUs\n\
+            For <span foreground=\"cyan\" weight=\"bold\">RTX/Turing+</span> GPUs download the <span foreground=\"green\" weight=\"bold\">nVidia</span> ISO instead.\n\n\
+            <span foreground=\"red\" weight=\"bold\">No Support/Help</span> will be provided for those Legacy GPUs !",
+        );
+        if !has_nvidia {
+            message.push_str(&format!(
+                "\n\n<span foreground=\"orange\" weight=\"bold\">{}</span>",
+                NO_NVIDIA_NOTICE
+            ));
+        }
+
+        let installed = core::are_packages_installed(NVIDIA_LEGACY_PACKAGES);
+        let missing = core::missing_packages(NVIDIA_LEGACY_PACKAGES, &installed);
+        let installed_count = NVIDIA_LEGACY_PACKAGES.len() - missing.len();
+        if installed_count > 0 {
+            message.push_str(&format!(
+                "\n\n{} of {} driver packages already installed.",
+                installed_count,
+                NVIDIA_LEGACY_PACKAGES.len()
+            ));
+        }
+
         let window_clone = window.clone();
         show_warning_confirmation(
             window.upcast_ref(),
             "Nvidia Legacy Drivers",
-            "This is only intended for <span foreground=\"red\" weight=\"bold\">GTX900/1000</span> Series Legacy GPUs\n\
-            For <span foreground=\"cyan\" weight=\"bold\">RTX/Turing+</span> GPUs download the <span foreground=\"green\" weight=\"bold\">nVidia</span> ISO instead.\n\n\
-            <span foreground=\"red\" weight=\"bold\">No Support/Help</span> will be provided for those Legacy GPUs !",
+            &message,
             move || {
                 // Use configured path
                 let script_dir = crate::config::paths::scripts();
@@ -256,23 +514,21 @@ fn setup_nvidia_legacy(builder: &Builder, window: &ApplicationWindow) {
                     .to_string_lossy()
                     .into_owned();
 
-                let commands = CommandSequence::new()
-                    .then(
+                // Only request missing packages; --needed still guards against races
+                let mut commands = CommandSequence::new();
+                if !missing.is_empty() {
+                    let mut args = vec!["-S", "--noconfirm", "--needed"];
+                    args.extend(missing);
+                    commands = commands.then(
                         Command::builder()
                             .aur()
-                            .args(&[
-                                "-S",
-                                "--noconfirm",
-                                "--needed",
-                                "lib32-nvidia-580xx-utils",
-                                "lib32-opencl-nvidia-580xx",
-                                "nvidia-580xx-dkms",
-                                "nvidia-580xx-utils",
-                                "opencl-nvidia-580xx",
-                            ])
+                            .args(&args)
                             .description("Installing Nvidia Legacy Drivers...")
                             .build(),
-                    )
+                    );
+                }
+
+                let commands = commands
                     .then(
                         Command::builder()
                             .privileged()
@@ -323,7 +579,9 @@ fn setup_nvidia_legacy(builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_rocm(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_rocm");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_rocm") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -349,34 +607,40 @@ fn setup_rocm(builder: &Builder, window: &ApplicationWindow) {
     });
 }
 
-fn setup_cuda(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_cuda");
+fn setup_cuda(builder: &Builder, window: &ApplicationWindow, has_nvidia: bool) {
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_cuda") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
         info!("NVIDIA CUDA button clicked");
 
         // Show selection dialog for CUDA version
-        let window_clone = window.clone();
-        let config = SelectionDialogConfig::new(
-            "NVIDIA CUDA Toolkit",
+        let mut description = String::from(
             "Select the CUDA version to install. The latest version is recommended for most users.",
-        )
-        .selection_type(SelectionType::Single)
-        .selection_required(true)
-        .add_option(SelectionOption::new(
-            "cuda",
-            "CUDA (Latest)",
-            "Install the latest CUDA toolkit from official repositories",
-            core::is_package_installed("cuda"),
-        ))
-        .add_option(SelectionOption::new(
-            "cuda-12.9",
-            "CUDA 12.9",
-            "Install CUDA Toolkit version 12.9 specifically",
-            core::is_package_installed("cuda-12.9"),
-        ))
-        .confirm_label("Install");
+        );
+        if !has_nvidia {
+            description.push_str(&format!("\n\n{}", NO_NVIDIA_NOTICE));
+        }
+
+        let window_clone = window.clone();
+        let config = SelectionDialogConfig::new("NVIDIA CUDA Toolkit", &description)
+            .selection_type(SelectionType::Single)
+            .selection_required(true)
+            .add_option(SelectionOption::new(
+                "cuda",
+                "CUDA (Latest)",
+                "Install the latest CUDA toolkit from official repositories",
+                core::is_package_installed("cuda"),
+            ))
+            .add_option(SelectionOption::new(
+                "cuda-12.9",
+                "CUDA 12.9",
+                "Install CUDA Toolkit version 12.9 specifically",
+                core::is_package_installed("cuda-12.9"),
+            ))
+            .confirm_label("Install");
 
         show_selection_dialog(window.upcast_ref(), config, move |selected| {
             if let Some(package) = selected.first() {
@@ -396,3 +660,57 @@ fn setup_cuda(builder: &Builder, window: &ApplicationWindow) {
         });
     });
 }
+
+fn setup_amd_gpu_tools(builder: &Builder, window: &ApplicationWindow, has_amd: bool) {
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_amd_gpu_tools") else {
+        return;
+    };
+    button.set_visible(has_amd);
+    let window = window.clone();
+
+    button.connect_clicked(move |_| {
+        info!("AMD GPU Tools button clicked");
+
+        let polkit_script = crate::config::paths::scripts()
+            .join("corectrl_polkit.sh")
+            .to_string_lossy()
+            .into_owned();
+
+        let commands = CommandSequence::new()
+            .then(
+                Command::builder()
+                    .aur()
+                    .args(&[
+                        "-S",
+                        "--noconfirm",
+                        "--needed",
+                        "mesa",
+                        "lib32-mesa",
+                        "vulkan-radeon",
+                        "lib32-vulkan-radeon",
+                        "vulkan-icd-loader",
+                        "lib32-vulkan-icd-loader",
+                    ])
+                    .description("Installing Mesa and RADV Vulkan drivers...")
+                    .build(),
+            )
+            .then(
+                Command::builder()
+                    .aur()
+                    .args(&["-S", "--noconfirm", "--needed", "corectrl"])
+                    .description("Installing CoreCtrl...")
+                    .build(),
+            )
+            .then(
+                Command::builder()
+                    .privileged()
+                    .program("bash")
+                    .args(&[&polkit_script])
+                    .description("Installing CoreCtrl polkit rule...")
+                    .build(),
+            )
+            .build();
+
+        task_runner::run(window.upcast_ref(), commands, "Install AMD GPU Tools");
+    });
+}
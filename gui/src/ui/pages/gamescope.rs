@@ -1,8 +1,14 @@
 //! Gamescope configuration page.
 //!
 //! Handles the logic for the Gamescope command generator.
-
-use crate::ui::utils::extract_widget;
+//! Numeric fields are validated as you type; invalid values are highlighted
+//! and left out of the generated command. The command can be test-launched
+//! with a demo app before pasting it into Steam.
+
+use crate::ui::dialogs::terminal;
+use crate::ui::utils::{
+    command_exists, extract_widget, is_positive_integer, is_unit_fraction, show_toast,
+};
 use adw::prelude::*;
 use adw::{ComboRow, EntryRow};
 use gtk4::{ApplicationWindow, Builder, Button, StringObject, Switch};
@@ -10,15 +16,12 @@ use log::info;
 use std::rc::Rc;
 
 /// Set up all handlers for the gamescope page.
-pub fn setup_handlers(
-    page_builder: &Builder,
-    _main_builder: &Builder,
-    _window: &ApplicationWindow,
-) {
+pub fn setup_handlers(page_builder: &Builder, main_builder: &Builder, window: &ApplicationWindow) {
     let widgets = Rc::new(extract_all_widgets(page_builder));
 
     connect_widget_signals(&widgets);
-    setup_copy_button(page_builder, &widgets);
+    setup_copy_button(&widgets, main_builder);
+    setup_test_launch_button(&widgets, window);
 
     // Generate initial command
     update_command_output(&widgets);
@@ -54,6 +57,10 @@ fn extract_all_widgets(builder: &Builder) -> GamescopeWidgets {
         // Backend / HDR / Misc
         combo_backend: extract_widget(builder, "combo_backend"),
         check_hdr_enabled: extract_widget(builder, "check_hdr_enabled"),
+        check_hdr_itm_enable: extract_widget(builder, "check_hdr_itm_enable"),
+        entry_hdr_itm_target_nits: extract_widget(builder, "entry_hdr_itm_target_nits"),
+        entry_sdr_gamut_wideness: extract_widget(builder, "entry_sdr_gamut_wideness"),
+        entry_nested_unfocused_refresh: extract_widget(builder, "entry_nested_unfocused_refresh"),
         entry_cursor_path: extract_widget(builder, "entry_cursor_path"),
         entry_framerate_limit: extract_widget(builder, "entry_framerate_limit"),
 
@@ -65,6 +72,8 @@ fn extract_all_widgets(builder: &Builder) -> GamescopeWidgets {
 
         // Output
         text_command_output: extract_widget(builder, "text_command_output"),
+        btn_copy_command: extract_widget(builder, "btn_copy_command"),
+        btn_test_launch: extract_widget(builder, "btn_test_launch"),
     }
 }
 
@@ -80,6 +89,9 @@ fn connect_widget_signals(widgets: &Rc<GamescopeWidgets>) {
     connect_entry_signal(widgets, &widgets.entry_fsr_sharpness);
     connect_entry_signal(widgets, &widgets.entry_cursor_path);
     connect_entry_signal(widgets, &widgets.entry_framerate_limit);
+    connect_entry_signal(widgets, &widgets.entry_hdr_itm_target_nits);
+    connect_entry_signal(widgets, &widgets.entry_sdr_gamut_wideness);
+    connect_entry_signal(widgets, &widgets.entry_nested_unfocused_refresh);
     connect_entry_signal(widgets, &widgets.entry_extra_flags);
 
     // Connect switches
@@ -91,6 +103,7 @@ fn connect_widget_signals(widgets: &Rc<GamescopeWidgets>) {
     connect_switch_signal(widgets, &widgets.check_expose_wayland);
     connect_switch_signal(widgets, &widgets.check_force_windows_fullscreen);
     connect_switch_signal(widgets, &widgets.check_hdr_enabled);
+    connect_switch_signal(widgets, &widgets.check_hdr_itm_enable);
     connect_switch_signal(widgets, &widgets.check_debug_layers);
     connect_switch_signal(widgets, &widgets.check_mangoapp);
     connect_switch_signal(widgets, &widgets.check_realtime);
@@ -126,23 +139,58 @@ fn connect_combo_signal(widgets: &Rc<GamescopeWidgets>, combo: &ComboRow) {
 }
 
 /// Set up the copy button to copy the command to clipboard.
-fn setup_copy_button(builder: &Builder, widgets: &Rc<GamescopeWidgets>) {
-    let btn_copy_command = extract_widget::<Button>(builder, "btn_copy_command");
+fn setup_copy_button(widgets: &Rc<GamescopeWidgets>, main_builder: &Builder) {
     let text_output = widgets.text_command_output.clone();
-    btn_copy_command.connect_clicked(move |_| {
+    let main_builder = main_builder.clone();
+    widgets.btn_copy_command.connect_clicked(move |_| {
         let text = text_output.text();
         if let Some(display) = gtk4::gdk::Display::default() {
             let clipboard = display.clipboard();
             clipboard.set(&text);
             info!("Copied gamescope command to clipboard");
+            show_toast(&main_builder, "Command copied to clipboard");
         }
     });
 }
 
+/// Set up the test launch button to run the command with a demo app.
+fn setup_test_launch_button(widgets: &Rc<GamescopeWidgets>, window: &ApplicationWindow) {
+    let text_output = widgets.text_command_output.clone();
+    let window = window.clone();
+    widgets.btn_test_launch.connect_clicked(move |_| {
+        // vkcube exercises the Vulkan path games use; glxgears is the fallback
+        let test_app = if command_exists("vkcube") {
+            "vkcube"
+        } else {
+            "glxgears"
+        };
+        let command = text_output
+            .text()
+            .replace("-- %command%", &format!("-- {}", test_app));
+
+        info!("Test launching gamescope command: {}", command);
+        terminal::show_terminal_dialog(
+            window.upcast_ref(),
+            "Gamescope Test Launch",
+            "sh",
+            &["-c", &command],
+            false,
+        );
+    });
+}
+
 /// Update the command output field with the generated command.
 fn update_command_output(widgets: &GamescopeWidgets) {
     let command = build_gamescope_command(widgets);
     widgets.text_command_output.set_text(&command);
+
+    // Don't let an incomplete command be copied while a field is invalid
+    let all_valid = widgets
+        .numeric_entries()
+        .iter()
+        .all(|entry| !entry.has_css_class("error"));
+    widgets.btn_copy_command.set_sensitive(all_valid);
+    widgets.btn_test_launch.set_sensitive(all_valid);
 }
 
 /// All widgets needed for command generation
@@ -165,6 +213,10 @@ struct GamescopeWidgets {
     check_force_windows_fullscreen: Switch,
     combo_backend: ComboRow,
     check_hdr_enabled: Switch,
+    check_hdr_itm_enable: Switch,
+    entry_hdr_itm_target_nits: EntryRow,
+    entry_sdr_gamut_wideness: EntryRow,
+    entry_nested_unfocused_refresh: EntryRow,
     entry_cursor_path: EntryRow,
     entry_framerate_limit: EntryRow,
     check_debug_layers: Switch,
@@ -172,6 +224,27 @@ struct GamescopeWidgets {
     check_realtime: Switch,
     entry_extra_flags: EntryRow,
     text_command_output: EntryRow,
+    btn_copy_command: Button,
+    btn_test_launch: Button,
+}
+
+impl GamescopeWidgets {
+    /// Entries that only accept numbers.
+    fn numeric_entries(&self) -> [&EntryRow; 11] {
+        [
+            &self.entry_output_width,
+            &self.entry_output_height,
+            &self.entry_max_scale,
+            &self.entry_nested_width,
+            &self.entry_nested_height,
+            &self.entry_nested_refresh,
+            &self.entry_fsr_sharpness,
+            &self.entry_framerate_limit,
+            &self.entry_hdr_itm_target_nits,
+            &self.entry_sdr_gamut_wideness,
+            &self.entry_nested_unfocused_refresh,
+        ]
+    }
 }
 
 /// Build the gamescope command from widget values
@@ -195,14 +268,39 @@ fn build_gamescope_command(widgets: &GamescopeWidgets) -> String {
 /// Add resolution and refresh rate flags.
 fn add_resolution_flags(parts: &mut Vec<String>, widgets: &GamescopeWidgets) {
     // Output (Visual)
-    add_flag_if_not_empty(parts, "-W", &widgets.entry_output_width.text());
-    add_flag_if_not_empty(parts, "-H", &widgets.entry_output_height.text());
-    add_flag_if_not_empty(parts, "-m", &widgets.entry_max_scale.text());
+    add_numeric_flag(
+        parts,
+        "-W",
+        &widgets.entry_output_width,
+        is_positive_integer,
+    );
+    add_numeric_flag(
+        parts,
+        "-H",
+        &widgets.entry_output_height,
+        is_positive_integer,
+    );
+    add_numeric_flag(parts, "-m", &widgets.entry_max_scale, is_positive_number);
 
     // Nested (Game)
-    add_flag_if_not_empty(parts, "-w", &widgets.entry_nested_width.text());
-    add_flag_if_not_empty(parts, "-h", &widgets.entry_nested_height.text());
-    add_flag_if_not_empty(parts, "-r", &widgets.entry_nested_refresh.text());
+    add_numeric_flag(
+        parts,
+        "-w",
+        &widgets.entry_nested_width,
+        is_positive_integer,
+    );
+    add_numeric_flag(
+        parts,
+        "-h",
+        &widgets.entry_nested_height,
+        is_positive_integer,
+    );
+    add_numeric_flag(
+        parts,
+        "-r",
+        &widgets.entry_nested_refresh,
+        is_positive_integer,
+    );
 }
 
 /// Add scaler and filter flags.
@@ -222,10 +320,11 @@ fn add_scaler_flags(parts: &mut Vec<String>, widgets: &GamescopeWidgets) {
     }
 
     // FSR sharpness
-    add_flag_if_not_empty(
+    add_numeric_flag(
         parts,
         "--fsr-sharpness",
-        &widgets.entry_fsr_sharpness.text(),
+        &widgets.entry_fsr_sharpness,
+        is_fsr_sharpness,
     );
 }
 
@@ -259,15 +358,37 @@ fn add_backend_flags(parts: &mut Vec<String>, widgets: &GamescopeWidgets) {
 
     // HDR
     add_switch_flag(parts, "--hdr-enabled", &widgets.check_hdr_enabled);
+    add_switch_flag(parts, "--hdr-itm-enable", &widgets.check_hdr_itm_enable);
+    add_numeric_flag(
+        parts,
+        "--hdr-itm-target-nits",
+        &widgets.entry_hdr_itm_target_nits,
+        is_positive_integer,
+    );
+    add_numeric_flag(
+        parts,
+        "--sdr-gamut-wideness",
+        &widgets.entry_sdr_gamut_wideness,
+        is_unit_fraction,
+    );
+
+    // Refresh rate while the nested window is unfocused
+    add_numeric_flag(
+        parts,
+        "--nested-unfocused-refresh",
+        &widgets.entry_nested_unfocused_refresh,
+        is_positive_integer,
+    );
 
     // Cursor
     add_flag_if_not_empty(parts, "--cursor", &widgets.entry_cursor_path.text());
 
     // Framerate limit
-    add_flag_if_not_empty(
+    add_numeric_flag(
         parts,
         "--framerate-limit",
-        &widgets.entry_framerate_limit.text(),
+        &widgets.entry_framerate_limit,
+        is_positive_integer,
     );
 }
 
@@ -293,6 +414,37 @@ fn add_flag_if_not_empty(parts: &mut Vec<String>, flag: &str, value: &str) {
     }
 }
 
+/// Add a flag with a numeric value from an entry.
+///
+/// Values that fail `is_valid` mark the entry with the `error` CSS class and
+/// are left out of the command.
+fn add_numeric_flag(
+    parts: &mut Vec<String>,
+    flag: &str,
+    entry: &EntryRow,
+    is_valid: fn(&str) -> bool,
+) {
+    let text = entry.text();
+    let value = text.trim();
+
+    if value.is_empty() || is_valid(value) {
+        entry.remove_css_class("error");
+        add_flag_if_not_empty(parts, flag, value);
+    } else {
+        entry.add_css_class("error");
+    }
+}
+
+/// Number greater than zero, fractions allowed (scale factors).
+fn is_positive_number(value: &str) -> bool {
+    value.parse::<f64>().is_ok_and(|n| n.is_finite() && n > 0.0)
+}
+
+/// FSR sharpness, from 0 (sharpest) to 20.
+fn is_fsr_sharpness(value: &str) -> bool {
+    value.parse::<u32>().is_ok_and(|n| n <= 20)
+}
+
 /// Add a flag if the switch is active.
 fn add_switch_flag(parts: &mut Vec<String>, flag: &str, switch: &Switch) {
     if switch.is_active() {
@@ -307,3 +459,31 @@ fn get_combo_value(combo: &ComboRow) -> Option<String> {
             .map(|obj| obj.string().to_string())
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_positive_number() {
+        for valid in ["1", "1.5", "0.25"] {
+            assert!(is_positive_number(valid), "{valid:?} should be valid");
+        }
+        for invalid in ["0", "-1.5", "inf", "NaN", "1,5", ""] {
+            assert!(
+                !is_positive_number(invalid),
+                "{invalid:?} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_fsr_sharpness() {
+        for valid in ["0", "5", "20"] {
+            assert!(is_fsr_sharpness(valid), "{valid:?} should be valid");
+        }
+        for invalid in ["21", "-1", "2.5", ""] {
+            assert!(!is_fsr_sharpness(invalid), "{invalid:?} should be invalid");
+        }
+    }
+}
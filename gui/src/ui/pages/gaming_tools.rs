@@ -4,14 +4,26 @@
 //! - Steam AiO installation
 //! - LACT GPU overclocking
 //! - Game launchers (Lutris, Heroic, Bottles)
+//! - ProtonUp-Qt compatibility tool manager
 //! - Controller tools
 //! - Falcond gaming utility
+//! - Sunshine game streaming host
 
+use crate::core;
+use crate::ui::dialogs::selection::{
+    show_selection_dialog, SelectionDialogConfig, SelectionOption, SelectionType,
+};
 use crate::ui::task_runner::{self, Command, CommandSequence};
-use crate::ui::utils::extract_widget;
-use gtk4::prelude::*;
-use gtk4::{ApplicationWindow, Builder, Button};
-use log::info;
+use crate::ui::utils::{download_size_hint, set_button_spinning, try_extract_widget};
+use adw::prelude::*;
+use gtk4::{glib, ApplicationWindow, Builder, Button};
+use log::{error, info};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::process::{Command as StdCommand, Stdio};
+use std::rc::Rc;
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::Duration;
 
 /// Set up all button handlers for the gaming tools page.
 pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &ApplicationWindow) {
@@ -20,100 +32,242 @@ pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &
     setup_lutris(page_builder, window);
     setup_heroic(page_builder, window);
     setup_bottles(page_builder, window);
+    setup_protonup(page_builder, window);
     setup_controller(page_builder, window);
     setup_falcond(page_builder, window);
+    setup_sunshine(page_builder, window);
 }
 
+/// Package groups offered by the Steam AiO dialog: (id, label, description, packages).
+const STEAM_AIO_GROUPS: &[(&str, &str, &str, &[&str])] = &[
+    (
+        "steam_core",
+        "Steam Core",
+        "Steam, ProtonPlus and the Vulkan loader",
+        &[
+            "steam",
+            "protonplus",
+            "vulkan-icd-loader",
+            "lib32-vulkan-icd-loader",
+        ],
+    ),
+    (
+        "lib32",
+        "32-bit Libraries",
+        "Graphics, audio and media libraries most games and Proton need",
+        &[
+            "giflib",
+            "lib32-giflib",
+            "libpng",
+            "lib32-libpng",
+            "libldap",
+            "lib32-libldap",
+            "gnutls",
+            "lib32-gnutls",
+            "mpg123",
+            "lib32-mpg123",
+            "openal",
+            "lib32-openal",
+            "v4l-utils",
+            "lib32-v4l-utils",
+            "libpulse",
+            "lib32-libpulse",
+            "libgpg-error",
+            "lib32-libgpg-error",
+            "alsa-plugins",
+            "lib32-alsa-plugins",
+            "alsa-lib",
+            "lib32-alsa-lib",
+            "libjpeg-turbo",
+            "lib32-libjpeg-turbo",
+            "sqlite",
+            "lib32-sqlite",
+            "libxcomposite",
+            "lib32-libxcomposite",
+            "libxinerama",
+            "lib32-libxinerama",
+            "libgcrypt",
+            "lib32-libgcrypt",
+            "ncurses",
+            "lib32-ncurses",
+            "ocl-icd",
+            "lib32-ocl-icd",
+            "libxslt",
+            "lib32-libxslt",
+            "libva",
+            "lib32-libva",
+            "gtk3",
+            "lib32-gtk3",
+            "gst-plugins-base-libs",
+            "lib32-gst-plugins-base-libs",
+            "opencl-icd-loader",
+            "lib32-opencl-icd-loader",
+        ],
+    ),
+    (
+        "wine",
+        "Wine & Compatibility",
+        "Wine, Gallium Nine, VKD3D, DOSBox and printing support",
+        &[
+            "wine-meta",
+            "wine-nine",
+            "vkd3d",
+            "lib32-vkd3d",
+            "cups",
+            "dosbox",
+        ],
+    ),
+    (
+        "overlays",
+        "MangoHud & Gamescope",
+        "Performance overlay, its configurator and the Gamescope compositor",
+        &["mangohud", "lib32-mangohud", "mangoverlay", "gamescope"],
+    ),
+    (
+        "fonts",
+        "Fonts",
+        "Liberation and WenQuanYi fonts so in-game text renders correctly",
+        &["ttf-liberation", "lib32-fontconfig", "wqy-zenhei"],
+    ),
+];
+
 fn setup_steam_aio(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_steam_aio");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_steam_aio") else {
+        return;
+    };
     let window = window.clone();
 
-    button.connect_clicked(move |_| {
+    button.connect_clicked(move |button| {
         info!("Steam AiO button clicked");
 
+        // Querying pacman and the download sizes can take a moment, so keep
+        // it off the main thread
+        set_button_spinning(button, true);
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let all_packages: Vec<&str> = STEAM_AIO_GROUPS
+                .iter()
+                .flat_map(|(_, _, _, packages)| packages.iter().copied())
+                .collect();
+            let installed = core::are_packages_installed(&all_packages);
+            let size_hints: Vec<Option<String>> = STEAM_AIO_GROUPS
+                .iter()
+                .map(|(_, _, _, packages)| {
+                    download_size_hint(&core::missing_packages(packages, &installed))
+                })
+                .collect();
+            let _ = tx.send((installed, size_hints));
+        });
+
+        let button = button.clone();
+        let window = window.clone();
+        glib::timeout_add_local(Duration::from_millis(50), move || {
+            let (installed, size_hints) = match rx.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                Err(TryRecvError::Disconnected) => (HashMap::new(), Vec::new()),
+            };
+            set_button_spinning(&button, false);
+            show_steam_aio_dialog(&window, installed, size_hints);
+            glib::ControlFlow::Break
+        });
+    });
+}
+
+/// Ask which Steam AiO components to install and install what's missing.
+///
+/// `size_hints` holds the download size of each group in [`STEAM_AIO_GROUPS`].
+fn show_steam_aio_dialog(
+    window: &ApplicationWindow,
+    installed: HashMap<String, bool>,
+    size_hints: Vec<Option<String>>,
+) {
+    let all_packages: Vec<&str> = STEAM_AIO_GROUPS
+        .iter()
+        .flat_map(|(_, _, _, packages)| packages.iter().copied())
+        .collect();
+    let installed_count =
+        all_packages.len() - core::missing_packages(&all_packages, &installed).len();
+
+    let mut config = SelectionDialogConfig::new(
+        "Steam AiO Installation",
+        &format!(
+            "Select the gaming components to install.\n{} of {} packages already installed.",
+            installed_count,
+            all_packages.len()
+        ),
+    )
+    .selection_type(SelectionType::Multi)
+    .selection_required(true)
+    .with_bulk_toggle(true)
+    .confirm_label("Install");
+
+    for (index, (id, label, description, packages)) in STEAM_AIO_GROUPS.iter().enumerate() {
+        let group_installed = core::missing_packages(packages, &installed).is_empty();
+        let description = match size_hints.get(index).cloned().flatten() {
+            Some(size) if !group_installed => format!("{} ({})", description, size),
+            _ => description.to_string(),
+        };
+
+        config = config.add_option(SelectionOption::new(
+            id,
+            label,
+            &description,
+            group_installed,
+        ));
+    }
+
+    let window_clone = window.clone();
+    show_selection_dialog(window.upcast_ref(), config, move |selected| {
+        let selected_packages: Vec<&str> = STEAM_AIO_GROUPS
+            .iter()
+            .filter(|(id, _, _, _)| selected.iter().any(|s| s == id))
+            .flat_map(|(_, _, _, packages)| packages.iter().copied())
+            .collect();
+
+        // Only request what's missing; --needed still guards against races
+        let missing = core::missing_packages(&selected_packages, &installed);
+        if missing.is_empty() {
+            info!("All selected Steam AiO packages are already installed");
+            let dialog = adw::AlertDialog::builder()
+                .heading("Nothing to Install")
+                .body("All packages in the selected components are already installed.")
+                .build();
+            dialog.add_response("close", "Close");
+            dialog.present(Some(&window_clone));
+            return;
+        }
+
+        info!(
+            "Installing {} of {} selected Steam AiO packages",
+            missing.len(),
+            selected_packages.len()
+        );
+        let mut args = vec!["-S", "--noconfirm", "--needed"];
+        args.extend(missing);
+
         let commands = CommandSequence::new()
             .then(
                 Command::builder()
                     .aur()
-                    .args(&[
-                        "-S",
-                        "--noconfirm",
-                        "--needed",
-                        "steam",
-                        "gamescope",
-                        "mangohud",
-                        "mangoverlay",
-                        "protonplus",
-                        "lib32-mangohud",
-                        "wine-meta",
-                        "wine-nine",
-                        "ttf-liberation",
-                        "lib32-fontconfig",
-                        "wqy-zenhei",
-                        "vkd3d",
-                        "giflib",
-                        "lib32-giflib",
-                        "libpng",
-                        "lib32-libpng",
-                        "libldap",
-                        "lib32-libldap",
-                        "gnutls",
-                        "lib32-gnutls",
-                        "mpg123",
-                        "lib32-mpg123",
-                        "openal",
-                        "lib32-openal",
-                        "v4l-utils",
-                        "lib32-v4l-utils",
-                        "libpulse",
-                        "lib32-libpulse",
-                        "libgpg-error",
-                        "lib32-libgpg-error",
-                        "alsa-plugins",
-                        "lib32-alsa-plugins",
-                        "alsa-lib",
-                        "lib32-alsa-lib",
-                        "libjpeg-turbo",
-                        "lib32-libjpeg-turbo",
-                        "sqlite",
-                        "lib32-sqlite",
-                        "libxcomposite",
-                        "lib32-libxcomposite",
-                        "libxinerama",
-                        "lib32-libgcrypt",
-                        "libgcrypt",
-                        "lib32-libxinerama",
-                        "ncurses",
-                        "lib32-ncurses",
-                        "ocl-icd",
-                        "lib32-ocl-icd",
-                        "libxslt",
-                        "lib32-libxslt",
-                        "libva",
-                        "lib32-libva",
-                        "gtk3",
-                        "lib32-gtk3",
-                        "gst-plugins-base-libs",
-                        "lib32-gst-plugins-base-libs",
-                        "vulkan-icd-loader",
-                        "lib32-vulkan-icd-loader",
-                        "cups",
-                        "dosbox",
-                        "lib32-opencl-icd-loader",
-                        "lib32-vkd3d",
-                        "opencl-icd-loader",
-                    ])
+                    .args(&args)
                     .description("Installing Steam and gaming dependencies...")
                     .build(),
             )
             .build();
 
-        task_runner::run(window.upcast_ref(), commands, "Steam AiO Installation");
+        task_runner::run(
+            window_clone.upcast_ref(),
+            commands,
+            "Steam AiO Installation",
+        );
     });
 }
 
 fn setup_lact_oc(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_lact_oc");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_lact_oc") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -141,96 +295,198 @@ fn setup_lact_oc(builder: &Builder, window: &ApplicationWindow) {
     });
 }
 
+/// Vulkan layers installed alongside the flatpak launchers that run games.
+const FLATPAK_VULKAN_LAYERS: &[&str] = &[
+    "org.freedesktop.Platform.VulkanLayer.gamescope/x86_64/25.08",
+    "org.freedesktop.Platform.VulkanLayer.MangoHud/x86_64/25.08",
+];
+
 fn setup_lutris(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_lutris");
-    let window = window.clone();
+    setup_flatpak_launcher(
+        builder,
+        window,
+        "btn_lutris",
+        "Lutris",
+        "net.lutris.Lutris",
+        "Installing Lutris and Vulkan layers...",
+        "Lutris Installation",
+        true,
+    );
+}
 
-    button.connect_clicked(move |_| {
-        info!("Lutris button clicked");
+fn setup_heroic(builder: &Builder, window: &ApplicationWindow) {
+    setup_flatpak_launcher(
+        builder,
+        window,
+        "btn_heroic",
+        "Heroic",
+        "com.heroicgameslauncher.hgl",
+        "Installing Heroic Games Launcher and Vulkan layers...",
+        "Heroic Launcher Installation",
+        true,
+    );
+}
 
-        let commands = CommandSequence::new()
-            .then(
-                Command::builder()
-                    .normal()
-                    .program("flatpak")
-                    .args(&[
-                        "install",
-                        "-y",
-                        "net.lutris.Lutris",
-                        "org.freedesktop.Platform.VulkanLayer.gamescope/x86_64/25.08",
-                        "org.freedesktop.Platform.VulkanLayer.MangoHud/x86_64/25.08",
-                    ])
-                    .description("Installing Lutris and Vulkan layers...")
-                    .build(),
-            )
-            .build();
+fn setup_bottles(builder: &Builder, window: &ApplicationWindow) {
+    setup_flatpak_launcher(
+        builder,
+        window,
+        "btn_bottles",
+        "Bottles",
+        "com.usebottles.bottles",
+        "Installing Bottles and Vulkan layers...",
+        "Bottles Installation",
+        true,
+    );
+}
 
-        task_runner::run(window.upcast_ref(), commands, "Lutris Installation");
-    });
+fn setup_protonup(builder: &Builder, window: &ApplicationWindow) {
+    setup_flatpak_launcher(
+        builder,
+        window,
+        "btn_protonup",
+        "ProtonUp-Qt",
+        "net.davidotek.pupgui2",
+        "Installing ProtonUp-Qt...",
+        "ProtonUp-Qt Installation",
+        false,
+    );
 }
 
-fn setup_heroic(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_heroic");
-    let window = window.clone();
+/// Helper to switch a launcher button between installing and launching its flatpak
+fn update_launcher_state(button: &Button, name: &str, is_installed: bool) {
+    if is_installed {
+        button.set_label(&format!("Launch {}", name));
+        button.add_css_class("suggested-action");
+    } else {
+        button.set_label(name);
+        button.remove_css_class("suggested-action");
+    }
+}
 
-    button.connect_clicked(move |_| {
-        info!("Heroic button clicked");
+/// Install state of a flatpak launcher button, cached so clicks and focus
+/// changes never wait on `flatpak`.
+#[derive(Clone)]
+struct LauncherState {
+    button: Button,
+    name: &'static str,
+    app_id: &'static str,
+    installed: Rc<Cell<bool>>,
+    checking: Rc<Cell<bool>>,
+}
 
-        let commands = CommandSequence::new()
-            .then(
-                Command::builder()
-                    .normal()
-                    .program("flatpak")
-                    .args(&[
-                        "install",
-                        "-y",
-                        "com.heroicgameslauncher.hgl",
-                        "org.freedesktop.Platform.VulkanLayer.gamescope/x86_64/25.08",
-                        "org.freedesktop.Platform.VulkanLayer.MangoHud/x86_64/25.08",
-                    ])
-                    .description("Installing Heroic Games Launcher...")
-                    .build(),
-            )
-            .build();
+impl LauncherState {
+    /// Check in the background whether the flatpak is installed, then update
+    /// the cache and the button. Does nothing while a check is running.
+    fn refresh(&self) {
+        if self.checking.replace(true) {
+            return;
+        }
 
-        task_runner::run(
-            window.upcast_ref(),
-            commands,
-            "Heroic Launcher Installation",
-        );
-    });
+        let app_id = self.app_id;
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(core::is_flatpak_installed(app_id));
+        });
+
+        let state = self.clone();
+        glib::timeout_add_local(Duration::from_millis(50), move || {
+            let installed = match rx.try_recv() {
+                Ok(installed) => installed,
+                Err(TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                Err(TryRecvError::Disconnected) => state.installed.get(),
+            };
+            state.checking.set(false);
+            state.installed.set(installed);
+            update_launcher_state(&state.button, state.name, installed);
+            glib::ControlFlow::Break
+        });
+    }
 }
 
-fn setup_bottles(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_bottles");
-    let window = window.clone();
+/// Wire up a game launcher button that installs a flatpak, or launches it once present.
+///
+/// `vulkan_layers` also installs [`FLATPAK_VULKAN_LAYERS`], for launchers
+/// that run games themselves.
+#[allow(clippy::too_many_arguments)]
+fn setup_flatpak_launcher(
+    builder: &Builder,
+    window: &ApplicationWindow,
+    button_id: &str,
+    name: &'static str,
+    app_id: &'static str,
+    description: &'static str,
+    title: &'static str,
+    vulkan_layers: bool,
+) {
+    let Some(button) = try_extract_widget::<Button>(builder, button_id) else {
+        return;
+    };
+
+    let state = LauncherState {
+        button: button.clone(),
+        name,
+        app_id,
+        installed: Rc::new(Cell::new(false)),
+        checking: Rc::new(Cell::new(false)),
+    };
 
+    // Initial check
+    state.refresh();
+
+    // Update on window focus (e.g. after installing or removing it elsewhere)
+    let state_clone = state.clone();
+    window.connect_is_active_notify(move |window| {
+        if window.is_active() {
+            state_clone.refresh();
+        }
+    });
+
+    let window = window.clone();
     button.connect_clicked(move |_| {
-        info!("Bottles button clicked");
+        info!("{} button clicked", name);
+
+        if state.installed.get() {
+            info!("Launching {}...", app_id);
+            if let Err(e) = StdCommand::new("flatpak")
+                .args(["run", app_id])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+            {
+                error!("Failed to launch {}: {}", app_id, e);
+            }
+            return;
+        }
+
+        let mut args = vec!["install", "-y", app_id];
+        if vulkan_layers {
+            args.extend_from_slice(FLATPAK_VULKAN_LAYERS);
+        }
 
         let commands = CommandSequence::new()
             .then(
                 Command::builder()
                     .normal()
                     .program("flatpak")
-                    .args(&[
-                        "install",
-                        "-y",
-                        "com.usebottles.bottles",
-                        "org.freedesktop.Platform.VulkanLayer.gamescope/x86_64/25.08",
-                        "org.freedesktop.Platform.VulkanLayer.MangoHud/x86_64/25.08",
-                    ])
-                    .description("Installing Bottles and Vulkan layers...")
+                    .args(&args)
+                    .description(description)
                     .build(),
             )
             .build();
 
-        task_runner::run(window.upcast_ref(), commands, "Bottles Installation");
+        let state = state.clone();
+        task_runner::run_with_on_complete(window.upcast_ref(), commands, title, move |_| {
+            state.refresh()
+        });
     });
 }
 
 fn setup_controller(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_controller");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_controller") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -264,7 +520,9 @@ fn setup_controller(builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_falcond(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_falcond");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_falcond") else {
+        return;
+    };
     let window = window.clone();
 
     let env = crate::config::env::get();
@@ -302,6 +560,7 @@ fn setup_falcond(builder: &Builder, window: &ApplicationWindow) {
                     .program("usermod")
                     .args(&["-aG", "falcond", &user])
                     .description("Adding your user to falcond group...")
+                    .requires_relogin()
                     .build(),
             )
             .then(
@@ -341,3 +600,124 @@ fn setup_falcond(builder: &Builder, window: &ApplicationWindow) {
         task_runner::run(window.upcast_ref(), commands, "Falcond Installation");
     });
 }
+
+/// Web interface Sunshine is configured through.
+const SUNSHINE_WEB_UI: &str = "https://localhost:47990";
+
+/// Ports Moonlight clients connect to Sunshine on.
+const SUNSHINE_TCP_PORTS: &[&str] = &["47984", "47989", "47990", "48010"];
+const SUNSHINE_UDP_PORTS: &[&str] = &["47998-48000", "48002", "48010"];
+
+fn setup_sunshine(builder: &Builder, window: &ApplicationWindow) {
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_sunshine") else {
+        return;
+    };
+    let window = window.clone();
+
+    button.connect_clicked(move |_| {
+        info!("Sunshine button clicked");
+
+        let Some(firewall) = core::detect_active_firewall() else {
+            run_sunshine_setup(&window, None);
+            return;
+        };
+
+        // Clients can't reach Sunshine through a firewall without these ports
+        let config = SelectionDialogConfig::new(
+            "Sunshine Game Streaming",
+            &format!(
+                "{} is active. Moonlight clients need TCP {} and UDP {} to reach Sunshine.",
+                firewall.name(),
+                SUNSHINE_TCP_PORTS.join(", "),
+                SUNSHINE_UDP_PORTS.join(", ")
+            ),
+        )
+        .selection_required(false)
+        .add_option(
+            SelectionOption::new(
+                "open_ports",
+                "Open Sunshine ports",
+                &format!("Allow the streaming ports in {}", firewall.name()),
+                false,
+            )
+            .selected(true),
+        )
+        .confirm_label("Install");
+
+        let window_clone = window.clone();
+        show_selection_dialog(window.upcast_ref(), config, move |selected| {
+            let open_ports = selected.iter().any(|id| id == "open_ports");
+            run_sunshine_setup(&window_clone, open_ports.then_some(firewall));
+        });
+    });
+}
+
+/// Install Sunshine, enable its user service and open the web config once done.
+fn run_sunshine_setup(window: &ApplicationWindow, open_ports_in: Option<core::Firewall>) {
+    let mut commands = CommandSequence::new()
+        .then(
+            Command::builder()
+                .aur()
+                .args(&["-S", "--noconfirm", "--needed", "sunshine"])
+                .description("Installing Sunshine streaming host...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .normal()
+                .program("systemctl")
+                .args(&["--user", "enable", "--now", "sunshine"])
+                .description("Enabling Sunshine user service...")
+                .build(),
+        );
+
+    if let Some(firewall) = open_ports_in {
+        commands = commands.then(
+            Command::builder()
+                .privileged()
+                .program("sh")
+                .args(&["-c", &firewall_open_ports_command(firewall)])
+                .description(&format!("Opening Sunshine ports in {}...", firewall.name()))
+                .build(),
+        );
+    }
+
+    let window_clone = window.clone();
+    task_runner::run_with_on_complete(
+        window.upcast_ref(),
+        commands.build(),
+        "Sunshine Setup",
+        move |success| {
+            if success {
+                crate::ui::dialogs::error::open_url_or_show_error(&window_clone, SUNSHINE_WEB_UI);
+            }
+        },
+    );
+}
+
+/// Shell command that opens the Sunshine ports in the given firewall.
+fn firewall_open_ports_command(firewall: core::Firewall) -> String {
+    match firewall {
+        core::Firewall::Firewalld => {
+            let ports: Vec<String> = SUNSHINE_TCP_PORTS
+                .iter()
+                .map(|port| format!("--add-port={}/tcp", port))
+                .chain(
+                    SUNSHINE_UDP_PORTS
+                        .iter()
+                        .map(|port| format!("--add-port={}/udp", port)),
+                )
+                .collect();
+            format!(
+                "firewall-cmd --permanent {} && firewall-cmd --reload",
+                ports.join(" ")
+            )
+        }
+        core::Firewall::Ufw => {
+            // ufw writes port ranges with a colon
+            let tcp = SUNSHINE_TCP_PORTS.join(",").replace('-', ":");
+            let udp = SUNSHINE_UDP_PORTS.join(",").replace('-', ":");
+            format!("ufw allow {}/tcp && ufw allow {}/udp", tcp, udp)
+        }
+    }
+}
@@ -4,39 +4,245 @@
 //! - Linux kernel installation and removal
 //! - Kernel headers management
 //! - Kernel listing and status
-
-use crate::ui::dialogs::warning::show_warning_confirmation;
+//! - Live filtering of the kernel lists
+//! - Short-lived caching of the repository package list
+//! - Grouping of available kernels by flavor
+//! - Choosing the default boot kernel (GRUB or systemd-boot)
+
+use crate::config::user::DismissableWarning;
+use crate::core;
+use crate::core::download::format_bytes;
+use crate::ui::dialogs::error::show_error;
+use crate::ui::dialogs::warning::{
+    show_dismissable_warning_confirmation, show_warning_confirmation,
+};
 use crate::ui::task_runner::{self, Command, CommandSequence};
-use crate::ui::utils::extract_widget;
+use crate::ui::utils::{extract_widget, set_button_spinning, try_extract_widget};
 use gtk4::glib;
 use gtk4::prelude::*;
-use gtk4::{ApplicationWindow, Box as GtkBox, Builder, Button, Image, Label, ListBox, Orientation};
+use gtk4::{
+    ApplicationWindow, Box as GtkBox, Builder, Button, Image, Label, ListBox, ListBoxRow,
+    Orientation, SearchEntry,
+};
 use log::{info, warn};
+use std::collections::HashMap;
 use std::process::{Command as StdCommand, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long a `pacman -Sl` snapshot is reused before querying pacman again.
+const SYNC_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Directory holding the sync databases; its mtime changes on `pacman -Sy`.
+const SYNC_DB_DIR: &str = "/var/lib/pacman/sync";
+
+/// Cached `linux*` packages from the sync databases, keyed on the sync state.
+struct SyncCache {
+    fetched_at: Instant,
+    sync_mtime: Option<SystemTime>,
+    packages: Arc<HashMap<String, String>>,
+}
+
+static SYNC_CACHE: Mutex<Option<SyncCache>> = Mutex::new(None);
+
+/// Bumped by every kernel scan, so a newer scan supersedes one in flight.
+static SCAN_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Check whether a newer scan has started since `generation`.
+fn is_stale_scan(generation: u64) -> bool {
+    SCAN_GENERATION.load(Ordering::SeqCst) != generation
+}
+
+/// Kernel flavor groups, in the order they are shown in the available list.
+const KERNEL_GROUPS: [&str; 6] = [
+    "Stable",
+    "LTS",
+    "Performance",
+    "Realtime",
+    "CachyOS",
+    "Other",
+];
+
+/// Name fragments identifying performance-tuned kernels.
+const PERFORMANCE_KERNELS: [&str; 5] = ["zen", "xanmod", "liquorix", "tkg", "clear"];
+
+/// A kernel package and its version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct KernelPackage {
+    name: String,
+    version: String,
+}
+
+impl KernelPackage {
+    /// Text shown in the kernel lists, e.g. "linux-zen 6.12.1.zen1-1".
+    fn display_text(&self) -> String {
+        format!("{} {}", self.name, self.version)
+    }
+}
+
+/// Bootloader whose default entry can be changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Bootloader {
+    Grub,
+    SystemdBoot,
+}
+
+impl Bootloader {
+    /// Display name of the bootloader.
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Grub => "GRUB",
+            Self::SystemdBoot => "systemd-boot",
+        }
+    }
+
+    /// Bootloader argument understood by `set_default_kernel.sh`.
+    fn script_arg(&self) -> &'static str {
+        match self {
+            Self::Grub => "grub",
+            Self::SystemdBoot => "systemd-boot",
+        }
+    }
+}
+
+/// Detect the bootloader from its configuration files.
+fn detect_bootloader() -> Option<Bootloader> {
+    use std::path::Path;
+
+    if Path::new("/etc/default/grub").exists() && Path::new("/boot/grub/grub.cfg").exists() {
+        return Some(Bootloader::Grub);
+    }
+
+    // Common ESP mount points
+    ["/boot", "/efi", "/boot/efi"]
+        .iter()
+        .any(|esp| Path::new(esp).join("loader/loader.conf").exists())
+        .then_some(Bootloader::SystemdBoot)
+}
 
 /// Set up all button handlers for the kernel manager page.
 pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &ApplicationWindow) {
     setup_kernel_lists(page_builder, window);
     setup_refresh_button(page_builder, window);
+    setup_kernel_filter(page_builder);
 }
 
 /// Initialize and populate kernel lists.
 fn setup_kernel_lists(builder: &Builder, window: &ApplicationWindow) {
+    let available_list = extract_widget::<ListBox>(builder, "available_kernels_list");
+    available_list.set_header_func(update_group_header);
+
     scan_and_populate_kernels(builder, window, None);
 }
 
 /// Set up refresh button to rescan kernels.
 fn setup_refresh_button(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_refresh_kernels");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_refresh_kernels") else {
+        return;
+    };
     let window = window.clone();
     let builder = builder.clone();
 
     button.connect_clicked(move |btn| {
         info!("Refresh kernels button clicked");
+        invalidate_sync_cache();
         scan_and_populate_kernels(&builder, &window, Some(btn));
     });
 }
 
+/// Set up the search entry that filters both kernel lists as the user types.
+fn setup_kernel_filter(builder: &Builder) {
+    let search_entry = extract_widget::<SearchEntry>(builder, "kernel_search_entry");
+    let lists = [
+        extract_widget::<ListBox>(builder, "installed_kernels_list"),
+        extract_widget::<ListBox>(builder, "available_kernels_list"),
+    ];
+
+    for list in &lists {
+        let entry = search_entry.clone();
+        list.set_filter_func(move |row| row_matches_filter(row, &entry.text()));
+    }
+
+    search_entry.connect_search_changed(move |_| {
+        for list in &lists {
+            list.invalidate_filter();
+        }
+    });
+}
+
+/// Check whether a kernel row matches the filter text.
+///
+/// Placeholder rows (plain labels) are always shown.
+fn row_matches_filter(row: &ListBoxRow, filter: &str) -> bool {
+    let filter = filter.trim().to_lowercase();
+    if filter.is_empty() {
+        return true;
+    }
+
+    row_label_text(row)
+        .map(|text| text.to_lowercase().contains(&filter))
+        .unwrap_or(true)
+}
+
+/// Text of the kernel label in a list row, or `None` for placeholder rows.
+fn row_label_text(row: &ListBoxRow) -> Option<String> {
+    let row_box = row.child().and_downcast::<GtkBox>()?;
+    let label = row_box.first_child().and_downcast::<Label>()?;
+    Some(label.text().to_string())
+}
+
+/// Get the flavor group a kernel belongs to, based on its package name.
+fn kernel_group(name: &str) -> &'static str {
+    if name.contains("cachyos") {
+        "CachyOS"
+    } else if name.contains("-rt") {
+        "Realtime"
+    } else if name.contains("lts") {
+        "LTS"
+    } else if PERFORMANCE_KERNELS.iter().any(|k| name.contains(k)) {
+        "Performance"
+    } else if name == "linux" {
+        "Stable"
+    } else {
+        "Other"
+    }
+}
+
+/// Position of a kernel's group in [`KERNEL_GROUPS`], used for sorting.
+fn kernel_group_index(name: &str) -> usize {
+    let group = kernel_group(name);
+    KERNEL_GROUPS
+        .iter()
+        .position(|g| *g == group)
+        .unwrap_or(KERNEL_GROUPS.len())
+}
+
+/// Add a group title above the first row of each kernel flavor group.
+fn update_group_header(row: &ListBoxRow, before: Option<&ListBoxRow>) {
+    let group_of = |row: &ListBoxRow| {
+        row_label_text(row).map(|text| {
+            let name = text.split_whitespace().next().unwrap_or_default();
+            kernel_group(name)
+        })
+    };
+
+    let group = group_of(row);
+    if group.is_none() || group == before.and_then(group_of) {
+        row.set_header(None::<&gtk4::Widget>);
+        return;
+    }
+
+    let header = Label::new(group);
+    header.set_xalign(0.0);
+    header.set_margin_start(12);
+    header.set_margin_end(12);
+    header.set_margin_top(12);
+    header.set_margin_bottom(4);
+    header.add_css_class("heading");
+    row.set_header(Some(&header));
+}
+
 /// Scan for available and installed kernels and populate lists.
 fn scan_and_populate_kernels(
     builder: &Builder,
@@ -48,31 +254,28 @@ fn scan_and_populate_kernels(
     let builder = builder.clone();
     let window = window.clone();
     let btn_opt = refresh_btn.cloned();
+    let generation = SCAN_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
 
     // Disable content while scanning
     let content_box = extract_widget::<GtkBox>(&builder, "content_box");
     content_box.set_sensitive(false);
+    show_scanning_placeholders(&builder);
 
     if let Some(btn) = refresh_btn {
-        btn.set_sensitive(false);
-        // Try to find image child to animate
-        if let Some(child) = btn.child() {
-            if let Some(img) = child.downcast_ref::<Image>() {
-                img.add_css_class("spinning");
-            } else if let Some(box_child) = child.downcast_ref::<GtkBox>() {
-                if let Some(img) = box_child.first_child().and_downcast::<Image>() {
-                    img.add_css_class("spinning");
-                }
-            }
-        }
+        set_button_spinning(btn, true);
     }
 
     // Use std::sync::mpsc for thread communication
-    let (sender, receiver) = std::sync::mpsc::channel::<(Vec<String>, Vec<String>)>();
+    let (sender, receiver) =
+        std::sync::mpsc::channel::<(Vec<KernelPackage>, Vec<KernelPackage>, Option<String>)>();
 
     // Run blocking operations in a separate thread
     std::thread::spawn(move || {
-        let available_result = get_available_kernels();
+        let available_result = get_sync_packages().map(|pkgs| get_available_kernels(&pkgs));
+        if is_stale_scan(generation) {
+            info!("Kernel scan superseded by a newer one");
+            return;
+        }
         let installed_result = get_installed_kernels();
 
         let available_kernels = match available_result {
@@ -91,22 +294,37 @@ fn scan_and_populate_kernels(
             }
         };
 
+        let running_kernel = get_running_kernel();
+
         info!(
-            "Found {} available kernels, {} installed",
+            "Found {} available kernels, {} installed (running: {})",
             available_kernels.len(),
-            installed_kernels.len()
+            installed_kernels.len(),
+            running_kernel.as_deref().unwrap_or("unknown")
         );
 
         // Send results back to main thread
-        let _ = sender.send((available_kernels, installed_kernels));
+        let _ = sender.send((available_kernels, installed_kernels, running_kernel));
     });
 
     // Poll for results in main thread
-    glib::timeout_add_local(
-        std::time::Duration::from_millis(100),
-        move || match receiver.try_recv() {
-            Ok((available_kernels, installed_kernels)) => {
-                populate_installed_list(&builder, &installed_kernels, &window);
+    glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+        // A newer scan owns the lists now; only hand back this scan's button
+        if is_stale_scan(generation) {
+            if let Some(btn) = &btn_opt {
+                set_button_spinning(btn, false);
+            }
+            return glib::ControlFlow::Break;
+        }
+
+        match receiver.try_recv() {
+            Ok((available_kernels, installed_kernels, running_kernel)) => {
+                populate_installed_list(
+                    &builder,
+                    &installed_kernels,
+                    running_kernel.as_deref(),
+                    &window,
+                );
                 populate_available_list(&builder, &available_kernels, &installed_kernels, &window);
                 update_status_labels(&builder, &available_kernels, &installed_kernels);
 
@@ -116,16 +334,7 @@ fn scan_and_populate_kernels(
 
                 // Restore button state
                 if let Some(btn) = &btn_opt {
-                    btn.set_sensitive(true);
-                    if let Some(child) = btn.child() {
-                        if let Some(img) = child.downcast_ref::<Image>() {
-                            img.remove_css_class("spinning");
-                        } else if let Some(box_child) = child.downcast_ref::<GtkBox>() {
-                            if let Some(img) = box_child.first_child().and_downcast::<Image>() {
-                                img.remove_css_class("spinning");
-                            }
-                        }
-                    }
+                    set_button_spinning(btn, false);
                 }
 
                 glib::ControlFlow::Break
@@ -133,31 +342,100 @@ fn scan_and_populate_kernels(
             Err(std::sync::mpsc::TryRecvError::Empty) => glib::ControlFlow::Continue,
             Err(std::sync::mpsc::TryRecvError::Disconnected) => {
                 warn!("Kernel scan thread disconnected");
+                for id in ["installed_kernels_list", "available_kernels_list"] {
+                    let list = extract_widget::<ListBox>(&builder, id);
+                    clear_list(&list);
+                    list.append(&placeholder_label(
+                        "Failed to scan kernels. Try refreshing.",
+                    ));
+                }
                 // Re-enable content even on failure
                 let content_box = extract_widget::<GtkBox>(&builder, "content_box");
                 content_box.set_sensitive(true);
                 if let Some(btn) = &btn_opt {
-                    btn.set_sensitive(true);
-                    if let Some(child) = btn.child() {
-                        if let Some(img) = child.downcast_ref::<Image>() {
-                            img.remove_css_class("spinning");
-                        } else if let Some(box_child) = child.downcast_ref::<GtkBox>() {
-                            if let Some(img) = box_child.first_child().and_downcast::<Image>() {
-                                img.remove_css_class("spinning");
-                            }
-                        }
-                    }
+                    set_button_spinning(btn, false);
                 }
                 glib::ControlFlow::Break
             }
-        },
-    );
+        }
+    });
 }
 
-/// Get list of available kernel packages from repositories.
-/// This function searches for kernel headers and then derives the kernel package names.
-/// Adapted from cachyos-kernel-manager logic.
-fn get_available_kernels() -> anyhow::Result<Vec<String>> {
+/// Remove every row from a kernel list.
+fn clear_list(list: &ListBox) {
+    while let Some(row) = list.first_child() {
+        list.remove(&row);
+    }
+}
+
+/// Build a dimmed message row shown in place of kernels.
+///
+/// Its child is a plain label, so filtering and group headers ignore it.
+fn placeholder_label(text: &str) -> Label {
+    let label = Label::new(Some(text));
+    label.add_css_class("dim-label");
+    label.set_margin_start(12);
+    label.set_margin_end(12);
+    label.set_margin_top(8);
+    label.set_margin_bottom(8);
+    label
+}
+
+/// Replace both kernel lists with a "Scanning…" row while a scan runs.
+///
+/// The spinner comes first in the row so it is treated as a placeholder too.
+fn show_scanning_placeholders(builder: &Builder) {
+    for id in ["installed_kernels_list", "available_kernels_list"] {
+        let list = extract_widget::<ListBox>(builder, id);
+        clear_list(&list);
+
+        let row_box = GtkBox::new(Orientation::Horizontal, 8);
+        row_box.set_halign(gtk4::Align::Center);
+        row_box.set_margin_top(8);
+        row_box.set_margin_bottom(8);
+
+        let spinner = Image::from_icon_name("circle-noth-symbolic");
+        spinner.add_css_class("spinning");
+        row_box.append(&spinner);
+
+        let label = Label::new(Some("Scanning…"));
+        label.add_css_class("dim-label");
+        row_box.append(&label);
+
+        list.append(&row_box);
+    }
+}
+
+/// Drop the cached repository package list so the next scan queries pacman.
+fn invalidate_sync_cache() {
+    if let Ok(mut cache) = SYNC_CACHE.lock() {
+        *cache = None;
+    }
+}
+
+/// Modification time of the sync database directory, if readable.
+fn sync_db_mtime() -> Option<SystemTime> {
+    std::fs::metadata(SYNC_DB_DIR)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+/// Get the `linux*` packages (name -> version) from the sync databases.
+///
+/// The result of `pacman -Sl` is reused for [`SYNC_CACHE_TTL`] as long as the
+/// sync databases haven't been refreshed in the meantime.
+fn get_sync_packages() -> anyhow::Result<Arc<HashMap<String, String>>> {
+    let sync_mtime = sync_db_mtime();
+
+    if let Ok(cache) = SYNC_CACHE.lock() {
+        if let Some(cached) = cache.as_ref() {
+            if cached.fetched_at.elapsed() < SYNC_CACHE_TTL && cached.sync_mtime == sync_mtime {
+                info!("Using cached repository package list");
+                return Ok(Arc::clone(&cached.packages));
+            }
+        }
+    }
+
     // Get all packages in one call
     let output = StdCommand::new("pacman")
         .args(["-Sl"])
@@ -170,58 +448,97 @@ fn get_available_kernels() -> anyhow::Result<Vec<String>> {
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
+    let packages = Arc::new(parse_sync_listing(&stdout));
+    if let Ok(mut cache) = SYNC_CACHE.lock() {
+        *cache = Some(SyncCache {
+            fetched_at: Instant::now(),
+            sync_mtime,
+            packages: Arc::clone(&packages),
+        });
+    }
 
-    // First pass: collect all available packages
-    let mut all_packages = std::collections::HashSet::new();
-    let mut kernel_headers = Vec::new();
+    Ok(packages)
+}
 
-    for line in stdout.lines() {
-        // Skip testing repo
-        if line.contains("testing/") {
-            continue;
-        }
+/// Collect `linux*` packages and versions from `pacman -Sl` output.
+///
+/// Packages from testing and staging repositories are skipped. When a
+/// package is in several repositories the first one listed wins, matching
+/// the repository pacman installs from.
+fn parse_sync_listing(listing: &str) -> HashMap<String, String> {
+    let mut packages = HashMap::new();
 
-        // Parse lines like: core linux-headers 6.6.1-1
+    for line in listing.lines() {
+        // Parse lines like: core linux-headers 6.6.1-1 [installed]
         let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() < 2 {
+        if parts.len() < 3 || is_unstable_repo(parts[0]) {
             continue;
         }
 
-        let pkg_name = parts[1];
-
-        // Collect all package names
-        if pkg_name.starts_with("linux") {
-            all_packages.insert(pkg_name.to_string());
-        }
-
-        // Find kernel headers (but not linux-api-headers)
-        if pkg_name.starts_with("linux")
-            && pkg_name.ends_with("-headers")
-            && pkg_name != "linux-api-headers"
-        {
-            kernel_headers.push(pkg_name.to_string());
+        if parts[1].starts_with("linux") {
+            packages
+                .entry(parts[1].to_string())
+                .or_insert_with(|| parts[2].to_string());
         }
     }
 
-    // Second pass: for each headers package, check if kernel exists
+    packages
+}
+
+/// Whether a repository carries pre-release packages (testing/staging variants).
+fn is_unstable_repo(repo: &str) -> bool {
+    ["testing", "staging"]
+        .iter()
+        .any(|kind| repo == *kind || repo.ends_with(&format!("-{}", kind)))
+}
+
+/// Name segments of `linux*` packages that are not kernels.
+const NON_KERNEL_SEGMENTS: [&str; 5] = ["api", "firmware", "tools", "docs", "meta"];
+
+/// Whether a package name looks like a kernel (e.g. `linux-zen`, not `linux-firmware`).
+fn is_kernel_name(name: &str) -> bool {
+    name.starts_with("linux")
+        && !name
+            .split('-')
+            .any(|segment| NON_KERNEL_SEGMENTS.contains(&segment))
+}
+
+/// Get list of available kernel packages from repositories.
+/// This function searches for kernel headers and then derives the kernel package names.
+/// Adapted from cachyos-kernel-manager logic.
+fn get_available_kernels(all_packages: &HashMap<String, String>) -> Vec<KernelPackage> {
+    // Find kernel headers (but not linux-api-headers and the like)
+    let kernel_headers = all_packages.keys().filter(|pkg_name| {
+        pkg_name
+            .strip_suffix("-headers")
+            .is_some_and(is_kernel_name)
+    });
+
+    // For each headers package, check if kernel exists
     let mut kernels = Vec::new();
     for headers_pkg in kernel_headers {
         if let Some(kernel_name) = headers_pkg.strip_suffix("-headers") {
             // Check if the corresponding kernel package exists
-            if all_packages.contains(kernel_name) {
-                kernels.push(kernel_name.to_string());
+            if let Some(version) = all_packages.get(kernel_name) {
+                kernels.push(KernelPackage {
+                    name: kernel_name.to_string(),
+                    version: version.clone(),
+                });
             }
         }
     }
 
-    kernels.sort();
-    kernels.dedup();
-    Ok(kernels)
+    kernels.sort_by(|a, b| a.name.cmp(&b.name));
+    kernels.dedup_by(|a, b| a.name == b.name);
+    kernels
 }
 
 /// Get list of installed kernel packages.
 /// Only returns kernels that have both the kernel and headers installed.
-fn get_installed_kernels() -> anyhow::Result<Vec<String>> {
+///
+/// This always queries the local database, which is cheap and must reflect
+/// installs and removals immediately, unlike the cached repository list.
+fn get_installed_kernels() -> anyhow::Result<Vec<KernelPackage>> {
     let output = StdCommand::new("pacman")
         .args(["-Q"])
         .stdout(Stdio::piped())
@@ -234,7 +551,7 @@ fn get_installed_kernels() -> anyhow::Result<Vec<String>> {
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut installed_headers = Vec::new();
-    let mut all_packages = Vec::new();
+    let mut all_packages = HashMap::new();
 
     // First pass: collect all packages and identify headers
     for line in stdout.lines() {
@@ -242,13 +559,16 @@ fn get_installed_kernels() -> anyhow::Result<Vec<String>> {
             continue;
         }
 
-        let pkg_name = line.split_whitespace().next().unwrap_or("");
-        all_packages.push(pkg_name.to_string());
+        // Parse lines like: linux-zen 6.12.1.zen1-1
+        let mut parts = line.split_whitespace();
+        let pkg_name = parts.next().unwrap_or("");
+        let version = parts.next().unwrap_or("");
+        all_packages.insert(pkg_name.to_string(), version.to_string());
 
         // Find kernel headers
-        if pkg_name.starts_with("linux")
-            && pkg_name.ends_with("-headers")
-            && pkg_name != "linux-api-headers"
+        if pkg_name
+            .strip_suffix("-headers")
+            .is_some_and(is_kernel_name)
         {
             installed_headers.push(pkg_name.to_string());
         }
@@ -260,27 +580,62 @@ fn get_installed_kernels() -> anyhow::Result<Vec<String>> {
     for headers_pkg in installed_headers {
         if let Some(kernel_name) = headers_pkg.strip_suffix("-headers") {
             // Check if the corresponding kernel package is installed
-            if all_packages.contains(&kernel_name.to_string()) {
-                kernels.push(kernel_name.to_string());
+            if let Some(version) = all_packages.get(kernel_name) {
+                kernels.push(KernelPackage {
+                    name: kernel_name.to_string(),
+                    version: version.clone(),
+                });
             }
         }
     }
 
-    kernels.sort();
-    kernels.dedup();
+    kernels.sort_by(|a, b| a.name.cmp(&b.name));
+    kernels.dedup_by(|a, b| a.name == b.name);
     Ok(kernels)
 }
 
+/// Get the package name of the currently running kernel.
+///
+/// Arch kernel packages ship a `pkgbase` file in their modules directory that
+/// names the package, which handles flavors like linux-zen and linux-cachyos.
+/// Falls back to deriving the flavor from the `uname -r` suffix
+/// (e.g. `6.12.1-zen1-1-zen` -> `linux-zen`, `6.12.1-arch1-1` -> `linux`).
+fn get_running_kernel() -> Option<String> {
+    let release = std::fs::read_to_string("/proc/sys/kernel/osrelease").ok()?;
+    let release = release.trim();
+
+    let pkgbase_path = format!("/usr/lib/modules/{}/pkgbase", release);
+    if let Ok(pkgbase) = std::fs::read_to_string(pkgbase_path) {
+        let pkgbase = pkgbase.trim();
+        if !pkgbase.is_empty() {
+            return Some(pkgbase.to_string());
+        }
+    }
+
+    let suffix = release.rsplit('-').next()?;
+    if suffix.chars().all(|c| c.is_ascii_alphabetic()) {
+        Some(format!("linux-{}", suffix))
+    } else {
+        Some("linux".to_string())
+    }
+}
+
 /// Populate the installed kernels list.
-fn populate_installed_list(builder: &Builder, kernels: &[String], window: &ApplicationWindow) {
+///
+/// The running kernel is marked with a badge and cannot be removed.
+fn populate_installed_list(
+    builder: &Builder,
+    kernels: &[KernelPackage],
+    running_kernel: Option<&str>,
+    window: &ApplicationWindow,
+) {
     let list = extract_widget::<ListBox>(builder, "installed_kernels_list");
 
-    // Clear existing items
-    while let Some(row) = list.first_child() {
-        list.remove(&row);
-    }
+    clear_list(&list);
+
+    let bootloader = detect_bootloader();
 
-    // Add kernels with remove buttons
+    // Add kernels with default-boot and remove buttons
     for kernel in kernels {
         let row_box = GtkBox::new(Orientation::Horizontal, 8);
         row_box.set_margin_start(12);
@@ -288,22 +643,61 @@ fn populate_installed_list(builder: &Builder, kernels: &[String], window: &Appli
         row_box.set_margin_top(8);
         row_box.set_margin_bottom(8);
 
-        let label = Label::new(Some(kernel));
+        let label = Label::new(Some(&kernel.display_text()));
         label.set_xalign(0.0);
         label.set_hexpand(true);
         row_box.append(&label);
 
+        let is_running = running_kernel == Some(kernel.name.as_str());
+        if is_running {
+            let badge = Label::new(Some("Running"));
+            badge.set_valign(gtk4::Align::Center);
+            badge.add_css_class("caption");
+            badge.add_css_class("success");
+            row_box.append(&badge);
+        }
+
+        let default_button = Button::new();
+        default_button.set_icon_name("star-symbolic");
+        default_button.set_valign(gtk4::Align::Center);
+        default_button.add_css_class("flat");
+        match bootloader {
+            Some(bootloader) => {
+                default_button.set_tooltip_text(Some(&format!(
+                    "Boot this kernel by default ({})",
+                    bootloader.name()
+                )));
+
+                let kernel_name = kernel.name.clone();
+                let window_clone = window.clone();
+                default_button.connect_clicked(move |_| {
+                    set_default_kernel(&kernel_name, bootloader, &window_clone);
+                });
+            }
+            None => {
+                default_button.set_sensitive(false);
+                default_button.set_tooltip_text(Some(
+                    "No supported bootloader (GRUB or systemd-boot) was found",
+                ));
+            }
+        }
+        row_box.append(&default_button);
+
         let remove_button = Button::new();
         remove_button.set_icon_name("trash-symbolic");
         remove_button.set_valign(gtk4::Align::Center);
         remove_button.add_css_class("flat");
         remove_button.add_css_class("destructive-action");
+        if is_running {
+            remove_button.set_sensitive(false);
+            remove_button.set_tooltip_text(Some("The running kernel cannot be removed"));
+        }
 
-        let kernel_name = kernel.clone();
+        let kernel_name = kernel.name.clone();
         let window_clone = window.clone();
         let builder_clone = builder.clone();
-        remove_button.connect_clicked(move |_| {
-            remove_kernel(&kernel_name, &window_clone, &builder_clone);
+        remove_button.connect_clicked(move |button| {
+            remove_kernel(&kernel_name, button, &window_clone, &builder_clone);
         });
 
         row_box.append(&remove_button);
@@ -311,41 +705,38 @@ fn populate_installed_list(builder: &Builder, kernels: &[String], window: &Appli
     }
 
     if kernels.is_empty() {
-        let label = Label::new(Some("No kernels installed"));
-        label.add_css_class("dim-label");
-        label.set_margin_start(12);
-        label.set_margin_end(12);
-        label.set_margin_top(8);
-        label.set_margin_bottom(8);
-        list.append(&label);
+        list.append(&placeholder_label("No kernels installed"));
     }
 }
 
 /// Populate the available kernels list (excluding installed ones).
+///
+/// Kernels are ordered by flavor group so the list header function can
+/// title each group.
 fn populate_available_list(
     builder: &Builder,
-    available: &[String],
-    installed: &[String],
+    available: &[KernelPackage],
+    installed: &[KernelPackage],
     window: &ApplicationWindow,
 ) {
     let list = extract_widget::<ListBox>(builder, "available_kernels_list");
 
-    // Clear existing items
-    while let Some(row) = list.first_child() {
-        list.remove(&row);
-    }
+    clear_list(&list);
+
+    let mut available: Vec<&KernelPackage> = available.iter().collect();
+    available.sort_by_key(|k| (kernel_group_index(&k.name), k.name.clone()));
 
     // Add kernels that are not installed with install buttons
     let mut added = 0;
     for kernel in available {
-        if !installed.contains(kernel) {
+        if !installed.iter().any(|k| k.name == kernel.name) {
             let row_box = GtkBox::new(Orientation::Horizontal, 8);
             row_box.set_margin_start(12);
             row_box.set_margin_end(12);
             row_box.set_margin_top(8);
             row_box.set_margin_bottom(8);
 
-            let label = Label::new(Some(kernel));
+            let label = Label::new(Some(&kernel.display_text()));
             label.set_xalign(0.0);
             label.set_hexpand(true);
             row_box.append(&label);
@@ -356,11 +747,11 @@ fn populate_available_list(
             install_button.add_css_class("flat");
             install_button.add_css_class("suggested-action");
 
-            let kernel_name = kernel.clone();
+            let kernel_name = kernel.name.clone();
             let window_clone = window.clone();
             let builder_clone = builder.clone();
-            install_button.connect_clicked(move |_| {
-                install_kernel(&kernel_name, &window_clone, &builder_clone);
+            install_button.connect_clicked(move |button| {
+                install_kernel(&kernel_name, button, &window_clone, &builder_clone);
             });
 
             row_box.append(&install_button);
@@ -370,87 +761,316 @@ fn populate_available_list(
     }
 
     if added == 0 {
-        let label = Label::new(Some("All available kernels are installed"));
-        label.add_css_class("dim-label");
-        label.set_margin_start(12);
-        label.set_margin_end(12);
-        label.set_margin_top(8);
-        label.set_margin_bottom(8);
-        list.append(&label);
+        list.append(&placeholder_label("All available kernels are installed"));
     }
 }
 
 /// Update status labels with kernel counts.
-fn update_status_labels(builder: &Builder, available: &[String], installed: &[String]) {
+fn update_status_labels(
+    builder: &Builder,
+    available: &[KernelPackage],
+    installed: &[KernelPackage],
+) {
     let installed_count = extract_widget::<Label>(builder, "installed_count_label");
     let available_count = extract_widget::<Label>(builder, "available_count_label");
 
     installed_count.set_text(&format!("{} installed", installed.len()));
 
-    let not_installed = available.iter().filter(|k| !installed.contains(k)).count();
+    let not_installed = available
+        .iter()
+        .filter(|k| !installed.iter().any(|i| i.name == k.name))
+        .count();
     available_count.set_text(&format!("{} available", not_installed));
 }
 
 /// Install a kernel with its headers.
-fn install_kernel(kernel_name: &str, window: &ApplicationWindow, builder: &Builder) {
+///
+/// The download size and whether the kernel is already installed or running
+/// are looked up in the background while `button` spins, then the
+/// installation is confirmed.
+fn install_kernel(
+    kernel_name: &str,
+    button: &Button,
+    window: &ApplicationWindow,
+    builder: &Builder,
+) {
     let headers = format!("{}-headers", kernel_name);
     let kernel_name = kernel_name.to_string();
+
+    set_button_spinning(button, true);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let packages = [kernel_name.clone(), headers.clone()];
+    std::thread::spawn(move || {
+        // --needed makes reinstalling an installed kernel a no-op, and removing
+        // one on cancel would bypass the removal guards
+        let remove_on_cancel = !core::is_package_installed(&packages[0])
+            && get_running_kernel().as_deref() != Some(packages[0].as_str());
+        let packages = [packages[0].as_str(), packages[1].as_str()];
+        let _ = sender.send((
+            core::aur::download_size(&packages),
+            core::repo_installed_size(&packages),
+            remove_on_cancel,
+        ));
+    });
+
+    let button = button.clone();
+    let window = window.clone();
+    let builder = builder.clone();
+    glib::timeout_add_local(Duration::from_millis(50), move || {
+        let (download_size, installed_size, remove_on_cancel) = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => (None, None, false),
+        };
+        set_button_spinning(&button, false);
+        confirm_install_kernel(
+            &kernel_name,
+            &headers,
+            download_size,
+            installed_size,
+            remove_on_cancel,
+            &window,
+            &builder,
+        );
+        glib::ControlFlow::Break
+    });
+}
+
+/// Ask to install a kernel and its headers, then install them.
+///
+/// With `remove_on_cancel`, cancelling after the kernel step removes the
+/// kernel again. Only pass it for a kernel that wasn't installed before.
+fn confirm_install_kernel(
+    kernel_name: &str,
+    headers: &str,
+    download_size: Option<u64>,
+    installed_size: Option<u64>,
+    remove_on_cancel: bool,
+    window: &ApplicationWindow,
+    builder: &Builder,
+) {
+    let kernel_name = kernel_name.to_string();
+    let headers = headers.to_string();
     let window_clone = window.clone();
     let builder_clone = builder.clone();
 
-    show_warning_confirmation(
+    let size_notes: Vec<String> = [
+        download_size
+            .filter(|&bytes| bytes > 0)
+            .map(|bytes| format!("~{} will be downloaded", format_bytes(bytes))),
+        installed_size.map(|bytes| format!("~{} will be installed", format_bytes(bytes))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let size_note = if size_notes.is_empty() {
+        String::new()
+    } else {
+        format!("\n\n{}.", size_notes.join(", "))
+    };
+
+    show_dismissable_warning_confirmation(
         window.upcast_ref(),
         "Confirm Installation",
         &format!(
             "Install <b>{}</b> and <b>{}</b>?\n\n\
-            This will download and install the kernel and its headers.",
-            kernel_name, headers
+            This will download and install the kernel and its headers.{}",
+            kernel_name, headers, size_note
         ),
+        DismissableWarning::KernelInstall,
         move || {
             info!("Installing {} and {}", kernel_name, headers);
 
+            // Separate steps so a cancel between them can remove the lone kernel again
+            let mut kernel_step = Command::builder()
+                .aur()
+                .args(&["-S", "--noconfirm", "--needed", &kernel_name])
+                .description(&format!("Installing {}...", kernel_name));
+            if remove_on_cancel {
+                kernel_step = kernel_step.on_cancel_cleanup(
+                    Command::builder()
+                        .aur()
+                        .args(&["-R", "--noconfirm", &kernel_name])
+                        .description(&format!("Removing partially installed {}...", kernel_name))
+                        .build(),
+                );
+            }
+
             let commands = CommandSequence::new()
+                .then(kernel_step.build())
                 .then(
                     Command::builder()
                         .aur()
-                        .args(&["-S", "--noconfirm", "--needed", &kernel_name, &headers])
-                        .description(&format!("Installing {} and {}...", kernel_name, headers))
+                        .args(&["-S", "--noconfirm", "--needed", &headers])
+                        .description(&format!("Installing {}...", headers))
                         .build(),
                 )
                 .build();
 
-            // Run installation
-            task_runner::run(window_clone.upcast_ref(), commands, "Install Kernel");
-
-            // Schedule refresh after dialog closes
-            glib::timeout_add_seconds_local(2, move || {
-                if !task_runner::is_running() {
-                    scan_and_populate_kernels(&builder_clone, &window_clone, None);
-                    glib::ControlFlow::Break
-                } else {
-                    glib::ControlFlow::Continue
-                }
-            });
+            // Run installation, rescanning once it ends
+            let parent = window_clone.clone();
+            task_runner::run_with_on_complete(
+                parent.upcast_ref(),
+                commands,
+                "Install Kernel",
+                move |_| scan_and_populate_kernels(&builder_clone, &window_clone, None),
+            );
         },
     );
 }
 
+/// Make a kernel the bootloader's default entry.
+fn set_default_kernel(kernel_name: &str, bootloader: Bootloader, window: &ApplicationWindow) {
+    info!(
+        "Setting {} as the default {} entry",
+        kernel_name,
+        bootloader.name()
+    );
+
+    let script = crate::config::paths::scripts()
+        .join("set_default_kernel.sh")
+        .to_string_lossy()
+        .into_owned();
+
+    let commands = CommandSequence::new()
+        .then(
+            Command::builder()
+                .privileged()
+                .program("bash")
+                .args(&[&script, bootloader.script_arg(), kernel_name])
+                .description(&format!(
+                    "Making {} the default {} entry...",
+                    kernel_name,
+                    bootloader.name()
+                ))
+                .build(),
+        )
+        .build();
+
+    task_runner::run(window.upcast_ref(), commands, "Set Default Kernel");
+}
+
 /// Remove a kernel with its headers.
-fn remove_kernel(kernel_name: &str, window: &ApplicationWindow, builder: &Builder) {
+///
+/// The running and installed kernels and the freed size are looked up in the
+/// background while `button` spins, then the removal is confirmed.
+fn remove_kernel(
+    kernel_name: &str,
+    button: &Button,
+    window: &ApplicationWindow,
+    builder: &Builder,
+) {
     let headers = format!("{}-headers", kernel_name);
     let kernel_name = kernel_name.to_string();
+
+    set_button_spinning(button, true);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let packages = [kernel_name.clone(), headers.clone()];
+    std::thread::spawn(move || {
+        let _ = sender.send((
+            get_running_kernel(),
+            get_installed_kernels(),
+            core::local_installed_size(&[&packages[0], &packages[1]]),
+        ));
+    });
+
+    let button = button.clone();
+    let window = window.clone();
+    let builder = builder.clone();
+    glib::timeout_add_local(Duration::from_millis(50), move || {
+        let (running_kernel, installed_kernels, freed_size) = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => (
+                None,
+                Err(anyhow::anyhow!("kernel lookup thread disconnected")),
+                None,
+            ),
+        };
+        set_button_spinning(&button, false);
+        confirm_remove_kernel(
+            &kernel_name,
+            &headers,
+            running_kernel.as_deref(),
+            installed_kernels,
+            freed_size,
+            &window,
+            &builder,
+        );
+        glib::ControlFlow::Break
+    });
+}
+
+/// Ask to remove a kernel and its headers, then remove them.
+///
+/// Refuses to remove the running kernel or the last installed one, since
+/// either would leave the system unable to boot.
+fn confirm_remove_kernel(
+    kernel_name: &str,
+    headers: &str,
+    running_kernel: Option<&str>,
+    installed_kernels: anyhow::Result<Vec<KernelPackage>>,
+    freed_size: Option<u64>,
+    window: &ApplicationWindow,
+    builder: &Builder,
+) {
+    if running_kernel == Some(kernel_name) {
+        warn!("Refusing to remove running kernel {}", kernel_name);
+        show_error(
+            window,
+            &format!(
+                "{} is the kernel currently running and cannot be removed.\n\n\
+                Reboot into another kernel first.",
+                kernel_name
+            ),
+        );
+        return;
+    }
+
+    match installed_kernels {
+        Ok(kernels) if kernels.iter().any(|k| k.name != kernel_name) => {}
+        Ok(_) => {
+            warn!(
+                "Refusing to remove {}, the last installed kernel",
+                kernel_name
+            );
+            show_error(
+                window,
+                &format!(
+                    "{} is the only installed kernel. Removing it would leave the \
+                    system unbootable.\n\nInstall another kernel first.",
+                    kernel_name
+                ),
+            );
+            return;
+        }
+        Err(e) => {
+            warn!("Failed to list installed kernels: {}", e);
+            show_error(
+                window,
+                "Could not verify that another kernel is installed, so the removal was cancelled.",
+            );
+            return;
+        }
+    }
+
+    let kernel_name = kernel_name.to_string();
+    let headers = headers.to_string();
     let window_clone = window.clone();
     let builder_clone = builder.clone();
 
+    let size_note = freed_size
+        .map(|bytes| format!("\n\n~{} will be freed.", format_bytes(bytes)))
+        .unwrap_or_default();
+
     show_warning_confirmation(
         window.upcast_ref(),
         "Confirm Removal",
         &format!(
             "Remove <b>{}</b> and <b>{}</b>?\n\n\
             <span foreground=\"red\" weight=\"bold\">Warning:</span> \
-            This will uninstall the kernel and its headers.\n\
-            Make sure you have at least one other kernel installed.",
-            kernel_name, headers
+            This will uninstall the kernel and its headers.{}",
+            kernel_name, headers, size_note
         ),
         move || {
             info!("Removing {} and {}", kernel_name, headers);
@@ -465,18 +1085,85 @@ fn remove_kernel(kernel_name: &str, window: &ApplicationWindow, builder: &Builde
                 )
                 .build();
 
-            // Run removal
-            task_runner::run(window_clone.upcast_ref(), commands, "Remove Kernel");
-
-            // Schedule refresh after dialog closes
-            glib::timeout_add_seconds_local(2, move || {
-                if !task_runner::is_running() {
-                    scan_and_populate_kernels(&builder_clone, &window_clone, None);
-                    glib::ControlFlow::Break
-                } else {
-                    glib::ControlFlow::Continue
-                }
-            });
+            // Run removal, rescanning once it ends
+            let parent = window_clone.clone();
+            task_runner::run_with_on_complete(
+                parent.upcast_ref(),
+                commands,
+                "Remove Kernel",
+                move |_| scan_and_populate_kernels(&builder_clone, &window_clone, None),
+            );
         },
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYNC_LISTING: &str = "\
+core linux 6.12.1.arch1-1 [installed]
+core linux-headers 6.12.1.arch1-1 [installed]
+core linux-api-headers 6.10-1
+core linux-firmware 20241210-1 [installed]
+core linux-lts 6.6.64-1
+core linux-lts-headers 6.6.64-1
+core-testing linux 6.13rc1.arch1-1
+core-testing linux-headers 6.13rc1.arch1-1
+extra linux-zen 6.12.1.zen1-1
+extra linux-zen-headers 6.12.1.zen1-1
+extra linux-tools-meta 6.12-1
+extra linux-docs 6.12.1.arch1-1
+extra-staging linux-hardened 6.12.1.hardened1-1
+extra-staging linux-hardened-headers 6.12.1.hardened1-1
+chaotic-aur linux-tkg-bore 6.12.1-1
+chaotic-aur linux-tkg-bore-headers 6.12.1-1
+";
+
+    fn kernel_names(listing: &str) -> Vec<String> {
+        get_available_kernels(&parse_sync_listing(listing))
+            .into_iter()
+            .map(|k| k.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_available_kernels_require_headers_partner() {
+        assert_eq!(
+            kernel_names(SYNC_LISTING),
+            vec!["linux", "linux-lts", "linux-tkg-bore", "linux-zen"]
+        );
+    }
+
+    #[test]
+    fn test_sync_listing_skips_testing_and_staging() {
+        let packages = parse_sync_listing(SYNC_LISTING);
+        assert_eq!(
+            packages.get("linux").map(String::as_str),
+            Some("6.12.1.arch1-1")
+        );
+        assert!(!packages.contains_key("linux-hardened"));
+    }
+
+    #[test]
+    fn test_sync_listing_prefers_first_repo() {
+        let listing = "\
+core linux-lts 6.6.64-1
+chaotic-aur linux-lts 6.6.70-1
+";
+        let packages = parse_sync_listing(listing);
+        assert_eq!(
+            packages.get("linux-lts").map(String::as_str),
+            Some("6.6.64-1")
+        );
+    }
+
+    #[test]
+    fn test_is_kernel_name() {
+        assert!(is_kernel_name("linux-cachyos-bore"));
+        assert!(!is_kernel_name("linux-firmware"));
+        assert!(!is_kernel_name("linux-api"));
+        assert!(!is_kernel_name("linux-tools-meta"));
+        assert!(!is_kernel_name("lib32-linux"));
+    }
+}
@@ -1,11 +1,16 @@
 //! SCX Scheduler page handlers.
 //!
 //! Manages sched-ext BPF CPU schedulers via scxctl.
+//! The last started scheduler and mode are remembered in the user config.
 
-use crate::ui::dialogs::warning::show_warning_confirmation;
+use crate::config;
+use crate::config::user::DismissableWarning;
+use crate::ui::dialogs::error::show_error;
+use crate::ui::dialogs::warning::show_dismissable_warning_confirmation;
 use crate::ui::task_runner::{self, Command, CommandSequence};
 use crate::ui::utils::{
-    extract_widget, get_combo_row_value, is_service_enabled, path_exists, run_command,
+    command_exists, extract_widget, get_combo_row_value, is_service_enabled, path_exists,
+    run_command, set_button_spinning, set_combo_row_value,
 };
 use adw::prelude::*;
 use gtk4::glib;
@@ -16,6 +21,35 @@ use std::rc::Rc;
 
 const SCHED_EXT_PATH: &str = "/sys/kernel/sched_ext";
 
+/// One-line descriptions shown under each scheduler in the selector.
+/// Schedulers missing here are still listed, just without a subtitle.
+const SCHEDULER_DESCRIPTIONS: &[(&str, &str)] = &[
+    ("scx_rusty", "General-purpose multi-domain"),
+    ("scx_lavd", "Latency-aware, good for gaming"),
+    ("scx_bpfland", "Prioritizes interactive tasks"),
+    (
+        "scx_cosmos",
+        "Lightweight, tuned for desktop responsiveness",
+    ),
+    ("scx_flash", "Fair, predictable latency for mixed workloads"),
+    ("scx_layered", "Configurable layers for server workloads"),
+    ("scx_flatcg", "Flattened cgroup hierarchy scheduling"),
+    ("scx_tickless", "Reduces timer ticks for server workloads"),
+    ("scx_nest", "Keeps tasks on warm cores for low latency"),
+    ("scx_simple", "Minimal example scheduler"),
+    ("scx_chaos", "Randomized scheduling for testing"),
+    ("scx_userland", "Scheduling decisions made in user space"),
+];
+
+/// Status of the running sched-ext scheduler, as reported by `scxctl get`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ScxStatus {
+    is_active: bool,
+    /// Scheduler name with the `scx_` prefix, empty when inactive
+    name: String,
+    mode: String,
+}
+
 /// Shared state for the scheduler page
 #[derive(Default)]
 struct State {
@@ -23,6 +57,11 @@ struct State {
     kernel_supported: bool,
     is_active: bool,
     selected_scheduler: Option<String>,
+    /// Bumped by every refresh, so a newer refresh supersedes one in flight
+    refresh_generation: u64,
+    /// Set while the persistence switch is synced to the system, so its
+    /// handler doesn't treat the change as a user toggle
+    syncing_persistence: bool,
 }
 
 pub fn setup_handlers(builder: &Builder, _main_builder: &Builder, window: &ApplicationWindow) {
@@ -30,6 +69,7 @@ pub fn setup_handlers(builder: &Builder, _main_builder: &Builder, window: &Appli
 
     init_kernel_support(builder, &state);
     setup_buttons(builder, window, &state);
+    setup_extra_args(builder);
     setup_persistence(builder, window, &state);
 
     // Initial scan
@@ -49,8 +89,10 @@ pub fn setup_handlers(builder: &Builder, _main_builder: &Builder, window: &Appli
 fn init_kernel_support(builder: &Builder, state: &Rc<RefCell<State>>) {
     let version = run_command("uname", &["-r"]).unwrap_or_else(|| "Unknown".to_string());
     let supported = path_exists(SCHED_EXT_PATH);
+    let scxctl_available = command_exists("scxctl");
 
     state.borrow_mut().kernel_supported = supported;
+    update_scx_tools_row(builder, supported, scxctl_available);
 
     let icon = extract_widget::<Image>(builder, "kernel_status_icon");
     let label = extract_widget::<Label>(builder, "kernel_version_label");
@@ -75,7 +117,42 @@ fn init_kernel_support(builder: &Builder, state: &Rc<RefCell<State>>) {
     });
 }
 
+/// Show the scx-scheds install prompt when the kernel supports sched-ext
+/// but the userspace tools are missing.
+fn update_scx_tools_row(builder: &Builder, kernel_supported: bool, scxctl_available: bool) {
+    extract_widget::<adw::ActionRow>(builder, "scx_tools_missing_row")
+        .set_visible(kernel_supported && !scxctl_available);
+}
+
 fn setup_buttons(builder: &Builder, window: &ApplicationWindow, state: &Rc<RefCell<State>>) {
+    // Install scx-scheds button
+    let b = builder.clone();
+    let w = window.clone();
+    let s = Rc::clone(state);
+    extract_widget::<Button>(builder, "btn_install_scx_scheds").connect_clicked(move |_| {
+        info!("Installing scx-scheds");
+
+        let commands = CommandSequence::new()
+            .then(
+                Command::builder()
+                    .aur()
+                    .args(&["-S", "--noconfirm", "--needed", "scx-scheds"])
+                    .description("Installing sched-ext schedulers and scxctl...")
+                    .build(),
+            )
+            .build();
+
+        // Re-scan once the installation is done
+        let b = b.clone();
+        let s = s.clone();
+        task_runner::run_with_on_complete(
+            w.upcast_ref(),
+            commands,
+            "Install SCX Schedulers",
+            move |_| refresh_state(&b, &s, None),
+        );
+    });
+
     // Scheduler Selection Row
     let b = builder.clone();
     let w = window.clone();
@@ -116,6 +193,14 @@ fn setup_buttons(builder: &Builder, window: &ApplicationWindow, state: &Rc<RefCe
             return;
         };
 
+        let extra_args = match read_extra_args(&b) {
+            Ok(args) => args,
+            Err(err) => {
+                show_error(&w, err);
+                return;
+            }
+        };
+
         let sched = format!("scx_{}", sched_name);
         let cmd = if s.borrow().is_active {
             "switch"
@@ -125,12 +210,24 @@ fn setup_buttons(builder: &Builder, window: &ApplicationWindow, state: &Rc<RefCe
 
         info!("{cmd}ing scheduler {sched_name} with mode {mode}");
 
+        // Update in-memory config; actual persistence happens on app shutdown.
+        {
+            let config = config::user::shared();
+            let mut config = config.borrow_mut();
+            config.scx.scheduler = Some(sched_name.clone());
+            config.scx.mode = mode.clone();
+        }
+
+        let scheduler_args = scxctl_args(&extra_args);
+        let mut args: Vec<&str> = vec![cmd, "--sched", &sched_name, "--mode", &mode];
+        args.extend(scheduler_args.iter().map(String::as_str));
+
         let commands = CommandSequence::new()
             .then(
                 Command::builder()
                     .normal()
                     .program("scxctl")
-                    .args(&[cmd, "--sched", &sched_name, "--mode", &mode])
+                    .args(&args)
                     .description(&format!(
                         "{}ing {} ({} mode)...",
                         if cmd == "switch" { "Switch" } else { "Start" },
@@ -141,7 +238,10 @@ fn setup_buttons(builder: &Builder, window: &ApplicationWindow, state: &Rc<RefCe
             )
             .build();
 
-        task_runner::run(
+        // Show the new scheduler right away instead of on the next status poll
+        let b = b.clone();
+        let s = s.clone();
+        task_runner::run_with_on_complete(
             w.upcast_ref(),
             commands,
             if cmd == "switch" {
@@ -149,19 +249,25 @@ fn setup_buttons(builder: &Builder, window: &ApplicationWindow, state: &Rc<RefCe
             } else {
                 "Start Scheduler"
             },
+            move |_| update_status(&b, &s),
         );
     });
 
     // Stop button
+    let b = builder.clone();
     let w = window.clone();
+    let s = Rc::clone(state);
     extract_widget::<Button>(builder, "btn_stop_scheduler").connect_clicked(move |_| {
         let wc = w.clone();
-        show_warning_confirmation(
+        let b = b.clone();
+        let s = s.clone();
+        show_dismissable_warning_confirmation(
             w.upcast_ref(),
             "Stop Scheduler",
             "Stop the current scheduler and fall back to EEVDF?",
+            DismissableWarning::SchedulerStop,
             move || {
-                task_runner::run(
+                task_runner::run_with_on_complete(
                     wc.upcast_ref(),
                     CommandSequence::new()
                         .then(
@@ -174,20 +280,122 @@ fn setup_buttons(builder: &Builder, window: &ApplicationWindow, state: &Rc<RefCe
                         )
                         .build(),
                     "Stop Scheduler",
+                    move |_| update_status(&b, &s),
                 );
             },
         );
     });
 }
 
+fn setup_extra_args(builder: &Builder) {
+    let entry = extract_widget::<adw::EntryRow>(builder, "extra_args_entry");
+    entry.connect_changed(|entry| {
+        if parse_extra_args(&entry.text()).is_ok() {
+            entry.remove_css_class("error");
+        } else {
+            entry.add_css_class("error");
+        }
+    });
+}
+
+/// Read the extra scheduler arguments from the entry.
+fn read_extra_args(builder: &Builder) -> Result<Vec<String>, &'static str> {
+    parse_extra_args(&extract_widget::<adw::EntryRow>(builder, "extra_args_entry").text())
+}
+
+/// Split extra scheduler arguments, returning an error message if they can't
+/// be passed on. scxctl splits `--args` values on commas, so an argument
+/// containing one would not reach the scheduler intact.
+fn parse_extra_args(text: &str) -> Result<Vec<String>, &'static str> {
+    let args = split_args(text).ok_or("The extra arguments contain an unbalanced quote.")?;
+    if args.iter().any(|arg| arg.contains(',')) {
+        return Err("The extra arguments can't contain commas.");
+    }
+    Ok(args)
+}
+
+/// Build the scxctl flags passing each of `args` to the scheduler.
+///
+/// Every argument gets its own `--args=` so scheduler flags like
+/// `--slice-us` aren't mistaken for scxctl's own options.
+fn scxctl_args(args: &[String]) -> Vec<String> {
+    args.iter().map(|arg| format!("--args={}", arg)).collect()
+}
+
+/// Split a command line into arguments, honoring single and double quotes
+/// and backslash escapes. Returns `None` on an unbalanced quote.
+fn split_args(input: &str) -> Option<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => {
+                current.push(chars.next()?);
+                in_arg = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return None;
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Some(args)
+}
+
+/// Format extra arguments for the `@EXTRA_ARGS@` placeholder of the service
+/// template, which sits inside a single-quoted `sh -c` in `ExecStart=`.
+///
+/// Returns `None` if the arguments contain characters that systemd or the
+/// shell would interpret (quotes, `$`, `%`, backslashes, ...).
+fn service_extra_args(args: &[String]) -> Option<String> {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || " _-./=:+@".contains(c);
+    if !args.iter().all(|arg| arg.chars().all(is_safe)) {
+        return None;
+    }
+
+    Some(
+        scxctl_args(args)
+            .iter()
+            .map(|arg| format!(" \"{}\"", arg))
+            .collect(),
+    )
+}
+
 fn setup_persistence(builder: &Builder, window: &ApplicationWindow, state: &Rc<RefCell<State>>) {
     let switch = extract_widget::<adw::SwitchRow>(builder, "persist_switch");
-    switch.set_active(is_service_enabled("scx.service"));
+    sync_persistence_switch(&switch, state);
 
     let b = builder.clone();
     let w = window.clone();
     let s = state.clone();
     switch.connect_active_notify(move |sw| {
+        if s.borrow().syncing_persistence {
+            return;
+        }
+
         if sw.is_active() {
             let scheduler = s.borrow().selected_scheduler.clone();
             let mode = get_combo_row_value(&extract_widget::<adw::ComboRow>(&b, "mode_combo"))
@@ -195,7 +403,25 @@ fn setup_persistence(builder: &Builder, window: &ApplicationWindow, state: &Rc<R
 
             let Some(sched_name) = scheduler else {
                 warn!("No valid scheduler selected for persistence");
-                sw.set_active(false);
+                sync_persistence_switch(sw, &s);
+                return;
+            };
+
+            let extra_args = match read_extra_args(&b) {
+                Ok(args) => args,
+                Err(err) => {
+                    sync_persistence_switch(sw, &s);
+                    show_error(&w, err);
+                    return;
+                }
+            };
+
+            let Some(service_args) = service_extra_args(&extra_args) else {
+                sync_persistence_switch(sw, &s);
+                show_error(
+                    &w,
+                    "The extra arguments contain special characters that can't be saved to the boot service.",
+                );
                 return;
             };
 
@@ -204,21 +430,26 @@ fn setup_persistence(builder: &Builder, window: &ApplicationWindow, state: &Rc<R
 
             let Ok(content) = std::fs::read_to_string(&template_path) else {
                 warn!("Failed to read service template");
-                sw.set_active(false);
+                sync_persistence_switch(sw, &s);
                 return;
             };
 
             let service = content
                 .replace("@SCHEDULER@", &sched)
                 .replace("@SCHEDULER_NAME@", &sched_name)
-                .replace("@MODE@", &mode);
+                .replace("@MODE@", &mode)
+                .replace("@EXTRA_ARGS@", &service_args);
 
             if std::fs::write("/tmp/scx.service", &service).is_err() {
-                sw.set_active(false);
+                sync_persistence_switch(sw, &s);
                 return;
             }
 
-            task_runner::run(
+            // Show whether the service really ended up enabled, even if the
+            // task failed or was cancelled
+            let sw = sw.clone();
+            let s = s.clone();
+            task_runner::run_with_on_complete(
                 w.upcast_ref(),
                 CommandSequence::new()
                     .then(
@@ -267,9 +498,12 @@ fn setup_persistence(builder: &Builder, window: &ApplicationWindow, state: &Rc<R
                     )
                     .build(),
                 "Enable Persistence",
+                move |_| sync_persistence_switch(&sw, &s),
             );
         } else {
-            task_runner::run(
+            let sw = sw.clone();
+            let s = s.clone();
+            task_runner::run_with_on_complete(
                 w.upcast_ref(),
                 CommandSequence::new()
                     .then(
@@ -290,15 +524,29 @@ fn setup_persistence(builder: &Builder, window: &ApplicationWindow, state: &Rc<R
                     )
                     .build(),
                 "Disable Persistence",
+                move |_| sync_persistence_switch(&sw, &s),
             );
         }
     });
 }
 
+/// Set the persistence switch to whether scx.service is enabled, without
+/// running its enable or disable task.
+fn sync_persistence_switch(switch: &adw::SwitchRow, state: &Rc<RefCell<State>>) {
+    state.borrow_mut().syncing_persistence = true;
+    switch.set_active(is_service_enabled("scx.service"));
+    state.borrow_mut().syncing_persistence = false;
+}
+
 fn refresh_state(builder: &Builder, state: &Rc<RefCell<State>>, refresh_btn: Option<&Button>) {
     let builder = builder.clone();
     let state = state.clone();
     let btn_opt = refresh_btn.cloned();
+    let generation = {
+        let mut s = state.borrow_mut();
+        s.refresh_generation += 1;
+        s.refresh_generation
+    };
 
     // Disable controls while refreshing
     let row = extract_widget::<adw::ActionRow>(&builder, "scheduler_selection_row");
@@ -314,36 +562,34 @@ fn refresh_state(builder: &Builder, state: &Rc<RefCell<State>>, refresh_btn: Opt
     persist.set_sensitive(false);
 
     if let Some(btn) = refresh_btn {
-        btn.set_sensitive(false);
-        // Try to find image child to animate
-        if let Some(child) = btn.child() {
-            if let Some(img) = child.downcast_ref::<Image>() {
-                img.add_css_class("spinning");
-            } else if let Some(box_child) = child.downcast_ref::<GtkBox>() {
-                if let Some(img) = box_child.first_child().and_downcast::<Image>() {
-                    img.add_css_class("spinning");
-                }
-            }
-        }
+        set_button_spinning(btn, true);
     }
 
     // Use std::sync::mpsc for thread communication
-    let (sender, receiver) =
-        std::sync::mpsc::channel::<(Vec<String>, bool, String, String, bool)>();
+    let (sender, receiver) = std::sync::mpsc::channel::<(Vec<String>, ScxStatus, bool, bool)>();
 
     // Run blocking operations in a separate thread
     std::thread::spawn(move || {
         let schedulers = get_schedulers();
-        let (is_active, name, mode) = get_status();
+        let status = get_status();
         let kernel_supported = path_exists(SCHED_EXT_PATH);
-        let _ = sender.send((schedulers, is_active, name, mode, kernel_supported));
+        let scxctl_available = command_exists("scxctl");
+        let _ = sender.send((schedulers, status, kernel_supported, scxctl_available));
     });
 
     // Poll for results in main thread
-    glib::timeout_add_local(
-        std::time::Duration::from_millis(100),
-        move || match receiver.try_recv() {
-            Ok((schedulers, is_active, name, mode, kernel_supported)) => {
+    glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
+        // A newer refresh owns the controls now; only hand back this refresh's button
+        if state.borrow().refresh_generation != generation {
+            if let Some(btn) = &btn_opt {
+                set_button_spinning(btn, false);
+            }
+            return glib::ControlFlow::Break;
+        }
+
+        match receiver.try_recv() {
+            Ok((schedulers, status, kernel_supported, scxctl_available)) => {
+                let is_active = status.is_active;
                 {
                     let mut s = state.borrow_mut();
                     s.schedulers = schedulers.clone();
@@ -351,12 +597,24 @@ fn refresh_state(builder: &Builder, state: &Rc<RefCell<State>>, refresh_btn: Opt
                     s.is_active = is_active;
                 }
 
-                // Select default scheduler if none selected
+                update_scx_tools_row(&builder, kernel_supported, scxctl_available);
+                if kernel_supported && !scxctl_available {
+                    warn!("Kernel supports sched-ext but scxctl is not installed");
+                }
+
+                // Restore the last used scheduler, or select a default if none selected
                 {
+                    let saved = config::user::shared().borrow().scx.clone();
                     let mut s = state.borrow_mut();
                     if s.selected_scheduler.is_none() && !schedulers.is_empty() {
-                        // Prefer scx_rusty or scx_lavd if available, otherwise first
-                        if schedulers.iter().any(|s| s == "scx_rusty") {
+                        // Prefer the saved choice, then scx_rusty or scx_lavd, otherwise first
+                        if let Some(saved_sched) = saved
+                            .scheduler
+                            .filter(|name| schedulers.iter().any(|s| s == name))
+                        {
+                            s.selected_scheduler = Some(saved_sched);
+                            set_combo_row_value(&mode_combo, &saved.mode);
+                        } else if schedulers.iter().any(|s| s == "scx_rusty") {
                             s.selected_scheduler = Some("scx_rusty".to_string());
                         } else if schedulers.iter().any(|s| s == "scx_lavd") {
                             s.selected_scheduler = Some("scx_lavd".to_string());
@@ -373,7 +631,7 @@ fn refresh_state(builder: &Builder, state: &Rc<RefCell<State>>, refresh_btn: Opt
                 }
 
                 // Update status display
-                update_status_labels(&builder, is_active, &name, &mode);
+                update_status_labels(&builder, &status);
 
                 // Update buttons and re-enable controls
                 row.set_sensitive(true);
@@ -385,20 +643,11 @@ fn refresh_state(builder: &Builder, state: &Rc<RefCell<State>>, refresh_btn: Opt
                 stop_btn.set_sensitive(is_active);
 
                 // Update persistence state
-                persist.set_active(is_service_enabled("scx.service"));
+                sync_persistence_switch(&persist, &state);
 
                 // Restore refresh button
                 if let Some(btn) = &btn_opt {
-                    btn.set_sensitive(true);
-                    if let Some(child) = btn.child() {
-                        if let Some(img) = child.downcast_ref::<Image>() {
-                            img.remove_css_class("spinning");
-                        } else if let Some(box_child) = child.downcast_ref::<GtkBox>() {
-                            if let Some(img) = box_child.first_child().and_downcast::<Image>() {
-                                img.remove_css_class("spinning");
-                            }
-                        }
-                    }
+                    set_button_spinning(btn, false);
                 }
 
                 info!(
@@ -420,36 +669,31 @@ fn refresh_state(builder: &Builder, state: &Rc<RefCell<State>>, refresh_btn: Opt
                 persist.set_sensitive(true);
 
                 if let Some(btn) = &btn_opt {
-                    btn.set_sensitive(true);
-                    if let Some(child) = btn.child() {
-                        if let Some(img) = child.downcast_ref::<Image>() {
-                            img.remove_css_class("spinning");
-                        } else if let Some(box_child) = child.downcast_ref::<GtkBox>() {
-                            if let Some(img) = box_child.first_child().and_downcast::<Image>() {
-                                img.remove_css_class("spinning");
-                            }
-                        }
-                    }
+                    set_button_spinning(btn, false);
                 }
                 glib::ControlFlow::Break
             }
-        },
-    );
+        }
+    });
 }
 
 fn update_status(builder: &Builder, state: &Rc<RefCell<State>>) {
-    let (is_active, name, mode) = get_status();
-    state.borrow_mut().is_active = is_active;
+    let status = get_status();
+    state.borrow_mut().is_active = status.is_active;
 
-    update_status_labels(builder, is_active, &name, &mode);
-    extract_widget::<Button>(builder, "btn_stop_scheduler").set_sensitive(is_active);
+    update_status_labels(builder, &status);
+    extract_widget::<Button>(builder, "btn_stop_scheduler").set_sensitive(status.is_active);
 }
 
-fn update_status_labels(builder: &Builder, is_active: bool, name: &str, mode: &str) {
+fn update_status_labels(builder: &Builder, status: &ScxStatus) {
     let active_label = extract_widget::<Label>(builder, "active_scheduler_label");
 
-    if is_active {
-        active_label.set_text(&format!("{} ({})", humanize_name(name), mode));
+    if status.is_active {
+        active_label.set_text(&format!(
+            "{} ({})",
+            humanize_name(&status.name),
+            status.mode
+        ));
         active_label.remove_css_class("dim-label");
         active_label.add_css_class("accent");
     } else {
@@ -461,47 +705,89 @@ fn update_status_labels(builder: &Builder, is_active: bool, name: &str, mode: &s
 
 fn get_schedulers() -> Vec<String> {
     run_command("scxctl", &["list"])
-        .and_then(|out| {
-            out.find("supported schedulers:")
-                .and_then(|i| out[i + 21..].find('[').map(|j| i + 21 + j))
-                .and_then(|start| {
-                    out[start..]
-                        .find(']')
-                        .map(|end| &out[start + 1..start + end])
-                })
-                .map(|list| {
-                    list.split(',')
-                        .map(|s| format!("scx_{}", s.trim().trim_matches('"')))
-                        .filter(|s| s.len() > 4)
-                        .collect()
-                })
-        })
+        .map(|out| parse_schedulers(&out))
         .unwrap_or_default()
 }
 
-fn get_status() -> (bool, String, String) {
+fn get_status() -> ScxStatus {
     run_command("scxctl", &["get"])
-        .map(|out| {
-            let lower = out.to_lowercase();
-            if lower.contains("not running") || out.is_empty() {
-                return (false, String::new(), String::new());
-            }
-            if lower.starts_with("running") {
-                let parts: Vec<&str> = out.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    let name = format!("scx_{}", parts[1].to_lowercase());
-                    let mode = out
-                        .split(" in ")
-                        .nth(1)
-                        .and_then(|s| s.split(" mode").next())
-                        .map(|s| s.trim().to_string())
-                        .unwrap_or_else(|| "N/A".to_string());
-                    return (true, name, mode);
-                }
+        .map(|out| parse_status(&out))
+        .unwrap_or_default()
+}
+
+/// Parse `scxctl list` output such as
+/// `supported schedulers: ["bpfland", "flash", "lavd"]` into `scx_`-prefixed names.
+fn parse_schedulers(output: &str) -> Vec<String> {
+    let lower = output.to_lowercase();
+    let Some(start) = lower.find("supported schedulers") else {
+        return Vec::new();
+    };
+
+    let rest = &output[start + "supported schedulers".len()..];
+    let rest = rest.trim_start().trim_start_matches(':');
+    let list = match (rest.find('['), rest.find(']')) {
+        (Some(open), Some(close)) if open < close => &rest[open + 1..close],
+        _ => rest.lines().next().unwrap_or_default(),
+    };
+
+    list.split(|c: char| c == ',' || c.is_whitespace())
+        .map(|name| name.trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace()))
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            let name = name.to_lowercase();
+            if name.starts_with("scx_") {
+                name
+            } else {
+                format!("scx_{}", name)
             }
-            (false, String::new(), String::new())
         })
-        .unwrap_or((false, String::new(), String::new()))
+        .collect()
+}
+
+/// Parse `scxctl get` output such as `running Rusty in Gaming mode`.
+///
+/// Anything that doesn't start with "running <name>" is treated as no
+/// scheduler running.
+fn parse_status(output: &str) -> ScxStatus {
+    let tokens: Vec<String> = output
+        .split_whitespace()
+        .map(|token| token.to_lowercase())
+        .collect();
+
+    let name = match tokens.as_slice() {
+        [first, name, ..] if first == "running" => name,
+        _ => return ScxStatus::default(),
+    };
+    let name = name.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '_');
+    if name.is_empty() {
+        return ScxStatus::default();
+    }
+
+    // Mode is the text between "in" and "mode", e.g. "in low latency mode"
+    let mode = tokens
+        .iter()
+        .skip(2)
+        .position(|token| token == "in")
+        .map(|pos| {
+            tokens[pos + 3..]
+                .iter()
+                .take_while(|token| token.as_str() != "mode")
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|mode| !mode.is_empty())
+        .unwrap_or_else(|| "N/A".to_string());
+
+    ScxStatus {
+        is_active: true,
+        name: if name.starts_with("scx_") {
+            name.to_string()
+        } else {
+            format!("scx_{}", name)
+        },
+        mode,
+    }
 }
 
 fn show_scheduler_selector(
@@ -545,6 +831,7 @@ fn show_scheduler_selector(
 
                 let row = adw::ActionRow::new();
                 row.set_title(&humanize_name(item));
+                row.set_subtitle(scheduler_description(item));
 
                 if let Some(ref current) = current_selected {
                     if current == item {
@@ -589,6 +876,7 @@ fn show_scheduler_selector(
         for item in others {
             let row = adw::ActionRow::new();
             row.set_title(&humanize_name(item));
+            row.set_subtitle(scheduler_description(item));
 
             if let Some(ref current) = current_selected {
                 if current == item {
@@ -617,6 +905,14 @@ fn show_scheduler_selector(
     window.present();
 }
 
+fn scheduler_description(name: &str) -> &'static str {
+    SCHEDULER_DESCRIPTIONS
+        .iter()
+        .find(|(sched, _)| *sched == name)
+        .map(|(_, description)| *description)
+        .unwrap_or_default()
+}
+
 fn humanize_name(name: &str) -> String {
     let name = name.strip_prefix("scx_").unwrap_or(name);
     let mut chars = name.chars();
@@ -625,3 +921,102 @@ fn humanize_name(name: &str) -> String {
         Some(f) => f.to_uppercase().collect::<String>() + chars.as_str(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_schedulers_bracket_list() {
+        let output = r#"supported schedulers: ["bpfland", "flash", "lavd", "rusty"]"#;
+        assert_eq!(
+            parse_schedulers(output),
+            vec!["scx_bpfland", "scx_flash", "scx_lavd", "scx_rusty"]
+        );
+    }
+
+    #[test]
+    fn test_parse_schedulers_tolerates_surrounding_text() {
+        let output = "Info\nSupported Schedulers: [ bpfland,scx_lavd ]\n";
+        assert_eq!(parse_schedulers(output), vec!["scx_bpfland", "scx_lavd"]);
+    }
+
+    #[test]
+    fn test_parse_schedulers_malformed() {
+        assert!(parse_schedulers("").is_empty());
+        assert!(parse_schedulers("error: dbus connection failed").is_empty());
+        assert!(parse_schedulers("supported schedulers: []").is_empty());
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(
+            split_args(r#"--slice-us 5000 --config "/etc/my layers.json" 'a b'"#),
+            Some(vec![
+                "--slice-us".to_string(),
+                "5000".to_string(),
+                "--config".to_string(),
+                "/etc/my layers.json".to_string(),
+                "a b".to_string(),
+            ])
+        );
+        assert_eq!(split_args("   "), Some(Vec::new()));
+        assert_eq!(split_args(r#"--config "/etc/layers.json"#), None);
+        assert_eq!(split_args("--name 'unterminated"), None);
+    }
+
+    #[test]
+    fn test_service_extra_args() {
+        assert_eq!(service_extra_args(&[]), Some(String::new()));
+        assert_eq!(
+            service_extra_args(&["--slice-us".to_string(), "5000".to_string()]),
+            Some(r#" "--args=--slice-us" "--args=5000""#.to_string())
+        );
+        assert_eq!(service_extra_args(&["$HOME".to_string()]), None);
+    }
+
+    #[test]
+    fn test_parse_extra_args() {
+        assert_eq!(
+            parse_extra_args("--slice-us 5000"),
+            Ok(vec!["--slice-us".to_string(), "5000".to_string()])
+        );
+        assert!(parse_extra_args("--cpus 0,1").is_err());
+        assert!(parse_extra_args("--name 'unterminated").is_err());
+        assert_eq!(
+            scxctl_args(&["--slice-us".to_string(), "5000".to_string()]),
+            vec!["--args=--slice-us".to_string(), "--args=5000".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_status_running() {
+        assert_eq!(
+            parse_status("running Rusty in Gaming mode"),
+            ScxStatus {
+                is_active: true,
+                name: "scx_rusty".to_string(),
+                mode: "gaming".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_status_without_mode() {
+        let status = parse_status("running scx_lavd");
+        assert!(status.is_active);
+        assert_eq!(status.name, "scx_lavd");
+        assert_eq!(status.mode, "N/A");
+    }
+
+    #[test]
+    fn test_parse_status_not_running() {
+        assert_eq!(
+            parse_status("no scx scheduler running"),
+            ScxStatus::default()
+        );
+        assert_eq!(parse_status("not running"), ScxStatus::default());
+        assert_eq!(parse_status(""), ScxStatus::default());
+        assert_eq!(parse_status("running"), ScxStatus::default());
+    }
+}
@@ -11,13 +11,14 @@
 use crate::config;
 use crate::core;
 use crate::ui::dialogs::download::show_download_dialog;
+use crate::ui::dialogs::error;
 use crate::ui::dialogs::selection::{
     show_selection_dialog, SelectionDialogConfig, SelectionOption, SelectionType,
 };
 use crate::ui::dialogs::terminal;
 use crate::ui::dialogs::warning::show_warning_confirmation;
 use crate::ui::task_runner::{self, Command, CommandSequence};
-use crate::ui::utils::extract_widget;
+use crate::ui::utils::{extract_widget, try_extract_widget};
 use gtk4::prelude::*;
 use gtk4::{ApplicationWindow, Builder, Button};
 use log::info;
@@ -25,111 +26,342 @@ use log::info;
 /// Set up all button handlers for the main page.
 pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &ApplicationWindow) {
     setup_update_system(page_builder, window);
+    setup_update_packages(page_builder, window);
     setup_pkg_manager(page_builder, window);
     setup_download_arch_iso(page_builder, window);
     setup_install_nix(page_builder, window);
     setup_obs_studio_aio(page_builder, window);
-    setup_external_links(page_builder);
+    setup_external_links(page_builder, window);
 }
 
 /// Set up OBS-Studio AiO button on the main page.
 fn setup_obs_studio_aio(builder: &Builder, window: &ApplicationWindow) {
-    let btn_obs_studio_aio = extract_widget::<Button>(builder, "btn_obs_studio_aio");
+    let Some(btn_obs_studio_aio) = try_extract_widget::<Button>(builder, "btn_obs_studio_aio")
+    else {
+        return;
+    };
     let window = window.clone();
     btn_obs_studio_aio.connect_clicked(move |_| {
         info!("Main page: OBS-Studio AiO button clicked");
-        let window_ref = window.upcast_ref();
 
-        let wayland_hotkeys_installed =
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.WaylandHotkeys");
-        let v4l2_installed = core::is_package_installed("v4l2loopback-dkms");
-
-        let graphics_capture_installed =
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.OBSVkCapture") &&
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.Gstreamer") &&
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.GStreamerVaapi");
-
-        let transitions_effects_installed =
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.MoveTransition") &&
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.TransitionTable") &&
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.ScaleToSound");
-
-        let streaming_tools_installed =
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.WebSocket") &&
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.SceneSwitcher") &&
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.DroidCam");
-
-        let audio_video_tools_installed =
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.waveform") &&
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.VerticalCanvas") &&
-            core::is_flatpak_installed("com.obsproject.Studio.Plugin.BackgroundRemoval");
+        let installed = core::are_packages_installed(&["com.obsproject.Studio", "obs-studio"]);
+        let mark_installed = |description: &str, package: &str| {
+            if installed.get(package).copied().unwrap_or(false) {
+                format!("{} (installed)", description)
+            } else {
+                description.to_string()
+            }
+        };
 
         let config = SelectionDialogConfig::new(
-            "OBS-Studio & Plugins Installation",
-            "OBS-Studio will be installed. Optionally select plugins to install.",
+            "OBS-Studio Installation",
+            "Choose how OBS-Studio should be installed.",
         )
-        .selection_type(SelectionType::Multi)
-        .selection_required(false)
-        .add_option(SelectionOption::new(
-            "wayland_hotkeys",
-            "Wayland Hotkeys Plugin",
-            "Enable hotkey support for OBS on Wayland",
-            wayland_hotkeys_installed,
-        ))
-        .add_option(SelectionOption::new(
-            "graphics_capture",
-            "Graphics Capture Plugins",
-            "VkCapture, GStreamer, GStreamer VA-API",
-            graphics_capture_installed,
-        ))
-        .add_option(SelectionOption::new(
-            "transitions_effects",
-            "Transitions & Effects",
-            "Move Transition, Transition Table, Scale to Sound",
-            transitions_effects_installed,
-        ))
-        .add_option(SelectionOption::new(
-            "streaming_tools",
-            "Streaming & Recording Tools",
-            "WebSocket API, Scene Switcher, DroidCam",
-            streaming_tools_installed,
-        ))
+        .selection_type(SelectionType::Single)
+        .selection_required(true)
         .add_option(SelectionOption::new(
-            "audio_video_tools",
-            "Audio & Video Tools",
-            "Waveform, Vertical Canvas, Background Removal",
-            audio_video_tools_installed,
+            "flatpak",
+            "Flatpak",
+            &mark_installed(
+                "Sandboxed build from Flathub with the curated plugin bundles",
+                "com.obsproject.Studio",
+            ),
+            false,
         ))
         .add_option(SelectionOption::new(
-            "v4l2",
-            "V4L2loopback Virtual Camera",
-            "Enable OBS virtual camera functionality",
-            v4l2_installed,
+            "native",
+            "Native (AUR)",
+            &mark_installed(
+                "System build with better V4L2 and PipeWire integration",
+                "obs-studio",
+            ),
+            false,
         ))
-        .confirm_label("Install");
+        .confirm_label("Continue");
 
         let window_for_closure = window.clone();
-        show_selection_dialog(window_ref, config, move |selected_ids| {
-            let mut commands = CommandSequence::new();
+        show_selection_dialog(window.upcast_ref(), config, move |selected_ids| {
+            if selected_ids.iter().any(|s| s == "native") {
+                show_obs_native_dialog(&window_for_closure);
+            } else if selected_ids.iter().any(|s| s == "flatpak") {
+                show_obs_flatpak_dialog(&window_for_closure);
+            }
+        });
+    });
+}
+
+/// Add the steps that install and configure the v4l2loopback virtual camera.
+fn with_v4l2loopback(commands: CommandSequence) -> CommandSequence {
+    commands
+        .then(
+            Command::builder()
+                .aur()
+                .args(&[
+                    "-S",
+                    "--noconfirm",
+                    "--needed",
+                    "v4l2loopback-dkms",
+                    "v4l2loopback-utils",
+                ])
+                .description("Installing V4L2 loopback modules...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .privileged()
+                .program("sh")
+                .args(&[
+                    "-c",
+                    "echo 'v4l2loopback' > /etc/modules-load.d/v4l2loopback.conf",
+                ])
+                .description("Enabling V4L2 loopback module at boot...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .privileged()
+                .program("sh")
+                .args(&[
+                    "-c",
+                    "echo 'options v4l2loopback exclusive_caps=1 card_label=\"OBS Virtual Camera\"' > /etc/modprobe.d/v4l2loopback.conf",
+                ])
+                .description("Configuring virtual camera options...")
+                .build(),
+        )
+}
+
+/// Native OBS plugin groups: (id, label, description, packages).
+const OBS_NATIVE_PLUGINS: &[(&str, &str, &str, &[&str])] = &[
+    (
+        "gstreamer",
+        "GStreamer Plugins",
+        "GStreamer and VA-API encoders and sources",
+        &["obs-gstreamer", "obs-vaapi"],
+    ),
+    (
+        "transitions_effects",
+        "Transitions & Effects",
+        "Move Transition",
+        &["obs-move-transition"],
+    ),
+    (
+        "streaming_tools",
+        "Streaming & Recording Tools",
+        "Advanced Scene Switcher, DroidCam (requires V4L2loopback)",
+        &["obs-advanced-scene-switcher", "droidcam-obs-plugin"],
+    ),
+    (
+        "background_removal",
+        "Background Removal",
+        "AI background removal and blur for camera sources",
+        &["obs-backgroundremoval"],
+    ),
+];
+
+/// Show the plugin selection for a native (AUR) OBS-Studio install.
+fn show_obs_native_dialog(window: &ApplicationWindow) {
+    let mut packages = vec!["v4l2loopback-dkms"];
+    for (_, _, _, group) in OBS_NATIVE_PLUGINS {
+        packages.extend_from_slice(group);
+    }
+    let installed = core::are_packages_installed(&packages);
+    let all_installed = |packages: &[&str]| {
+        packages
+            .iter()
+            .all(|package| installed.get(*package).copied().unwrap_or(false))
+    };
+
+    let mut config = SelectionDialogConfig::new(
+        "OBS-Studio & Plugins Installation",
+        "OBS-Studio and OBS VkCapture will be installed. Optionally select plugins to install.",
+    )
+    .selection_type(SelectionType::Multi)
+    .selection_required(false)
+    .with_bulk_toggle(true)
+    .confirm_label("Install");
+
+    for (id, label, description, group) in OBS_NATIVE_PLUGINS {
+        let mut option = SelectionOption::new(id, label, description, all_installed(group));
+        if *id == "streaming_tools" {
+            option = option.requires(&["v4l2"]);
+        }
+        config = config.add_option(option);
+    }
 
-            // Always install OBS-Studio
-            commands = commands.then(Command::builder()
+    config = config.add_option(SelectionOption::new(
+        "v4l2",
+        "V4L2loopback Virtual Camera",
+        "Enable OBS virtual camera functionality",
+        all_installed(&["v4l2loopback-dkms"]),
+    ));
+
+    let window = window.clone();
+    show_selection_dialog(window.upcast_ref(), config, move |selected_ids| {
+        let mut args = vec![
+            "-S",
+            "--noconfirm",
+            "--needed",
+            "obs-studio",
+            "obs-vkcapture",
+            "lib32-obs-vkcapture",
+        ];
+        for (id, _, _, group) in OBS_NATIVE_PLUGINS {
+            if selected_ids.iter().any(|s| s == id) {
+                args.extend_from_slice(group);
+            }
+        }
+
+        let mut commands = CommandSequence::new().then(
+            Command::builder()
+                .aur()
+                .args(&args)
+                .description("Installing OBS-Studio and plugins...")
+                .build(),
+        );
+
+        if selected_ids.iter().any(|s| s == "v4l2") {
+            commands = with_v4l2loopback(commands);
+        }
+
+        task_runner::run(window.upcast_ref(), commands.build(), "OBS-Studio Setup");
+    });
+}
+
+/// Show the plugin selection for a Flatpak OBS-Studio install.
+fn show_obs_flatpak_dialog(window: &ApplicationWindow) {
+    let window_ref = window.upcast_ref();
+
+    // One batched lookup instead of a process per plugin
+    let installed = core::are_packages_installed(&[
+        "v4l2loopback-dkms",
+        "com.obsproject.Studio.Plugin.WaylandHotkeys",
+        "com.obsproject.Studio.Plugin.OBSVkCapture",
+        "com.obsproject.Studio.Plugin.Gstreamer",
+        "com.obsproject.Studio.Plugin.GStreamerVaapi",
+        "com.obsproject.Studio.Plugin.MoveTransition",
+        "com.obsproject.Studio.Plugin.TransitionTable",
+        "com.obsproject.Studio.Plugin.ScaleToSound",
+        "com.obsproject.Studio.Plugin.WebSocket",
+        "com.obsproject.Studio.Plugin.SceneSwitcher",
+        "com.obsproject.Studio.Plugin.DroidCam",
+        "com.obsproject.Studio.Plugin.waveform",
+        "com.obsproject.Studio.Plugin.VerticalCanvas",
+        "com.obsproject.Studio.Plugin.BackgroundRemoval",
+    ]);
+    let all_installed = |packages: &[&str]| {
+        packages
+            .iter()
+            .all(|package| installed.get(*package).copied().unwrap_or(false))
+    };
+
+    let wayland_hotkeys_installed = all_installed(&["com.obsproject.Studio.Plugin.WaylandHotkeys"]);
+    let v4l2_installed = all_installed(&["v4l2loopback-dkms"]);
+
+    let graphics_capture_installed = all_installed(&[
+        "com.obsproject.Studio.Plugin.OBSVkCapture",
+        "com.obsproject.Studio.Plugin.Gstreamer",
+        "com.obsproject.Studio.Plugin.GStreamerVaapi",
+    ]);
+
+    let transitions_effects_installed = all_installed(&[
+        "com.obsproject.Studio.Plugin.MoveTransition",
+        "com.obsproject.Studio.Plugin.TransitionTable",
+        "com.obsproject.Studio.Plugin.ScaleToSound",
+    ]);
+
+    let streaming_tools_installed = all_installed(&[
+        "com.obsproject.Studio.Plugin.WebSocket",
+        "com.obsproject.Studio.Plugin.SceneSwitcher",
+        "com.obsproject.Studio.Plugin.DroidCam",
+    ]);
+
+    let audio_video_tools_installed = all_installed(&[
+        "com.obsproject.Studio.Plugin.waveform",
+        "com.obsproject.Studio.Plugin.VerticalCanvas",
+        "com.obsproject.Studio.Plugin.BackgroundRemoval",
+    ]);
+
+    let config = SelectionDialogConfig::new(
+        "OBS-Studio & Plugins Installation",
+        "OBS-Studio will be installed. Optionally select plugins to install.",
+    )
+    .selection_type(SelectionType::Multi)
+    .selection_required(false)
+    .with_bulk_toggle(true)
+    .add_option(SelectionOption::new(
+        "wayland_hotkeys",
+        "Wayland Hotkeys Plugin",
+        "Enable hotkey support for OBS on Wayland",
+        wayland_hotkeys_installed,
+    ))
+    .add_option(SelectionOption::new(
+        "graphics_capture",
+        "Graphics Capture Plugins",
+        "VkCapture, GStreamer, GStreamer VA-API",
+        graphics_capture_installed,
+    ))
+    .add_option(SelectionOption::new(
+        "transitions_effects",
+        "Transitions & Effects",
+        "Move Transition, Transition Table, Scale to Sound",
+        transitions_effects_installed,
+    ))
+    // DroidCam exposes the phone camera through the v4l2loopback device
+    .add_option(
+        SelectionOption::new(
+            "streaming_tools",
+            "Streaming & Recording Tools",
+            "WebSocket API, Scene Switcher, DroidCam (requires V4L2loopback)",
+            streaming_tools_installed,
+        )
+        .requires(&["v4l2"]),
+    )
+    .add_option(SelectionOption::new(
+        "audio_video_tools",
+        "Audio & Video Tools",
+        "Waveform, Vertical Canvas, Background Removal",
+        audio_video_tools_installed,
+    ))
+    .add_option(SelectionOption::new(
+        "v4l2",
+        "V4L2loopback Virtual Camera",
+        "Enable OBS virtual camera functionality",
+        v4l2_installed,
+    ))
+    .confirm_label("Install");
+
+    let window_for_closure = window.clone();
+    show_selection_dialog(window_ref, config, move |selected_ids| {
+        let mut commands = CommandSequence::new();
+
+        // Always install OBS-Studio
+        commands = commands.then(
+            Command::builder()
                 .normal()
                 .program("flatpak")
                 .args(&["install", "-y", "com.obsproject.Studio"])
                 .description("Installing OBS-Studio...")
-                .build());
+                .build(),
+        );
 
-            if selected_ids.iter().any(|s| s == "wayland_hotkeys") {
-                commands = commands.then(Command::builder()
+        if selected_ids.iter().any(|s| s == "wayland_hotkeys") {
+            commands = commands.then(
+                Command::builder()
                     .normal()
                     .program("flatpak")
-                    .args(&["install", "-y", "com.obsproject.Studio.Plugin.WaylandHotkeys"])
+                    .args(&[
+                        "install",
+                        "-y",
+                        "com.obsproject.Studio.Plugin.WaylandHotkeys",
+                    ])
                     .description("Installing Wayland Hotkeys plugin...")
-                    .build());
-            }
-            if selected_ids.iter().any(|s| s == "graphics_capture") {
-                commands = commands.then(Command::builder()
+                    .optional()
+                    .build(),
+            );
+        }
+        if selected_ids.iter().any(|s| s == "graphics_capture") {
+            commands = commands.then(
+                Command::builder()
                     .normal()
                     .program("flatpak")
                     .args(&[
@@ -141,10 +373,13 @@ fn setup_obs_studio_aio(builder: &Builder, window: &ApplicationWindow) {
                         "com.obsproject.Studio.Plugin.GStreamerVaapi",
                     ])
                     .description("Installing graphics capture plugins...")
-                    .build());
-            }
-            if selected_ids.iter().any(|s| s == "transitions_effects") {
-                commands = commands.then(Command::builder()
+                    .optional()
+                    .build(),
+            );
+        }
+        if selected_ids.iter().any(|s| s == "transitions_effects") {
+            commands = commands.then(
+                Command::builder()
                     .normal()
                     .program("flatpak")
                     .args(&[
@@ -155,10 +390,13 @@ fn setup_obs_studio_aio(builder: &Builder, window: &ApplicationWindow) {
                         "com.obsproject.Studio.Plugin.ScaleToSound",
                     ])
                     .description("Installing transitions & effects plugins...")
-                    .build());
-            }
-            if selected_ids.iter().any(|s| s == "streaming_tools") {
-                commands = commands.then(Command::builder()
+                    .optional()
+                    .build(),
+            );
+        }
+        if selected_ids.iter().any(|s| s == "streaming_tools") {
+            commands = commands.then(
+                Command::builder()
                     .normal()
                     .program("flatpak")
                     .args(&[
@@ -169,10 +407,13 @@ fn setup_obs_studio_aio(builder: &Builder, window: &ApplicationWindow) {
                         "com.obsproject.Studio.Plugin.DroidCam",
                     ])
                     .description("Installing streaming tools...")
-                    .build());
-            }
-            if selected_ids.iter().any(|s| s == "audio_video_tools") {
-                commands = commands.then(Command::builder()
+                    .optional()
+                    .build(),
+            );
+        }
+        if selected_ids.iter().any(|s| s == "audio_video_tools") {
+            commands = commands.then(
+                Command::builder()
                     .normal()
                     .program("flatpak")
                     .args(&[
@@ -183,39 +424,27 @@ fn setup_obs_studio_aio(builder: &Builder, window: &ApplicationWindow) {
                         "com.obsproject.Studio.Plugin.BackgroundRemoval",
                     ])
                     .description("Installing audio/video enhancement plugins...")
-                    .build());
-            }
-            if selected_ids.iter().any(|s| s == "v4l2") {
-                commands = commands.then(Command::builder()
-                    .aur()
-                    .args(&["-S", "--noconfirm", "--needed", "v4l2loopback-dkms", "v4l2loopback-utils"])
-                    .description("Installing V4L2 loopback modules...")
-                    .build());
-                commands = commands.then(Command::builder()
-                    .privileged()
-                    .program("sh")
-                    .args(&["-c", "echo 'v4l2loopback' > /etc/modules-load.d/v4l2loopback.conf"])
-                    .description("Enabling V4L2 loopback module at boot...")
-                    .build());
-                commands = commands.then(Command::builder()
-                    .privileged()
-                    .program("sh")
-                    .args(&[
-                        "-c",
-                        "echo 'options v4l2loopback exclusive_caps=1 card_label=\"OBS Virtual Camera\"' > /etc/modprobe.d/v4l2loopback.conf",
-                    ])
-                    .description("Configuring virtual camera options...")
-                    .build());
-            }
-
-            task_runner::run(window_for_closure.upcast_ref(), commands.build(), "OBS-Studio Setup");
-        });
+                    .optional()
+                    .build(),
+            );
+        }
+        if selected_ids.iter().any(|s| s == "v4l2") {
+            commands = with_v4l2loopback(commands);
+        }
+
+        task_runner::run(
+            window_for_closure.upcast_ref(),
+            commands.build(),
+            "OBS-Studio Setup",
+        );
     });
 }
 
 /// Setup system update button.
 fn setup_update_system(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_update_system");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_update_system") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -232,15 +461,99 @@ fn setup_update_system(builder: &Builder, window: &ApplicationWindow) {
     });
 }
 
+/// Setup package update button.
+///
+/// Unlike "Update System", this runs the upgrade through the task runner so
+/// download progress is shown.
+fn setup_update_packages(builder: &Builder, window: &ApplicationWindow) {
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_update_packages") else {
+        return;
+    };
+    let window = window.clone();
+
+    button.connect_clicked(move |_| {
+        info!("Update Packages button clicked");
+
+        let Some(age) = core::package::keyring_age_if_stale() else {
+            run_system_update(&window, false);
+            return;
+        };
+
+        // An old keyring is the most common reason updates fail after a
+        // system has sat idle, so offer to refresh it before upgrading.
+        info!("Arch keyring looks stale, offering to refresh it first");
+        let config = SelectionDialogConfig::new(
+            "Update System",
+            &format!(
+                "Your Arch Linux keyring was last updated {} days ago. Updating with an \
+                 outdated keyring often fails with invalid or unknown signature errors.",
+                age.as_secs() / 86_400
+            ),
+        )
+        .selection_required(false)
+        .add_option(SelectionOption::new(
+            "keyring",
+            "Refresh archlinux-keyring first",
+            "Install the latest signing keys before upgrading (recommended)",
+            false,
+        ))
+        .confirm_label("Update");
+
+        let window_clone = window.clone();
+        show_selection_dialog(window.upcast_ref(), config, move |selected| {
+            run_system_update(&window_clone, selected.iter().any(|id| id == "keyring"));
+        });
+    });
+}
+
+/// Run a full system upgrade through the AUR helper, optionally refreshing
+/// the Arch keyring first.
+fn run_system_update(window: &ApplicationWindow, refresh_keyring: bool) {
+    let mut commands = CommandSequence::new();
+
+    if refresh_keyring {
+        commands = commands.then(
+            Command::builder()
+                .privileged()
+                .program("pacman")
+                .args(&["-Sy", "--needed", "--noconfirm", "archlinux-keyring"])
+                .description("Refreshing Arch Linux keyring...")
+                .build(),
+        );
+    }
+
+    commands = commands.then(
+        Command::builder()
+            .aur()
+            .args(&["-Syu", "--noconfirm"])
+            .description("Updating system packages...")
+            .build(),
+    );
+
+    task_runner::run(window.upcast_ref(), commands.build(), "System Update");
+}
+
 /// Setup package manager GUI button.
 fn setup_pkg_manager(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_pkg_manager");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_pkg_manager") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
         info!("PKG Manager GUI button clicked");
 
         // Check which package managers are already installed
+        let installed = core::are_packages_installed(&[
+            "octopi",
+            "pacseek",
+            "bauh",
+            "io.github.flattool.Warehouse",
+            "com.github.tchx84.Flatseal",
+            "io.github.kolunmi.Bazaar",
+        ]);
+        let is_installed = |package: &str| installed.get(package).copied().unwrap_or(false);
+
         let config = SelectionDialogConfig::new(
             "Package Manager GUI Applications",
             "Select which package manager GUIs to install. Multiple selections allowed.",
@@ -257,37 +570,37 @@ fn setup_pkg_manager(builder: &Builder, window: &ApplicationWindow) {
             "octopi",
             "Octopi",
             "Powerful Pacman GUI with AUR support",
-            core::is_package_installed("octopi"),
+            is_installed("octopi"),
         ))
         .add_option(SelectionOption::new(
             "pacseek",
             "PacSeek",
             "Terminal UI package manager with search",
-            core::is_package_installed("pacseek"),
+            is_installed("pacseek"),
         ))
         .add_option(SelectionOption::new(
             "bauh",
             "Bauh",
             "Manage Pacman, AUR, Flatpak, Snap packages",
-            core::is_package_installed("bauh"),
+            is_installed("bauh"),
         ))
         .add_option(SelectionOption::new(
             "warehouse",
             "Warehouse",
             "Flatpak package manager (Flatpak)",
-            core::is_flatpak_installed("io.github.flattool.Warehouse"),
+            is_installed("io.github.flattool.Warehouse"),
         ))
         .add_option(SelectionOption::new(
             "flatseal",
             "Flatseal",
             "Flatpak permissions manager (Flatpak)",
-            core::is_flatpak_installed("com.github.tchx84.Flatseal"),
+            is_installed("com.github.tchx84.Flatseal"),
         ))
         .add_option(SelectionOption::new(
             "bazaar",
             "Bazaar",
             "Browse and install Flatpak apps (Flatpak)",
-            core::is_flatpak_installed("io.github.kolunmi.Bazaar"),
+            is_installed("io.github.kolunmi.Bazaar"),
         ))
         .confirm_label("Install");
 
@@ -388,7 +701,9 @@ fn build_pkg_manager_commands(selected: &[String]) -> CommandSequence {
 
 /// Setup download Arch ISO button.
 fn setup_download_arch_iso(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_download_arch_iso");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_download_arch_iso") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -400,7 +715,9 @@ fn setup_download_arch_iso(builder: &Builder, window: &ApplicationWindow) {
 
 /// Setup Nix package manager installation button.
 fn setup_install_nix(builder: &Builder, window: &ApplicationWindow) {
-    let button = extract_widget::<Button>(builder, "btn_install_nix");
+    let Some(button) = try_extract_widget::<Button>(builder, "btn_install_nix") else {
+        return;
+    };
     let window = window.clone();
 
     button.connect_clicked(move |_| {
@@ -470,22 +787,25 @@ fn setup_install_nix(builder: &Builder, window: &ApplicationWindow) {
 }
 
 /// Setup external link buttons.
-fn setup_external_links(builder: &Builder) {
+fn setup_external_links(builder: &Builder, window: &ApplicationWindow) {
     let btn_youtube = extract_widget::<Button>(builder, "link_youtube");
-    btn_youtube.connect_clicked(|_| {
+    let window_clone = window.clone();
+    btn_youtube.connect_clicked(move |_| {
         info!("YouTube link clicked");
-        let _ = core::package::open_url(config::links::YOUTUBE);
+        error::open_url_or_show_error(&window_clone, config::links::YOUTUBE);
     });
 
     let btn_website = extract_widget::<Button>(builder, "link_website");
-    btn_website.connect_clicked(|_| {
+    let window_clone = window.clone();
+    btn_website.connect_clicked(move |_| {
         info!("Website link clicked");
-        let _ = core::package::open_url(config::links::WEBSITE);
+        error::open_url_or_show_error(&window_clone, config::links::WEBSITE);
     });
 
     let btn_donate = extract_widget::<Button>(builder, "link_donate");
-    btn_donate.connect_clicked(|_| {
+    let window_clone = window.clone();
+    btn_donate.connect_clicked(move |_| {
         info!("Donate link clicked");
-        let _ = core::package::open_url(config::links::DONATE);
+        error::open_url_or_show_error(&window_clone, config::links::DONATE);
     });
 }
@@ -0,0 +1,271 @@
+//! MangoHud configuration page.
+//!
+//! Handles the logic for the MangoHud config generator.
+//! Every toggle rebuilds the config preview; "Apply" writes it to
+//! `~/.config/MangoHud/MangoHud.conf`, which MangoHud reads for every game.
+//! Numeric fields are validated the same way as on the Gamescope page.
+
+use crate::ui::dialogs::error::show_error;
+use crate::ui::dialogs::warning::show_warning_confirmation;
+use crate::ui::utils::{
+    extract_widget, get_combo_row_value, is_positive_integer, is_unit_fraction, show_toast,
+};
+use adw::prelude::*;
+use adw::{ComboRow, EntryRow};
+use gtk4::{glib, ApplicationWindow, Builder, Button, Switch, TextView};
+use log::{info, warn};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Set up all handlers for the MangoHud page.
+pub fn setup_handlers(page_builder: &Builder, main_builder: &Builder, window: &ApplicationWindow) {
+    let widgets = Rc::new(extract_all_widgets(page_builder));
+
+    connect_widget_signals(&widgets);
+    setup_copy_button(&widgets, main_builder);
+    setup_apply_button(&widgets, main_builder, window);
+
+    // Generate initial config
+    update_config_preview(&widgets);
+}
+
+/// Extract all widgets from the UI builder.
+fn extract_all_widgets(builder: &Builder) -> MangoHudWidgets {
+    MangoHudWidgets {
+        // Switches paired with the MangoHud option they enable
+        switches: [
+            ("fps", "switch_fps"),
+            ("frametime", "switch_frametime"),
+            ("frame_timing", "switch_frame_timing"),
+            ("show_fps_limit", "switch_show_fps_limit"),
+            ("cpu_stats", "switch_cpu_stats"),
+            ("cpu_temp", "switch_cpu_temp"),
+            ("gpu_stats", "switch_gpu_stats"),
+            ("gpu_temp", "switch_gpu_temp"),
+            ("gpu_power", "switch_gpu_power"),
+            ("vram", "switch_vram"),
+            ("ram", "switch_ram"),
+            ("gamemode", "switch_gamemode"),
+            ("wine", "switch_wine"),
+            ("engine_version", "switch_engine_version"),
+            ("horizontal", "switch_horizontal"),
+        ]
+        .into_iter()
+        .map(|(option, id)| (option, extract_widget(builder, id)))
+        .collect(),
+
+        // Layout
+        combo_position: extract_widget(builder, "combo_position"),
+        entry_font_size: extract_widget(builder, "entry_font_size"),
+        entry_background_alpha: extract_widget(builder, "entry_background_alpha"),
+        entry_fps_limit: extract_widget(builder, "entry_fps_limit"),
+
+        // Output
+        text_config_preview: extract_widget(builder, "text_config_preview"),
+        btn_copy_config: extract_widget(builder, "btn_copy_mangohud_config"),
+        btn_apply_config: extract_widget(builder, "btn_apply_mangohud_config"),
+    }
+}
+
+/// Connect all widget signals to regenerate the config on changes.
+fn connect_widget_signals(widgets: &Rc<MangoHudWidgets>) {
+    for (_, switch) in &widgets.switches {
+        let widgets_clone = widgets.clone();
+        switch.connect_active_notify(move |_| {
+            update_config_preview(&widgets_clone);
+        });
+    }
+
+    for entry in widgets.numeric_entries() {
+        let widgets_clone = widgets.clone();
+        entry.connect_notify_local(Some("text"), move |_, _| {
+            update_config_preview(&widgets_clone);
+        });
+    }
+
+    let widgets_clone = widgets.clone();
+    widgets.combo_position.connect_selected_notify(move |_| {
+        update_config_preview(&widgets_clone);
+    });
+}
+
+/// Set up the copy button to copy the config to clipboard.
+fn setup_copy_button(widgets: &Rc<MangoHudWidgets>, main_builder: &Builder) {
+    let widgets_clone = widgets.clone();
+    let main_builder = main_builder.clone();
+    widgets.btn_copy_config.connect_clicked(move |_| {
+        let text = widgets_clone.config_text();
+        if let Some(display) = gtk4::gdk::Display::default() {
+            display.clipboard().set(&text);
+            info!("Copied MangoHud config to clipboard");
+            show_toast(&main_builder, "Config copied to clipboard");
+        }
+    });
+}
+
+/// Set up the apply button to write the config file.
+fn setup_apply_button(
+    widgets: &Rc<MangoHudWidgets>,
+    main_builder: &Builder,
+    window: &ApplicationWindow,
+) {
+    let widgets_clone = widgets.clone();
+    let main_builder = main_builder.clone();
+    let window = window.clone();
+    widgets.btn_apply_config.connect_clicked(move |_| {
+        let text = widgets_clone.config_text();
+        let path = config_path();
+
+        if !path.exists() {
+            write_config(&window, &main_builder, &path, &text);
+            return;
+        }
+
+        let window_clone = window.clone();
+        let main_builder = main_builder.clone();
+        show_warning_confirmation(
+            window.upcast_ref(),
+            "Replace MangoHud Config",
+            &format!(
+                "{} already exists and will be overwritten.\n\nContinue?",
+                glib::markup_escape_text(&path.to_string_lossy())
+            ),
+            move || write_config(&window_clone, &main_builder, &path, &text),
+        );
+    });
+}
+
+/// Path of the user's MangoHud config file.
+fn config_path() -> PathBuf {
+    PathBuf::from(&crate::config::env::get().home).join(".config/MangoHud/MangoHud.conf")
+}
+
+/// Write the config to `path`, reporting the outcome to the user.
+fn write_config(window: &ApplicationWindow, main_builder: &Builder, path: &Path, text: &str) {
+    let result = match path.parent() {
+        Some(dir) => std::fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+    .and_then(|_| std::fs::write(path, text));
+
+    match result {
+        Ok(()) => {
+            info!("Wrote MangoHud config to {}", path.display());
+            show_toast(main_builder, "MangoHud config saved");
+        }
+        Err(e) => {
+            warn!("Failed to write MangoHud config: {}", e);
+            show_error(window, &format!("Couldn't write {}: {}", path.display(), e));
+        }
+    }
+}
+
+/// Update the preview with the generated config.
+fn update_config_preview(widgets: &MangoHudWidgets) {
+    let config = build_mangohud_config(widgets);
+    widgets.text_config_preview.buffer().set_text(&config);
+
+    // Don't let an incomplete config be copied or saved while a field is invalid
+    let all_valid = widgets
+        .numeric_entries()
+        .iter()
+        .all(|entry| !entry.has_css_class("error"));
+    widgets.btn_copy_config.set_sensitive(all_valid);
+    widgets.btn_apply_config.set_sensitive(all_valid);
+}
+
+/// All widgets needed for config generation
+struct MangoHudWidgets {
+    switches: Vec<(&'static str, Switch)>,
+    combo_position: ComboRow,
+    entry_font_size: EntryRow,
+    entry_background_alpha: EntryRow,
+    entry_fps_limit: EntryRow,
+    text_config_preview: TextView,
+    btn_copy_config: Button,
+    btn_apply_config: Button,
+}
+
+impl MangoHudWidgets {
+    /// Entries that only accept numbers.
+    fn numeric_entries(&self) -> [&EntryRow; 3] {
+        [
+            &self.entry_font_size,
+            &self.entry_background_alpha,
+            &self.entry_fps_limit,
+        ]
+    }
+
+    /// The config text currently shown in the preview.
+    fn config_text(&self) -> String {
+        let buffer = self.text_config_preview.buffer();
+        buffer
+            .text(&buffer.start_iter(), &buffer.end_iter(), false)
+            .to_string()
+    }
+}
+
+/// Build the MangoHud config from widget values.
+fn build_mangohud_config(widgets: &MangoHudWidgets) -> String {
+    let mut lines = vec![
+        "# Generated by XeroLinux Toolkit".to_string(),
+        // Only show what's listed below instead of MangoHud's default set
+        "legacy_layout=0".to_string(),
+    ];
+
+    for (option, switch) in &widgets.switches {
+        if switch.is_active() {
+            lines.push(option.to_string());
+        }
+    }
+
+    if let Some(position) = get_combo_row_value(&widgets.combo_position) {
+        lines.push(format!("position={}", position));
+    }
+
+    add_numeric_option(
+        &mut lines,
+        "font_size",
+        &widgets.entry_font_size,
+        is_positive_integer,
+    );
+    add_numeric_option(
+        &mut lines,
+        "background_alpha",
+        &widgets.entry_background_alpha,
+        is_unit_fraction,
+    );
+    add_numeric_option(
+        &mut lines,
+        "fps_limit",
+        &widgets.entry_fps_limit,
+        is_positive_integer,
+    );
+
+    let mut config = lines.join("\n");
+    config.push('\n');
+    config
+}
+
+/// Add a `key=value` option with a numeric value from an entry.
+///
+/// Values that fail `is_valid` mark the entry with the `error` CSS class and
+/// are left out of the config.
+fn add_numeric_option(
+    lines: &mut Vec<String>,
+    key: &str,
+    entry: &EntryRow,
+    is_valid: fn(&str) -> bool,
+) {
+    let text = entry.text();
+    let value = text.trim();
+
+    if value.is_empty() || is_valid(value) {
+        entry.remove_css_class("error");
+        if !value.is_empty() {
+            lines.push(format!("{}={}", key, value));
+        }
+    } else {
+        entry.add_css_class("error");
+    }
+}
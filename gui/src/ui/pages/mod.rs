@@ -5,11 +5,13 @@
 //! - `drivers`: GPU drivers, Tailscale, ASUS ROG tools
 //! - `gaming_tools`: Steam, controllers, game launchers
 //! - `gamescope`: Gamescope command generator
+//! - `mangohud`: MangoHud config generator
 //! - `containers_vms`: Docker, Podman, VirtualBox, KVM
 //! - `customization`: ZSH, themes, wallpapers
 //! - `kernel_schedulers`: Kernel Manager and SCX Scheduler (with subtabs)
 //! - `servicing`: System fixes and maintenance
 //! - `biometrics`: Fingerprint and facial recognition setup
+//! - `settings`: Toolkit preferences (autostart, AUR helper, seasonal effects)
 
 pub mod biometrics;
 pub mod containers_vms;
@@ -19,4 +21,6 @@ pub mod gamescope;
 pub mod gaming_tools;
 pub mod kernel_schedulers;
 pub mod main_page;
+pub mod mangohud;
 pub mod servicing;
+pub mod settings;
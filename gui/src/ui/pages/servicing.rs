@@ -10,17 +10,27 @@
 //! - Fix Arch keyring
 //! - Update mirrorlist
 //! - Parallel downloads adjustment
+//! - Installing an arbitrary package by name
+//! - Cleaning the package cache and orphaned packages
+//! - Ranking mirrors for a country with reflector
 
 use crate::core;
+use crate::core::download::format_bytes;
+use crate::core::mirrors;
+use crate::ui::dialogs::error;
+use crate::ui::dialogs::package_install;
 use crate::ui::dialogs::selection::{
     show_selection_dialog, SelectionDialogConfig, SelectionOption, SelectionType,
 };
 use crate::ui::dialogs::terminal;
+use crate::ui::dialogs::warning::show_warning_confirmation;
 use crate::ui::task_runner::{self, Command, CommandSequence};
-use crate::ui::utils::extract_widget;
-use gtk4::prelude::*;
-use gtk4::{ApplicationWindow, Builder};
-use log::info;
+use crate::ui::utils::{command_exists, set_button_spinning, try_extract_widget};
+use adw::prelude::*;
+use gtk4::{glib, ApplicationWindow, Builder};
+use log::{info, warn};
+use std::sync::mpsc::{self, TryRecvError};
+use std::time::Duration;
 
 /// Set up all button handlers for the servicing/system tweaks page
 pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &ApplicationWindow) {
@@ -28,15 +38,21 @@ pub fn setup_handlers(page_builder: &Builder, _main_builder: &Builder, window: &
     setup_unlock_pacman(page_builder, window);
     setup_plasma_x11(page_builder, window);
     setup_pacman_db_fix(page_builder, window);
-    setup_waydroid_guide(page_builder);
+    setup_waydroid_guide(page_builder, window);
     setup_fix_gpgme(page_builder, window);
     setup_fix_arch_keyring(page_builder, window);
     setup_update_mirrorlist(page_builder, window);
     setup_parallel_downloads(page_builder, window);
+    setup_install_package(page_builder, window);
+    setup_clean_package_cache(page_builder, window);
+    setup_rank_mirrors(page_builder, window);
 }
 
 fn setup_clr_pacman(page_builder: &Builder, window: &ApplicationWindow) {
-    let btn_clr_pacman = extract_widget::<gtk4::Button>(page_builder, "btn_clr_pacman");
+    let Some(btn_clr_pacman) = try_extract_widget::<gtk4::Button>(page_builder, "btn_clr_pacman")
+    else {
+        return;
+    };
     let window = window.clone();
     btn_clr_pacman.connect_clicked(move |_| {
         info!("Servicing: Clear Pacman Cache button clicked");
@@ -52,7 +68,11 @@ fn setup_clr_pacman(page_builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_unlock_pacman(page_builder: &Builder, window: &ApplicationWindow) {
-    let btn_unlock_pacman = extract_widget::<gtk4::Button>(page_builder, "btn_unlock_pacman");
+    let Some(btn_unlock_pacman) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_unlock_pacman")
+    else {
+        return;
+    };
     let window = window.clone();
     btn_unlock_pacman.connect_clicked(move |_| {
         info!("Servicing: Unlock Pacman DB button clicked");
@@ -71,7 +91,10 @@ fn setup_unlock_pacman(page_builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_plasma_x11(page_builder: &Builder, window: &ApplicationWindow) {
-    let btn_plasma_x11 = extract_widget::<gtk4::Button>(page_builder, "btn_plasma_x11");
+    let Some(btn_plasma_x11) = try_extract_widget::<gtk4::Button>(page_builder, "btn_plasma_x11")
+    else {
+        return;
+    };
     let window = window.clone();
     btn_plasma_x11.connect_clicked(move |_| {
         info!("Servicing: Plasma X11 Session button clicked");
@@ -89,7 +112,11 @@ fn setup_plasma_x11(page_builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_pacman_db_fix(page_builder: &Builder, window: &ApplicationWindow) {
-    let btn_pacman_db_fix = extract_widget::<gtk4::Button>(page_builder, "btn_pacman_db_fix");
+    let Some(btn_pacman_db_fix) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_pacman_db_fix")
+    else {
+        return;
+    };
     let window = window.clone();
     btn_pacman_db_fix.connect_clicked(move |_| {
         info!("Servicing: Pacman DB Fix button clicked");
@@ -110,18 +137,24 @@ fn setup_pacman_db_fix(page_builder: &Builder, window: &ApplicationWindow) {
     });
 }
 
-fn setup_waydroid_guide(page_builder: &Builder) {
-    let btn_waydroid_guide = extract_widget::<gtk4::Button>(page_builder, "btn_waydroid_guide");
+fn setup_waydroid_guide(page_builder: &Builder, window: &ApplicationWindow) {
+    let Some(btn_waydroid_guide) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_waydroid_guide")
+    else {
+        return;
+    };
+    let window = window.clone();
     btn_waydroid_guide.connect_clicked(move |_| {
         info!("Servicing: WayDroid Guide button clicked - opening guide");
-        let _ = std::process::Command::new("xdg-open")
-            .arg("https://xerolinux.xyz/posts/waydroid-guide/")
-            .spawn();
+        error::open_url_or_show_error(&window, crate::config::links::WAYDROID_GUIDE);
     });
 }
 
 fn setup_fix_gpgme(page_builder: &Builder, window: &ApplicationWindow) {
-    let btn_fix_gpgme = extract_widget::<gtk4::Button>(page_builder, "btn_fix_gpgme");
+    let Some(btn_fix_gpgme) = try_extract_widget::<gtk4::Button>(page_builder, "btn_fix_gpgme")
+    else {
+        return;
+    };
     let window = window.clone();
     btn_fix_gpgme.connect_clicked(move |_| {
         info!("Servicing: Fix GPGME Database button clicked");
@@ -137,7 +170,11 @@ fn setup_fix_gpgme(page_builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_fix_arch_keyring(page_builder: &Builder, window: &ApplicationWindow) {
-    let btn_fix_arch_keyring = extract_widget::<gtk4::Button>(page_builder, "btn_fix_arch_keyring");
+    let Some(btn_fix_arch_keyring) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_fix_arch_keyring")
+    else {
+        return;
+    };
     let window = window.clone();
     btn_fix_arch_keyring.connect_clicked(move |_| {
         info!("Servicing: Fix Arch Keyring button clicked");
@@ -178,8 +215,11 @@ fn setup_fix_arch_keyring(page_builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_update_mirrorlist(page_builder: &Builder, window: &ApplicationWindow) {
-    let btn_update_mirrorlist =
-        extract_widget::<gtk4::Button>(page_builder, "btn_update_mirrorlist");
+    let Some(btn_update_mirrorlist) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_update_mirrorlist")
+    else {
+        return;
+    };
     let window = window.clone();
     btn_update_mirrorlist.connect_clicked(move |_| {
         info!("Servicing: Update Mirrorlist button clicked");
@@ -236,8 +276,11 @@ fn setup_update_mirrorlist(page_builder: &Builder, window: &ApplicationWindow) {
 }
 
 fn setup_parallel_downloads(page_builder: &Builder, window: &ApplicationWindow) {
-    let btn_parallel_downloads =
-        extract_widget::<gtk4::Button>(page_builder, "btn_parallel_downloads");
+    let Some(btn_parallel_downloads) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_parallel_downloads")
+    else {
+        return;
+    };
     let window = window.clone();
     btn_parallel_downloads.connect_clicked(move |_| {
         info!("Servicing: Change Parallel Downloads button clicked");
@@ -251,3 +294,343 @@ fn setup_parallel_downloads(page_builder: &Builder, window: &ApplicationWindow)
         );
     });
 }
+
+fn setup_install_package(page_builder: &Builder, window: &ApplicationWindow) {
+    let Some(btn_install_package) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_install_package")
+    else {
+        return;
+    };
+    let window = window.clone();
+    btn_install_package.connect_clicked(move |_| {
+        info!("Servicing: Install a Package button clicked");
+        package_install::show_package_install_dialog(window.upcast_ref());
+    });
+}
+
+fn setup_clean_package_cache(page_builder: &Builder, window: &ApplicationWindow) {
+    let Some(btn_clean_package_cache) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_clean_package_cache")
+    else {
+        return;
+    };
+    let window = window.clone();
+    btn_clean_package_cache.connect_clicked(move |button| {
+        info!("Servicing: Clean Package Cache button clicked");
+
+        // Sizing the cache walks every cached package, so keep it off the main thread
+        set_button_spinning(button, true);
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send((core::pacman_cache_size(), core::orphan_packages()));
+        });
+
+        let button = button.clone();
+        let window = window.clone();
+        glib::timeout_add_local(Duration::from_millis(50), move || {
+            let result = match rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                Err(TryRecvError::Disconnected) => None,
+            };
+            set_button_spinning(&button, false);
+            match result {
+                Some((cache_size, orphans)) => {
+                    show_clean_package_cache_dialog(&window, cache_size, orphans)
+                }
+                None => warn!("Package cache scan ended without a result"),
+            }
+            glib::ControlFlow::Break
+        });
+    });
+}
+
+/// Ask what to clean, given the current cache size and orphaned packages.
+fn show_clean_package_cache_dialog(
+    window: &ApplicationWindow,
+    cache_size: u64,
+    orphans: Vec<String>,
+) {
+    let mut config = SelectionDialogConfig::new(
+        "Clean Package Cache",
+        &format!(
+            "The package cache currently uses {}. Select what to clean up.",
+            format_bytes(cache_size)
+        ),
+    )
+    .add_option(
+        SelectionOption::new(
+            "cache",
+            "Old Package Versions",
+            "Keep only the two most recent versions of each cached package",
+            false,
+        )
+        .selected(true),
+    )
+    .confirm_label("Clean");
+
+    if !orphans.is_empty() {
+        config = config.add_option(SelectionOption::new(
+            "orphans",
+            "Orphaned Packages",
+            &format!(
+                "Uninstall {} package(s) no longer required by anything",
+                orphans.len()
+            ),
+            false,
+        ));
+    }
+
+    let window_clone = window.clone();
+    show_selection_dialog(window.upcast_ref(), config, move |selected_ids| {
+        let trim_cache = selected_ids.iter().any(|id| id == "cache");
+        if !selected_ids.iter().any(|id| id == "orphans") {
+            clean_package_cache(&window_clone, cache_size, trim_cache, &[]);
+            return;
+        }
+
+        // Orphans are uninstalled, not just uncached, so spell out what goes
+        let window_for_cleanup = window_clone.clone();
+        let orphans = orphans.clone();
+        show_warning_confirmation(
+            window_clone.upcast_ref(),
+            "Remove Orphaned Packages",
+            &format!(
+                "The following packages were installed as dependencies and nothing \
+                 requires them anymore. They will be <b>uninstalled</b> along with \
+                 their configuration files:\n\n<tt>{}</tt>\n\nContinue?",
+                glib::markup_escape_text(&orphans.join(" "))
+            ),
+            move || clean_package_cache(&window_for_cleanup, cache_size, trim_cache, &orphans),
+        );
+    });
+}
+
+/// Trim the package cache and remove `orphans`, then report the space freed.
+fn clean_package_cache(
+    window: &ApplicationWindow,
+    cache_size: u64,
+    trim_cache: bool,
+    orphans: &[String],
+) {
+    let mut commands = CommandSequence::new();
+
+    if trim_cache {
+        if !command_exists("paccache") {
+            commands = commands.then(
+                Command::builder()
+                    .privileged()
+                    .program("pacman")
+                    .args(&["-S", "--needed", "--noconfirm", "pacman-contrib"])
+                    .description("Installing pacman-contrib for paccache...")
+                    .build(),
+            );
+        }
+        commands = commands.then(
+            Command::builder()
+                .privileged()
+                .program("paccache")
+                .args(&["-rk2"])
+                .description("Removing old cached package versions...")
+                .build(),
+        );
+    }
+
+    if !orphans.is_empty() {
+        let mut args = vec!["-Rns", "--noconfirm"];
+        args.extend(orphans.iter().map(String::as_str));
+        commands = commands.then(
+            Command::builder()
+                .privileged()
+                .program("pacman")
+                .args(&args)
+                .description("Removing orphaned packages...")
+                .build(),
+        );
+    }
+
+    if commands.is_empty() {
+        return;
+    }
+
+    let window_clone = window.clone();
+    task_runner::run_with_on_complete(
+        window.upcast_ref(),
+        commands.build(),
+        "Clean Package Cache",
+        move |success| {
+            if !success {
+                return;
+            }
+
+            let (tx, rx) = mpsc::channel();
+            std::thread::spawn(move || {
+                let _ = tx.send(core::pacman_cache_size());
+            });
+
+            glib::timeout_add_local(Duration::from_millis(50), move || {
+                let new_size = match rx.try_recv() {
+                    Ok(size) => size,
+                    Err(TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                    Err(TryRecvError::Disconnected) => return glib::ControlFlow::Break,
+                };
+                let freed = cache_size.saturating_sub(new_size);
+                info!("Package cache cleanup freed {} bytes", freed);
+                let dialog = adw::AlertDialog::builder()
+                    .heading("Package Cache Cleaned")
+                    .body(format!(
+                        "Freed {} from the package cache.",
+                        format_bytes(freed)
+                    ))
+                    .build();
+                dialog.add_response("close", "Close");
+                dialog.present(Some(&window_clone));
+                glib::ControlFlow::Break
+            });
+        },
+    );
+}
+
+/// Mirrorlist replaced by reflector, and where the previous one is kept.
+const MIRRORLIST: &str = "/etc/pacman.d/mirrorlist";
+const MIRRORLIST_BACKUP: &str = "/etc/pacman.d/mirrorlist.bak";
+
+/// Label of the country choice that ranks mirrors from every country.
+const WORLDWIDE: &str = "Worldwide";
+
+fn setup_rank_mirrors(page_builder: &Builder, window: &ApplicationWindow) {
+    let Some(btn_rank_mirrors) =
+        try_extract_widget::<gtk4::Button>(page_builder, "btn_rank_mirrors")
+    else {
+        return;
+    };
+    let window = window.clone();
+    btn_rank_mirrors.connect_clicked(move |button| {
+        info!("Servicing: Rank Mirrors button clicked");
+
+        // Detection may fall back to a GeoIP lookup, so keep it off the main thread
+        set_button_spinning(button, true);
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(mirrors::detect_country());
+        });
+
+        let button = button.clone();
+        let window = window.clone();
+        glib::timeout_add_local(Duration::from_millis(50), move || {
+            let country = match rx.try_recv() {
+                Ok(country) => country,
+                Err(TryRecvError::Empty) => return glib::ControlFlow::Continue,
+                Err(TryRecvError::Disconnected) => None,
+            };
+            set_button_spinning(&button, false);
+            show_rank_mirrors_dialog(&window, country);
+            glib::ControlFlow::Break
+        });
+    });
+}
+
+/// Ask which country to rank mirrors for, preselecting `detected`.
+fn show_rank_mirrors_dialog(window: &ApplicationWindow, detected: Option<&'static str>) {
+    let mut labels = vec![WORLDWIDE];
+    labels.extend(mirrors::COUNTRIES.iter().map(|(_, name)| *name));
+
+    let combo = adw::ComboRow::builder()
+        .title("Country")
+        .model(&gtk4::StringList::new(&labels))
+        .build();
+    if let Some(index) = detected.and_then(|code| {
+        mirrors::COUNTRIES
+            .iter()
+            .position(|(known, _)| *known == code)
+    }) {
+        // Offset by the leading "Worldwide" entry
+        combo.set_selected(index as u32 + 1);
+    }
+
+    let list = gtk4::ListBox::builder()
+        .selection_mode(gtk4::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    list.append(&combo);
+
+    let dialog = adw::AlertDialog::builder()
+        .heading("Rank Mirrors")
+        .body(format!(
+            "reflector will test recent HTTPS mirrors and write the fastest to {}. \
+             The current list is backed up to {}.",
+            MIRRORLIST, MIRRORLIST_BACKUP
+        ))
+        .build();
+    dialog.set_extra_child(Some(&list));
+    dialog.add_response("cancel", "Cancel");
+    dialog.add_response("rank", "Rank Mirrors");
+    dialog.set_response_appearance("rank", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("rank"));
+    dialog.set_close_response("cancel");
+
+    let window_clone = window.clone();
+    dialog.connect_response(Some("rank"), move |_, _| {
+        // Index 0 is "Worldwide", which ranks without a country filter
+        let country = (combo.selected() as usize)
+            .checked_sub(1)
+            .and_then(|index| mirrors::COUNTRIES.get(index))
+            .map(|(code, _)| *code);
+        rank_mirrors(&window_clone, country);
+    });
+
+    dialog.present(Some(window));
+}
+
+/// Back up the mirrorlist and replace it with reflector's fastest mirrors.
+fn rank_mirrors(window: &ApplicationWindow, country: Option<&str>) {
+    info!("Ranking mirrors for {}", country.unwrap_or(WORLDWIDE));
+    let mut commands = CommandSequence::new();
+
+    if !command_exists("reflector") {
+        commands = commands.then(
+            Command::builder()
+                .privileged()
+                .program("pacman")
+                .args(&["-S", "--needed", "--noconfirm", "reflector"])
+                .description("Installing reflector...")
+                .build(),
+        );
+    }
+
+    let mut args = vec![
+        "--protocol",
+        "https",
+        "--age",
+        "12",
+        "--latest",
+        "20",
+        "--sort",
+        "rate",
+        "--save",
+        MIRRORLIST,
+    ];
+    if let Some(country) = country {
+        args.extend(["--country", country]);
+    }
+
+    commands = commands
+        .then(
+            Command::builder()
+                .privileged()
+                .program("cp")
+                .args(&[MIRRORLIST, MIRRORLIST_BACKUP])
+                .description("Backing up current mirrorlist...")
+                .build(),
+        )
+        .then(
+            Command::builder()
+                .privileged()
+                .program("reflector")
+                .args(&args)
+                .description("Ranking the fastest mirrors...")
+                .build(),
+        );
+
+    task_runner::run(window.upcast_ref(), commands.build(), "Rank Mirrors");
+}
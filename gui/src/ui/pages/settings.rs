@@ -0,0 +1,259 @@
+//! Settings page handlers.
+//!
+//! Handles:
+//! - Start on login
+//! - Preferred AUR helper
+//! - Install confirmation level
+//! - Per-effect seasonal effect toggles
+//! - Resetting all settings to their defaults
+
+use crate::config;
+use crate::config::user::ConfirmationLevel;
+use crate::core;
+use crate::ui::dialogs::warning::show_warning_confirmation;
+use crate::ui::seasonal;
+use crate::ui::utils::{extract_widget, get_combo_row_value, show_toast};
+use adw::prelude::*;
+use gtk4::{ApplicationWindow, Builder, Button};
+use log::{info, warn};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Label for letting the toolkit pick the AUR helper itself.
+const AUTOMATIC_AUR_HELPER: &str = "Automatic";
+
+/// Confirmation levels in the order they are listed, with their labels.
+const CONFIRMATION_LEVELS: [(ConfirmationLevel, &str); 3] = [
+    (ConfirmationLevel::Silent, "Never"),
+    (
+        ConfirmationLevel::ConfirmConflicts,
+        "On Conflicts and Upgrades",
+    ),
+    (ConfirmationLevel::AlwaysConfirm, "Always"),
+];
+
+/// Set up all handlers for the settings page
+pub fn setup_handlers(page_builder: &Builder, main_builder: &Builder, window: &ApplicationWindow) {
+    let autostart_row = setup_autostart(page_builder, main_builder);
+    let aur_helper_row = setup_aur_helper(page_builder, main_builder);
+    let confirmation_row = setup_confirmation_level(page_builder);
+    let effect_rows = setup_seasonal_effects(page_builder, window);
+    setup_reset_settings(
+        page_builder,
+        main_builder,
+        window,
+        autostart_row,
+        aur_helper_row,
+        confirmation_row,
+        effect_rows,
+    );
+}
+
+fn setup_autostart(builder: &Builder, main_builder: &Builder) -> adw::SwitchRow {
+    let row = extract_widget::<adw::SwitchRow>(builder, "switch_autostart");
+    row.set_active(config::user::shared().borrow().general.autostart);
+
+    // Set while the row is flipped back after a failure, so it isn't applied again
+    let reverting = Rc::new(Cell::new(false));
+    let main_builder = main_builder.clone();
+    row.connect_active_notify(move |row| {
+        if reverting.get() {
+            return;
+        }
+
+        let state = row.is_active();
+        info!("Autostart toggle changed to: {}", state);
+
+        let result = if state {
+            core::autostart::enable()
+        } else {
+            core::autostart::disable()
+        };
+
+        if let Err(e) = result {
+            warn!(
+                "Failed to {} autostart: {}",
+                if state { "enable" } else { "disable" },
+                e
+            );
+            show_toast(&main_builder, &format!("Couldn't change autostart: {}", e));
+            reverting.set(true);
+            row.set_active(!state);
+            reverting.set(false);
+            return;
+        }
+
+        // Update in-memory config; actual persistence happens on app shutdown.
+        config::user::shared().borrow_mut().general.autostart = state;
+        show_toast(
+            &main_builder,
+            if state {
+                "Autostart enabled"
+            } else {
+                "Autostart disabled"
+            },
+        );
+    });
+
+    row
+}
+
+fn setup_aur_helper(builder: &Builder, main_builder: &Builder) -> adw::ComboRow {
+    let combo = extract_widget::<adw::ComboRow>(builder, "combo_aur_helper");
+
+    let helpers = core::aur::installed_helpers();
+    let mut labels = vec![AUTOMATIC_AUR_HELPER];
+    labels.extend(helpers.iter().copied());
+    combo.set_model(Some(&gtk4::StringList::new(&labels)));
+
+    let preferred = config::user::shared()
+        .borrow()
+        .general
+        .preferred_aur_helper
+        .clone();
+    let selected = preferred
+        .as_deref()
+        .and_then(|name| labels.iter().position(|&label| label == name))
+        .unwrap_or(0);
+    combo.set_selected(selected as u32);
+
+    let main_builder = main_builder.clone();
+    combo.connect_selected_notify(move |combo| {
+        let preferred = get_combo_row_value(combo).filter(|value| value != AUTOMATIC_AUR_HELPER);
+        info!(
+            "Preferred AUR helper changed to: {}",
+            preferred.as_deref().unwrap_or(AUTOMATIC_AUR_HELPER)
+        );
+
+        if core::aur::init(preferred.as_deref()) {
+            let helper = core::aur_helper().unwrap_or(AUTOMATIC_AUR_HELPER);
+            show_toast(&main_builder, &format!("Using {} for AUR packages", helper));
+        } else {
+            warn!("No AUR helper available after changing preference");
+        }
+
+        // Update in-memory config; actual persistence happens on app shutdown.
+        config::user::shared()
+            .borrow_mut()
+            .general
+            .preferred_aur_helper = preferred;
+    });
+
+    combo
+}
+
+fn setup_confirmation_level(builder: &Builder) -> adw::ComboRow {
+    let combo = extract_widget::<adw::ComboRow>(builder, "combo_confirmation_level");
+
+    let labels: Vec<&str> = CONFIRMATION_LEVELS
+        .iter()
+        .map(|(_, label)| *label)
+        .collect();
+    combo.set_model(Some(&gtk4::StringList::new(&labels)));
+
+    let current = config::user::shared().borrow().general.confirmation_level;
+    let selected = CONFIRMATION_LEVELS
+        .iter()
+        .position(|(level, _)| *level == current)
+        .unwrap_or(0);
+    combo.set_selected(selected as u32);
+
+    combo.connect_selected_notify(move |combo| {
+        let Some((level, _)) = CONFIRMATION_LEVELS.get(combo.selected() as usize) else {
+            return;
+        };
+        info!("Install confirmation level changed to: {:?}", level);
+
+        // Update in-memory config; actual persistence happens on app shutdown.
+        config::user::shared()
+            .borrow_mut()
+            .general
+            .confirmation_level = *level;
+    });
+
+    combo
+}
+
+/// Add a switch per seasonal effect. Returns each row with its effect id.
+fn setup_seasonal_effects(
+    builder: &Builder,
+    window: &ApplicationWindow,
+) -> Vec<(&'static str, adw::SwitchRow)> {
+    let group = extract_widget::<adw::PreferencesGroup>(builder, "seasonal_effects_group");
+
+    seasonal::effect_choices()
+        .into_iter()
+        .map(|(id, name)| {
+            let row = adw::SwitchRow::builder()
+                .title(name)
+                .active(seasonal::is_effect_enabled(id))
+                .build();
+
+            let window = window.clone();
+            row.connect_active_notify(move |row| {
+                let enabled = row.is_active();
+                info!(
+                    "Seasonal effect {} {}",
+                    id,
+                    if enabled { "enabled" } else { "disabled" }
+                );
+                seasonal::set_effect_enabled(&window, id, enabled);
+            });
+
+            group.add(&row);
+            (id, row)
+        })
+        .collect()
+}
+
+fn setup_reset_settings(
+    builder: &Builder,
+    main_builder: &Builder,
+    window: &ApplicationWindow,
+    autostart_row: adw::SwitchRow,
+    aur_helper_row: adw::ComboRow,
+    confirmation_row: adw::ComboRow,
+    effect_rows: Vec<(&'static str, adw::SwitchRow)>,
+) {
+    let button = extract_widget::<Button>(builder, "reset_settings_button");
+    let window = window.clone();
+    let effect_rows = Rc::new(effect_rows);
+    let main_builder = main_builder.clone();
+
+    button.connect_clicked(move |_| {
+        info!("Reset settings button clicked");
+
+        let autostart_row = autostart_row.clone();
+        let aur_helper_row = aur_helper_row.clone();
+        let confirmation_row = confirmation_row.clone();
+        let effect_rows = effect_rows.clone();
+        let main_builder = main_builder.clone();
+        show_warning_confirmation(
+            window.upcast_ref(),
+            "Reset Settings",
+            "All toolkit settings, including dismissed warnings and the remembered \
+             scheduler, will be restored to their defaults.\n\nContinue?",
+            move || {
+                let config = config::user::shared();
+                config.borrow_mut().reset();
+
+                // Persist right away so the reset survives a crash
+                if let Err(e) = config.borrow().save() {
+                    warn!("Failed to save config after reset: {}", e);
+                } else {
+                    info!("Configuration reset to defaults");
+                }
+
+                // Sync the controls; their handlers apply the defaults
+                let autostart = config.borrow().general.autostart;
+                autostart_row.set_active(autostart);
+                aur_helper_row.set_selected(0);
+                confirmation_row.set_selected(0);
+                for (id, row) in effect_rows.iter() {
+                    row.set_active(seasonal::is_effect_enabled(id));
+                }
+                show_toast(&main_builder, "Settings reset to defaults");
+            },
+        );
+    });
+}
@@ -0,0 +1,266 @@
+//! Global search across the tool buttons of every page.
+//!
+//! The index is built on first use by loading each page's UI resource and
+//! collecting its `btn_*` buttons, so it stays in sync with the .ui files
+//! without a separate registry.
+
+use crate::ui::context::AppContext;
+use crate::ui::navigation::PAGES;
+use crate::ui::utils::extract_widget;
+use gtk4::prelude::*;
+use gtk4::{glib, Builder, Button, Label, ListBox, Popover, SearchEntry};
+use log::info;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Maximum number of results shown in the popover.
+const MAX_RESULTS: usize = 8;
+
+/// How long a button stays highlighted after jumping to it.
+const HIGHLIGHT_DURATION: Duration = Duration::from_secs(2);
+
+/// A tool button that can be found through search.
+#[derive(Clone, Debug)]
+struct SearchItem {
+    page_id: &'static str,
+    page_title: &'static str,
+    button_id: String,
+    title: String,
+    summary: String,
+}
+
+impl SearchItem {
+    /// Rank against a lowercase query: 0 for title prefix, 1 for title match,
+    /// 2 for summary/page match, `None` if it doesn't match at all.
+    fn rank(&self, query: &str) -> Option<u8> {
+        let title = self.title.to_lowercase();
+        if title.starts_with(query) {
+            Some(0)
+        } else if title.contains(query) {
+            Some(1)
+        } else if self.summary.to_lowercase().contains(query)
+            || self.page_title.to_lowercase().contains(query)
+        {
+            Some(2)
+        } else {
+            None
+        }
+    }
+}
+
+/// Collect the tool buttons of every page.
+fn build_index() -> Vec<SearchItem> {
+    let mut items = Vec::new();
+
+    for page in PAGES {
+        let builder = Builder::from_resource(page.ui_resource);
+        for object in builder.objects() {
+            let Ok(button) = object.downcast::<Button>() else {
+                continue;
+            };
+            let Some(button_id) = button.buildable_id().filter(|id| id.starts_with("btn_")) else {
+                continue;
+            };
+
+            let summary = button
+                .tooltip_text()
+                .map(|text| text.to_string())
+                .unwrap_or_default();
+            let title = button_title(&button).unwrap_or_else(|| summary.clone());
+            if title.is_empty() {
+                continue;
+            }
+
+            items.push(SearchItem {
+                page_id: page.id,
+                page_title: page.title,
+                button_id: button_id.to_string(),
+                title,
+                summary,
+            });
+        }
+    }
+
+    info!("Global search indexed {} buttons", items.len());
+    items
+}
+
+/// Visible label of a button, whether set directly or via `AdwButtonContent`.
+fn button_title(button: &Button) -> Option<String> {
+    button
+        .label()
+        .or_else(|| {
+            button
+                .child()
+                .and_then(|child| child.downcast::<adw::ButtonContent>().ok())
+                .map(|content| content.label())
+        })
+        .map(|label| label.to_string())
+        .filter(|label| !label.is_empty())
+}
+
+/// Find the best matches for a query.
+fn search(index: &[SearchItem], query: &str) -> Vec<SearchItem> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<(u8, &SearchItem)> = index
+        .iter()
+        .filter_map(|item| item.rank(&query).map(|rank| (rank, item)))
+        .collect();
+    matches.sort_by_key(|(rank, _)| *rank);
+
+    matches
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, item)| item.clone())
+        .collect()
+}
+
+/// Wire up the header search entry and its results popover.
+pub fn setup_global_search(builder: &Builder, ctx: &AppContext) {
+    let entry: SearchEntry = extract_widget(builder, "global_search_entry");
+
+    let results_list = ListBox::new();
+    results_list.add_css_class("navigation-sidebar");
+
+    let popover = Popover::builder()
+        .child(&results_list)
+        .autohide(false)
+        .has_arrow(false)
+        .position(gtk4::PositionType::Bottom)
+        .build();
+    popover.set_parent(&entry);
+
+    let index: Rc<RefCell<Option<Vec<SearchItem>>>> = Rc::new(RefCell::new(None));
+    let results: Rc<RefCell<Vec<SearchItem>>> = Rc::new(RefCell::new(Vec::new()));
+
+    // Refresh results as the user types
+    let popover_clone = popover.clone();
+    let results_list_clone = results_list.clone();
+    let results_clone = results.clone();
+    entry.connect_search_changed(move |entry| {
+        let matches = {
+            let mut index = index.borrow_mut();
+            let index = index.get_or_insert_with(build_index);
+            search(index, &entry.text())
+        };
+
+        results_list_clone.remove_all();
+        for item in &matches {
+            results_list_clone.append(&result_row(item));
+        }
+
+        if matches.is_empty() {
+            popover_clone.popdown();
+        } else {
+            popover_clone.popup();
+        }
+        *results_clone.borrow_mut() = matches;
+    });
+
+    // Jump to the chosen result
+    let open_result = {
+        let ctx = ctx.clone();
+        let entry = entry.clone();
+        let popover = popover.clone();
+        let results = results.clone();
+        move |position: usize| {
+            let Some(item) = results.borrow().get(position).cloned() else {
+                return;
+            };
+
+            info!(
+                "Global search: jumping to '{}' on page '{}'",
+                item.button_id, item.page_id
+            );
+            popover.popdown();
+            entry.set_text("");
+            ctx.navigate_to_page(item.page_id);
+            highlight_button(&ctx, item.page_id, item.button_id);
+        }
+    };
+
+    let open_result_clone = open_result.clone();
+    results_list.connect_row_activated(move |_, row| {
+        open_result_clone(row.index().max(0) as usize);
+    });
+
+    entry.connect_activate(move |_| {
+        open_result(0);
+    });
+
+    let popover_clone = popover.clone();
+    entry.connect_stop_search(move |entry| {
+        entry.set_text("");
+        popover_clone.popdown();
+    });
+}
+
+/// Build a results row showing the button title and its page.
+fn result_row(item: &SearchItem) -> gtk4::Box {
+    let row = gtk4::Box::new(gtk4::Orientation::Vertical, 2);
+    row.set_margin_top(4);
+    row.set_margin_bottom(4);
+
+    let title = Label::new(Some(&item.title));
+    title.set_xalign(0.0);
+
+    let page = Label::new(Some(item.page_title));
+    page.set_xalign(0.0);
+    page.add_css_class("dim-label");
+    page.add_css_class("caption");
+
+    row.append(&title);
+    row.append(&page);
+    row
+}
+
+/// Focus and briefly highlight a button once its page has finished loading.
+fn highlight_button(ctx: &AppContext, page_id: &str, button_id: String) {
+    let Some(page) = ctx.ui.stack.child_by_name(page_id) else {
+        return;
+    };
+
+    // Pages are lazy-loaded, so poll until the button shows up
+    let mut attempts = 0;
+    glib::timeout_add_local(Duration::from_millis(100), move || {
+        attempts += 1;
+
+        let Some(button) = find_button(&page, &button_id) else {
+            return if attempts < 20 {
+                glib::ControlFlow::Continue
+            } else {
+                glib::ControlFlow::Break
+            };
+        };
+
+        button.grab_focus();
+        button.add_css_class("search-highlight");
+        glib::timeout_add_local_once(HIGHLIGHT_DURATION, move || {
+            button.remove_css_class("search-highlight");
+        });
+
+        glib::ControlFlow::Break
+    });
+}
+
+/// Recursively find a button by its id from the .ui file.
+fn find_button(parent: &gtk4::Widget, button_id: &str) -> Option<Button> {
+    let mut child = parent.first_child();
+    while let Some(widget) = child {
+        if let Some(button) = widget.downcast_ref::<Button>() {
+            if button.buildable_id().as_deref() == Some(button_id) {
+                return Some(button.clone());
+            }
+        }
+        if let Some(found) = find_button(&widget, button_id) {
+            return Some(found);
+        }
+        child = widget.next_sibling();
+    }
+    None
+}
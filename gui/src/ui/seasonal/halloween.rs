@@ -44,6 +44,10 @@ impl SeasonalEffect for HalloweenEffect {
         }
     }
 
+    fn id(&self) -> &'static str {
+        "halloween"
+    }
+
     fn name(&self) -> &'static str {
         "Bats (Halloween)"
     }
@@ -112,7 +116,7 @@ impl SeasonalEffect for HalloweenEffect {
 
         if add_overlay_to_window(window, &drawing_area) {
             // Register effect for lifecycle management (timer start/stop on toggle)
-            register_effect(drawing_area.clone(), timer_source);
+            register_effect(self.id(), drawing_area.clone(), timer_source);
             info!("Halloween effect overlay added successfully");
             Some(drawing_area)
         } else {
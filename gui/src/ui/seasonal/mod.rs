@@ -3,8 +3,8 @@
 //! This module provides animated overlay effects that appear during specific
 //! times of the year (e.g., snow for December, Halloween effects for October).
 //!
-//! Effects can be toggled on/off, and the animation timer is stopped when
-//! effects are disabled to save CPU/memory.
+//! Effects can be toggled on/off, globally or one by one, and the animation
+//! timer is stopped when effects are disabled to save CPU/memory.
 
 mod common;
 mod halloween;
@@ -27,6 +27,7 @@ static EFFECTS_ENABLED: AtomicBool = AtomicBool::new(true);
 
 /// Entry for a registered effect with its drawing area and timer control.
 struct EffectEntry {
+    id: &'static str,
     drawing_area: Rc<DrawingArea>,
     timer_source: Rc<RefCell<Option<glib::SourceId>>>,
 }
@@ -54,9 +55,52 @@ pub fn are_effects_enabled() -> bool {
 /// Set whether seasonal effects are enabled and update visibility/timers of drawing areas.
 pub fn set_effects_enabled(enabled: bool) {
     EFFECTS_ENABLED.store(enabled, Ordering::Relaxed);
+    refresh_effects();
+}
+
+/// Check if the user has enabled a specific effect.
+pub fn is_effect_enabled(id: &str) -> bool {
+    crate::config::user::shared()
+        .borrow()
+        .seasonal
+        .is_enabled(id)
+}
+
+/// Enable or disable a single effect, applying it first if it was never shown.
+pub fn set_effect_enabled(window: &ApplicationWindow, id: &str, enabled: bool) {
+    // Update in-memory config; actual persistence happens on app shutdown.
+    if let Some(flag) = crate::config::user::shared()
+        .borrow_mut()
+        .seasonal
+        .effect_mut(id)
+    {
+        *flag = enabled;
+    }
+
+    let applied = get_effect_registry()
+        .borrow()
+        .iter()
+        .any(|entry| entry.id == id);
+    if enabled && !applied {
+        if let Some(effect) = all_effects()
+            .into_iter()
+            .find(|effect| effect.id() == id && effect.is_active())
+        {
+            let mouse_context = common::setup_mouse_tracking(window);
+            effect.apply(window, Some(&mouse_context));
+        }
+    }
+
+    refresh_effects();
+}
+
+/// Show or hide every registered effect and start/stop its timer to match the settings.
+fn refresh_effects() {
+    let globally_enabled = are_effects_enabled();
 
     let registry = get_effect_registry();
     for entry in registry.borrow().iter() {
+        let enabled = globally_enabled && is_effect_enabled(entry.id);
         entry.drawing_area.set_visible(enabled);
 
         if enabled {
@@ -83,21 +127,40 @@ pub fn set_effects_enabled(enabled: bool) {
     }
 }
 
-/// Check if any seasonal effect is currently active.
-pub fn has_active_effect() -> bool {
-    let effects: Vec<Box<dyn SeasonalEffect>> =
-        vec![Box::new(SnowEffect), Box::new(HalloweenEffect)];
+/// Every seasonal effect the toolkit knows about.
+fn all_effects() -> Vec<Box<dyn SeasonalEffect>> {
+    vec![Box::new(SnowEffect), Box::new(HalloweenEffect)]
+}
+
+/// Get the (id, name) of every effect, for per-effect settings.
+pub fn effect_choices() -> Vec<(&'static str, &'static str)> {
+    all_effects()
+        .iter()
+        .map(|effect| (effect.id(), effect.name()))
+        .collect()
+}
 
-    effects.iter().any(|e| e.is_active())
+/// Check if any seasonal effect is in season, regardless of user settings.
+pub fn has_in_season_effect() -> bool {
+    all_effects().iter().any(|e| e.is_active())
+}
+
+/// Check if any seasonal effect is in season and enabled by the user.
+pub fn has_active_effect() -> bool {
+    all_effects()
+        .iter()
+        .any(|e| e.is_active() && is_effect_enabled(e.id()))
 }
 
 /// Register an effect with its drawing area and timer source for lifecycle management.
 pub fn register_effect(
+    id: &'static str,
     drawing_area: Rc<DrawingArea>,
     timer_source: Rc<RefCell<Option<glib::SourceId>>>,
 ) {
     let registry = get_effect_registry();
     registry.borrow_mut().push(EffectEntry {
+        id,
         drawing_area,
         timer_source,
     });
@@ -108,6 +171,9 @@ pub trait SeasonalEffect {
     /// Check if this effect should be active at the current time.
     fn is_active(&self) -> bool;
 
+    /// Get the id of this effect, as used in the user config.
+    fn id(&self) -> &'static str;
+
     /// Get the name of this seasonal effect (for logging).
     fn name(&self) -> &'static str;
 
@@ -130,12 +196,18 @@ pub fn apply_seasonal_effects(window: &ApplicationWindow) {
 
     info!("Checking for active seasonal effects...");
 
-    let mouse_context = common::setup_mouse_tracking(window);
+    if !has_active_effect() {
+        info!("No enabled seasonal effect is in season");
+        return;
+    }
 
-    let effects: Vec<Box<dyn SeasonalEffect>> =
-        vec![Box::new(SnowEffect), Box::new(HalloweenEffect)];
+    let mouse_context = common::setup_mouse_tracking(window);
 
-    for effect in effects {
+    for effect in all_effects() {
+        if !is_effect_enabled(effect.id()) {
+            info!("Seasonal effect {} is disabled by the user", effect.name());
+            continue;
+        }
         if effect.is_active() {
             info!("Active seasonal effect detected: {}", effect.name());
             if let Some(_drawing_area) = effect.apply(window, Some(&mouse_context)) {
@@ -38,6 +38,10 @@ impl SeasonalEffect for SnowEffect {
         }
     }
 
+    fn id(&self) -> &'static str {
+        "snow"
+    }
+
     fn name(&self) -> &'static str {
         "Snow (Christmas)"
     }
@@ -94,7 +98,7 @@ impl SeasonalEffect for SnowEffect {
 
         if add_overlay_to_window(window, &drawing_area) {
             // Register effect for lifecycle management (timer start/stop on toggle)
-            register_effect(drawing_area.clone(), timer_source);
+            register_effect(self.id(), drawing_area.clone(), timer_source);
             Some(drawing_area)
         } else {
             // Clean up timer if overlay failed
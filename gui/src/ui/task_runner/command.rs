@@ -3,14 +3,16 @@
 //! This module provides the core data structures for representing commands
 //! and their execution results in the task runner system.
 
+use std::time::Duration;
+
 /// Type of command to execute.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CommandType {
     /// Normal command (no special handling)
     Normal,
-    /// Command that needs privilege escalation (pkexec)
+    /// Command that needs privilege escalation (via the xero-auth daemon)
     Privileged,
-    /// AUR helper command (paru/yay)
+    /// AUR helper command (paru, yay, pikaur, trizen or aura)
     Aur,
 }
 
@@ -27,6 +29,8 @@ pub enum TaskStatus {
     Failed,
     /// Task was canceled by user
     Cancelled,
+    /// Optional task failed and was skipped
+    Skipped,
 }
 
 /// Result of command execution.
@@ -39,6 +43,11 @@ pub enum CommandResult {
         /// Exit code of the command, if available
         exit_code: Option<i32>,
     },
+    /// Command was killed after exceeding its timeout
+    TimedOut {
+        /// The timeout that was exceeded
+        after: Duration,
+    },
 }
 
 /// A command step to be executed by the task runner.
@@ -55,6 +64,22 @@ pub struct Command {
     pub args: Vec<String>,
     /// Human-readable description shown in the UI
     pub description: String,
+    /// Number of times to re-run the command if it fails
+    pub retries: u32,
+    /// Whether a failure should be skipped instead of aborting the sequence
+    pub optional: bool,
+    /// Working directory to run the program in
+    pub cwd: Option<String>,
+    /// Extra environment variables set for the program
+    pub env: Vec<(String, String)>,
+    /// Maximum time the command may run before it is killed
+    pub timeout: Option<Duration>,
+    /// Command that undoes this step if a later step is cancelled
+    pub on_cancel: Option<Box<Command>>,
+    /// Whether the command runs in an interactive terminal instead of the output log
+    pub interactive: bool,
+    /// Whether the change only takes effect after logging out and back in
+    pub requires_relogin: bool,
 }
 
 /// Builder for constructing `Command` objects with a fluent API.
@@ -93,9 +118,35 @@ pub struct CommandBuilder {
     program: Option<String>,
     args: Vec<String>,
     description: Option<String>,
+    retries: u32,
+    optional: bool,
+    cwd: Option<String>,
+    env: Vec<(String, String)>,
+    timeout: Option<Duration>,
+    on_cancel: Option<Box<Command>>,
+    interactive: bool,
+    requires_relogin: bool,
 }
 
 impl CommandBuilder {
+    /// Create a builder for the given command type.
+    fn new(command_type: CommandType) -> Self {
+        Self {
+            command_type,
+            program: None,
+            args: Vec::new(),
+            description: None,
+            retries: 0,
+            optional: false,
+            cwd: None,
+            env: Vec::new(),
+            timeout: None,
+            on_cancel: None,
+            interactive: false,
+            requires_relogin: false,
+        }
+    }
+
     /// Set the program/executable to run.
     ///
     /// For AUR commands, the program is automatically set and this is ignored.
@@ -116,6 +167,81 @@ impl CommandBuilder {
         self
     }
 
+    /// Set the working directory the program runs in.
+    ///
+    /// Prefer this over `sh -c "cd ... && ..."` so paths containing spaces or
+    /// shell metacharacters are handled correctly.
+    pub fn cwd(mut self, cwd: &str) -> Self {
+        self.cwd = Some(cwd.to_string());
+        self
+    }
+
+    /// Set an environment variable for the program.
+    ///
+    /// Can be called multiple times to set several variables.
+    pub fn env(mut self, key: &str, value: &str) -> Self {
+        self.env.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Kill the command if it runs longer than `timeout`.
+    ///
+    /// A timed out command is treated as a failure, so retries and
+    /// `optional()` still apply. Without a timeout the command may run
+    /// indefinitely.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Re-run the command up to `retries` times if it fails.
+    ///
+    /// Useful for steps prone to transient failures (mirror timeouts, network
+    /// blips). Defaults to 0, meaning a failure aborts the sequence right away.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Run `cleanup` if the sequence is cancelled after this step succeeded.
+    ///
+    /// Cleanups of all completed steps run newest first in a follow-up task,
+    /// so a cancelled install doesn't leave the system half-configured.
+    pub fn on_cancel_cleanup(mut self, cleanup: Command) -> Self {
+        self.on_cancel = Some(Box::new(cleanup));
+        self
+    }
+
+    /// Mark the command as optional.
+    ///
+    /// If an optional command fails, it is marked as skipped and the sequence
+    /// continues with the next step instead of aborting.
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Run the command in an interactive terminal window.
+    ///
+    /// For tools that prompt for input (passwords, package conflicts), which
+    /// would hang with piped output. The sequence resumes once the process
+    /// exits; closing the terminal before that counts as a failure. Timeouts
+    /// don't apply to interactive commands.
+    pub fn interactive(mut self) -> Self {
+        self.interactive = true;
+        self
+    }
+
+    /// Mark the command as only taking effect after a new login session.
+    ///
+    /// Group membership and login shell changes aren't picked up by running
+    /// sessions, so a successful sequence containing such a step reminds the
+    /// user to log out and back in.
+    pub fn requires_relogin(mut self) -> Self {
+        self.requires_relogin = true;
+        self
+    }
+
     /// Build the final `Command` object.
     ///
     /// # Panics
@@ -136,6 +262,14 @@ impl CommandBuilder {
             program,
             args: self.args,
             description,
+            retries: self.retries,
+            optional: self.optional,
+            cwd: self.cwd,
+            env: self.env,
+            timeout: self.timeout,
+            on_cancel: self.on_cancel,
+            interactive: self.interactive,
+            requires_relogin: self.requires_relogin,
         }
     }
 }
@@ -200,31 +334,16 @@ pub struct CommandBuilderType;
 impl CommandBuilderType {
     /// Create a builder for a normal command (no special handling).
     pub fn normal(self) -> CommandBuilder {
-        CommandBuilder {
-            command_type: CommandType::Normal,
-            program: None,
-            args: Vec::new(),
-            description: None,
-        }
+        CommandBuilder::new(CommandType::Normal)
     }
 
-    /// Create a builder for a privileged command (runs through pkexec).
+    /// Create a builder for a privileged command (runs through xero-auth).
     pub fn privileged(self) -> CommandBuilder {
-        CommandBuilder {
-            command_type: CommandType::Privileged,
-            program: None,
-            args: Vec::new(),
-            description: None,
-        }
+        CommandBuilder::new(CommandType::Privileged)
     }
 
-    /// Create a builder for an AUR helper command (paru/yay).
+    /// Create a builder for an AUR helper command (detected helper).
     pub fn aur(self) -> CommandBuilder {
-        CommandBuilder {
-            command_type: CommandType::Aur,
-            program: None,
-            args: Vec::new(),
-            description: None,
-        }
+        CommandBuilder::new(CommandType::Aur)
     }
 }
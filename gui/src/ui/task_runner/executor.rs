@@ -13,8 +13,10 @@ use crate::core::daemon::get_xero_auth_path;
 use gtk4::gio;
 use gtk4::glib;
 use log::{error, info, warn};
-use std::cell::RefCell;
+use regex::Regex;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::sync::OnceLock;
 use xero_auth::utils::read_buffer_with_line_processing;
 
 /// Context for a running command execution.
@@ -22,6 +24,7 @@ pub struct RunningContext {
     pub widgets: Rc<TaskRunnerWidgets>,
     pub commands: Rc<Vec<Command>>,
     pub index: usize,
+    pub attempt: u32,
     pub cancelled: Rc<RefCell<bool>>,
     pub current_process: Rc<RefCell<Option<gio::Subprocess>>>,
     exit_result: RefCell<Option<CommandResult>>,
@@ -33,6 +36,7 @@ impl RunningContext {
         widgets: Rc<TaskRunnerWidgets>,
         commands: Rc<Vec<Command>>,
         index: usize,
+        attempt: u32,
         cancelled: Rc<RefCell<bool>>,
         current_process: Rc<RefCell<Option<gio::Subprocess>>>,
     ) -> Rc<Self> {
@@ -40,6 +44,7 @@ impl RunningContext {
             widgets,
             commands,
             index,
+            attempt,
             cancelled,
             current_process,
             exit_result: RefCell::new(None),
@@ -71,7 +76,7 @@ impl RunningContext {
             // Mark the current task as canceled
             self.widgets
                 .update_task_status(self.index, TaskStatus::Cancelled);
-            finalize_execution(&self.widgets, false, super::CANCELLED_MESSAGE);
+            finalize_cancelled(&self.widgets, &self.commands);
             return;
         }
 
@@ -83,6 +88,7 @@ impl RunningContext {
 
                 self.widgets
                     .update_task_status(self.index, TaskStatus::Success);
+                self.widgets.set_step_progress(self.index, 1.0);
                 execute_commands(
                     self.widgets.clone(),
                     self.commands.clone(),
@@ -99,26 +105,92 @@ impl RunningContext {
                 };
                 self.widgets.append_colored(&exit_msg, "stderr");
 
-                self.widgets
-                    .update_task_status(self.index, TaskStatus::Failed);
-
                 // Include exit code in error message if available
-                let exit_msg = exit_code
+                let reason = exit_code
                     .map(|code| format!(" (exit code: {})", code))
                     .unwrap_or_default();
-                let final_message = format!(
-                    "Operation failed at step {} of {}{}",
-                    self.index + 1,
-                    self.commands.len(),
-                    exit_msg
-                );
+                self.handle_failure(&reason);
+            }
+            CommandResult::TimedOut { after } => {
+                let timeout_msg = format!("\n[Timed out after {}s]\n", after.as_secs());
+                self.widgets.append_colored(&timeout_msg, "error");
 
-                finalize_execution(&self.widgets, false, &final_message);
+                self.handle_failure(&format!(" (timed out after {}s)", after.as_secs()));
             }
         }
     }
+
+    /// Handle a failed step: retry it, skip it if optional, or abort the sequence.
+    ///
+    /// `reason` is appended to the final failure message.
+    fn handle_failure(self: &Rc<Self>, reason: &str) {
+        // Retry the step if it has attempts left
+        let retries = self.commands[self.index].retries;
+        if self.attempt < retries {
+            let next_attempt = self.attempt + 1;
+            let retry_msg = format!(
+                "Retrying step {} (attempt {}/{})...\n",
+                self.index + 1,
+                next_attempt + 1,
+                retries + 1
+            );
+            warn!("{}", retry_msg.trim_end());
+            self.widgets.append_colored(&retry_msg, "stderr");
+
+            let widgets = self.widgets.clone();
+            let commands = self.commands.clone();
+            let index = self.index;
+            let cancelled = self.cancelled.clone();
+            let current_process = self.current_process.clone();
+            glib::timeout_add_local_once(RETRY_BACKOFF * next_attempt, move || {
+                execute_step(
+                    widgets,
+                    commands,
+                    index,
+                    next_attempt,
+                    cancelled,
+                    current_process,
+                );
+            });
+            return;
+        }
+
+        // Optional steps are skipped rather than aborting the sequence
+        if self.commands[self.index].optional {
+            warn!("Optional step {} failed, skipping", self.index + 1);
+            self.widgets.append_colored(
+                "Optional step failed, continuing with the next step\n",
+                "stderr",
+            );
+            self.widgets
+                .update_task_status(self.index, TaskStatus::Skipped);
+            execute_commands(
+                self.widgets.clone(),
+                self.commands.clone(),
+                self.index + 1,
+                self.cancelled.clone(),
+                self.current_process.clone(),
+            );
+            return;
+        }
+
+        self.widgets
+            .update_task_status(self.index, TaskStatus::Failed);
+
+        let final_message = format!(
+            "Operation failed at step {} of {}{}",
+            self.index + 1,
+            self.commands.len(),
+            reason
+        );
+
+        finalize_execution(&self.widgets, false, &final_message);
+    }
 }
 
+/// Delay before retrying a failed step, multiplied by the attempt number.
+const RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_secs(2);
+
 /// Execute a sequence of commands.
 pub fn execute_commands(
     widgets: Rc<TaskRunnerWidgets>,
@@ -126,18 +198,37 @@ pub fn execute_commands(
     index: usize,
     cancelled: Rc<RefCell<bool>>,
     current_process: Rc<RefCell<Option<gio::Subprocess>>>,
+) {
+    execute_step(widgets, commands, index, 0, cancelled, current_process);
+}
+
+/// Execute the command at `index`, continuing with the rest of the sequence.
+///
+/// `attempt` is 0 for the first run and increases with each retry.
+fn execute_step(
+    widgets: Rc<TaskRunnerWidgets>,
+    commands: Rc<Vec<Command>>,
+    index: usize,
+    attempt: u32,
+    cancelled: Rc<RefCell<bool>>,
+    current_process: Rc<RefCell<Option<gio::Subprocess>>>,
 ) {
     if *cancelled.borrow() {
         // If there's a current task being processed, mark it as canceled
         if index < commands.len() {
             widgets.update_task_status(index, TaskStatus::Cancelled);
         }
-        finalize_execution(&widgets, false, super::CANCELLED_MESSAGE);
+        finalize_cancelled(&widgets, &commands);
         return;
     }
 
     if index >= commands.len() {
-        finalize_execution(&widgets, true, super::SUCCESS_MESSAGE);
+        let message = if commands.iter().any(|cmd| cmd.requires_relogin) {
+            super::RELOGIN_MESSAGE
+        } else {
+            super::SUCCESS_MESSAGE
+        };
+        finalize_execution(&widgets, true, message);
         return;
     }
 
@@ -145,6 +236,7 @@ pub fn execute_commands(
 
     // Mark current task as running
     widgets.update_task_status(index, TaskStatus::Running);
+    widgets.set_step_progress(index, 0.0);
     widgets.set_title(&cmd.description);
 
     let (program, args) = match resolve_command(cmd) {
@@ -166,6 +258,7 @@ pub fn execute_commands(
     info!("Executing: {} {:?}", program, args);
 
     // Use std::process for real-time output streaming
+    use std::os::unix::process::CommandExt;
     use std::process::{Command, Stdio};
     use std::sync::Arc;
     use std::thread;
@@ -175,6 +268,7 @@ pub fn execute_commands(
         widgets.clone(),
         commands.clone(),
         index,
+        attempt,
         cancelled.clone(),
         current_process.clone(),
     );
@@ -182,21 +276,34 @@ pub fn execute_commands(
     // Display command header
     widgets.append_command_header(&cmd.description);
 
+    if cmd.interactive {
+        run_interactive(context, &program, &args);
+        return;
+    }
+
     let mut process = Command::new(&program);
     process.args(&args);
 
+    if let Some(dir) = &cmd.cwd {
+        process.current_dir(dir);
+    }
+
     // Inject sudo shim to intercept sudo calls in scripts
-    let scripts_dir = crate::config::paths::scripts();
-    if scripts_dir.exists() {
-        if let Ok(path) = std::env::var("PATH") {
-            let new_path = format!("{}:{}", scripts_dir.display(), path);
-            process.env("PATH", new_path);
-        }
+    if let Some(path) = shim_path() {
+        process.env("PATH", path);
+    }
+
+    // Apply per-command environment, forwarded by xero-auth for privileged steps
+    for (key, value) in &cmd.env {
+        process.env(key, value);
     }
 
     process.stdout(Stdio::piped());
     process.stderr(Stdio::piped());
 
+    // Own process group, so a timeout can kill everything the step started
+    process.process_group(0);
+
     let child = match process.spawn() {
         Ok(child) => child,
         Err(err) => {
@@ -213,6 +320,8 @@ pub fn execute_commands(
         }
     };
 
+    let pid = child.id();
+
     // Store child process for cancellation
     use std::sync::Mutex;
     let child_arc = Arc::new(Mutex::new(Some(child)));
@@ -278,18 +387,31 @@ pub fn execute_commands(
     let widgets_stdout = widgets.clone();
     let widgets_stderr = widgets.clone();
     let result_arc_for_output = result_arc.clone();
+    // Progress within the current step, only ever moves forward
+    let step_progress = Cell::new(0.0);
+    let pacman_output = reports_pacman_progress(cmd);
+    let update_progress = move |widgets: &TaskRunnerWidgets, text: &str| {
+        if let Some(fraction) = parse_progress(text, pacman_output) {
+            if fraction > step_progress.get() {
+                step_progress.set(fraction);
+                widgets.set_step_progress(index, fraction);
+            }
+        }
+    };
     glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
         // Process stdout
         while let Ok(text) = stdout_rx.try_recv() {
             let cleaned_text = strip_ansi_escapes::strip_str(&text);
             // Text already includes newline from buffer processing
-            widgets_stdout.append_colored(&cleaned_text, "stdout");
+            widgets_stdout.append_colored(&cleaned_text, output_tag(&cleaned_text, "stdout"));
+            update_progress(&widgets_stdout, &cleaned_text);
         }
         // Process stderr
         while let Ok(text) = stderr_rx.try_recv() {
             let cleaned_text = strip_ansi_escapes::strip_str(&text);
             // Text already includes newline from buffer processing
-            widgets_stderr.append_colored(&cleaned_text, "stderr");
+            widgets_stderr.append_colored(&cleaned_text, output_tag(&cleaned_text, "stderr"));
+            update_progress(&widgets_stderr, &cleaned_text);
         }
         // Stop if result is ready
         if result_arc_for_output.lock().unwrap().is_some() {
@@ -339,19 +461,158 @@ pub fn execute_commands(
 
     // Check for result in main thread
     let context_clone = context.clone();
+    let timeout = cmd.timeout;
+    let started = std::time::Instant::now();
+    let timed_out = Cell::new(false);
     glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
         let mut result_guard = result_arc.lock().unwrap();
         if let Some(result) = result_guard.take() {
+            let result = match timeout {
+                Some(after) if timed_out.get() => CommandResult::TimedOut { after },
+                _ => result,
+            };
             context_clone.set_exit_result(result);
-            glib::ControlFlow::Break
-        } else {
-            glib::ControlFlow::Continue
+            return glib::ControlFlow::Break;
+        }
+
+        // Kill the step's process group once its timeout has elapsed. For
+        // privileged steps that is the xero-auth client; the daemon notices
+        // it disconnect and kills the process group it spawned as root.
+        if let Some(after) = timeout {
+            if !timed_out.get() && started.elapsed() >= after {
+                warn!(
+                    "Command timed out after {:?}, killing process group {}",
+                    after, pid
+                );
+                timed_out.set(true);
+                unsafe {
+                    libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+                }
+            }
         }
+
+        glib::ControlFlow::Continue
     });
 }
 
+/// Run an interactive step in a terminal window and resume once it exits.
+///
+/// The output isn't captured, so the log only notes where the step ran.
+fn run_interactive(context: Rc<RunningContext>, program: &str, args: &[String]) {
+    let cmd = &context.commands[context.index];
+
+    context.widgets.append_colored(
+        "Waiting for the command to finish in the terminal window...\n",
+        "stdout",
+    );
+
+    let mut env = Vec::new();
+    if let Some(path) = shim_path() {
+        env.push(("PATH".to_string(), path));
+    }
+    env.extend(cmd.env.iter().cloned());
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let parent = context.widgets.window.clone();
+    let title = cmd.description.clone();
+    let cwd = cmd.cwd.clone();
+
+    crate::ui::dialogs::terminal::show_terminal_dialog_with_on_exit(
+        &parent,
+        &title,
+        program,
+        &args,
+        &env,
+        cwd.as_deref(),
+        move |status| {
+            let result = match status {
+                Some(0) => CommandResult::Success,
+                exit_code => CommandResult::Failure { exit_code },
+            };
+            context.set_exit_result(result);
+        },
+    );
+}
+
+/// `PATH` with the scripts directory prepended, so scripts pick up the sudo shim.
+fn shim_path() -> Option<String> {
+    let scripts_dir = crate::config::paths::scripts();
+    if !scripts_dir.exists() {
+        return None;
+    }
+    let path = std::env::var("PATH").ok()?;
+    Some(format!("{}:{}", scripts_dir.display(), path))
+}
+
+/// Whether a step's output comes from pacman, directly or through an AUR helper.
+fn reports_pacman_progress(cmd: &Command) -> bool {
+    cmd.command_type == CommandType::Aur || cmd.program == "pacman"
+}
+
+/// Extract progress (0.0 to 1.0) from a chunk of command output.
+///
+/// Recognizes pacman's `( 3/12) installing ...` counters and percentages as
+/// printed by flatpak and most downloaders. Returns the last match found, or
+/// `None` when the output carries no progress information.
+///
+/// pacman prints a separate percentage bar per package and restarts its
+/// counter for every phase (key checks, downloads, hooks), so with `pacman`
+/// only the counter of the package operations themselves is used.
+fn parse_progress(text: &str, pacman: bool) -> Option<f64> {
+    static COUNTER: OnceLock<Regex> = OnceLock::new();
+    static PACKAGE_COUNTER: OnceLock<Regex> = OnceLock::new();
+    static PERCENT: OnceLock<Regex> = OnceLock::new();
+    let counter = if pacman {
+        PACKAGE_COUNTER.get_or_init(|| {
+            Regex::new(
+                r"\(\s*(\d+)/(\d+)\)\s+(?:installing|upgrading|reinstalling|downgrading|removing)\s",
+            )
+            .unwrap()
+        })
+    } else {
+        COUNTER.get_or_init(|| Regex::new(r"\(\s*(\d+)/(\d+)\)").unwrap())
+    };
+    let percent = PERCENT.get_or_init(|| Regex::new(r"(\d{1,3})(?:\.\d+)?\s?%").unwrap());
+
+    text.lines().rev().find_map(|line| {
+        if let Some(caps) = counter.captures(line) {
+            let current: f64 = caps[1].parse().ok()?;
+            let total: f64 = caps[2].parse().ok()?;
+            if total > 0.0 && current <= total {
+                return Some(current / total);
+            }
+        }
+        if pacman {
+            return None;
+        }
+        percent
+            .captures_iter(line)
+            .last()
+            .and_then(|caps| caps[1].parse::<f64>().ok())
+            .filter(|value| *value <= 100.0)
+            .map(|value| value / 100.0)
+    })
+}
+
+/// Pick the color tag for a line of command output.
+///
+/// Lines that look like errors (e.g. pacman's `error:` prefix) are tagged as
+/// `error` so they stand out from regular output.
+fn output_tag<'a>(line: &str, default: &'a str) -> &'a str {
+    let lower = line.trim_start().to_lowercase();
+    if lower.starts_with("error")
+        || lower.starts_with("fatal")
+        || lower.starts_with("==> error")
+        || lower.contains("failed to")
+    {
+        "error"
+    } else {
+        default
+    }
+}
+
 /// Resolve command to executable program and arguments,
-/// handling privilege escalation (pkexec) and AUR helper detection.
+/// handling privilege escalation (xero-auth) and AUR helper detection.
 ///
 /// # Returns
 ///
@@ -361,7 +622,7 @@ pub fn execute_commands(
 /// # Errors
 ///
 /// Returns an error if the AUR helper is required but not available.
-fn resolve_command(command: &Command) -> Result<(String, Vec<String>), String> {
+pub(super) fn resolve_command(command: &Command) -> Result<(String, Vec<String>), String> {
     // Prepare PATH with scripts directory for sudo shim
     let scripts_dir = crate::config::paths::scripts();
     let shim_path_env = if scripts_dir.exists() {
@@ -386,22 +647,82 @@ fn resolve_command(command: &Command) -> Result<(String, Vec<String>), String> {
                 args.push(env.clone());
             }
 
+            if let Some(dir) = &command.cwd {
+                args.push("--cwd".to_string());
+                args.push(dir.clone());
+            }
+
             args.push(command.program.clone());
             args.extend(command.args.clone());
             Ok((get_xero_auth_path().to_string_lossy().to_string(), args))
         }
         CommandType::Aur => {
-            let helper = core::aur_helper()
-                .ok_or_else(|| "AUR helper not available (paru or yay required)".to_string())?;
-            let mut args = Vec::with_capacity(command.args.len() + 2);
-            args.push("--sudo".to_string());
-            args.push(get_xero_auth_path().to_string_lossy().to_string());
-            args.extend(command.args.clone());
-            Ok((helper.to_string(), args))
+            let helper = core::aur_helper().ok_or_else(|| {
+                format!(
+                    "AUR helper not available (one of {} required)",
+                    core::aur::AUR_HELPERS.join(", ")
+                )
+            })?;
+            Ok(core::aur::build_command(
+                helper,
+                &command.args,
+                &get_xero_auth_path().to_string_lossy(),
+            ))
         }
     }
 }
 
+/// Format the fully resolved argv of a command as a copy-pasteable shell line.
+///
+/// Arguments containing whitespace or shell metacharacters are quoted, so
+/// `sh -c` scripts show up exactly as they are passed.
+pub(super) fn format_command_line(command: &Command) -> String {
+    let needs_quoting = |arg: &str| {
+        arg.is_empty()
+            || !arg
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c))
+    };
+
+    match resolve_command(command) {
+        Ok((program, args)) => std::iter::once(program)
+            .chain(args)
+            .map(|arg| {
+                if needs_quoting(&arg) {
+                    core::aur::shell_quote(&arg)
+                } else {
+                    arg
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        Err(err) => format!("<unresolved: {}>", err),
+    }
+}
+
+/// Build a header describing the task for saved logs.
+///
+/// Includes the task title, a timestamp and the resolved argv of each step
+/// so the log is self-contained.
+pub fn build_log_header(title: &str, commands: &[Command]) -> String {
+    let timestamp = glib::DateTime::now_local()
+        .and_then(|now| now.format("%Y-%m-%d %H:%M:%S"))
+        .map(|s| s.to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+
+    let mut header = format!("Task: {}\nDate: {}\n\nSteps:\n", title, timestamp);
+    for (i, cmd) in commands.iter().enumerate() {
+        header.push_str(&format!(
+            "  {}. {}\n     $ {}\n",
+            i + 1,
+            cmd.description,
+            format_command_line(cmd)
+        ));
+    }
+    header.push_str("\n--- Output ---\n");
+    header
+}
+
 /// Stop the daemon if needed.
 fn stop_daemon_if_needed() {
     let rt = tokio::runtime::Runtime::new().unwrap();
@@ -410,10 +731,59 @@ fn stop_daemon_if_needed() {
     }
 }
 
+/// Finalize a cancelled sequence.
+///
+/// Lists the steps that had already completed so the user knows what was
+/// changed, then runs their cleanup commands (newest first) as a follow-up task.
+fn finalize_cancelled(widgets: &Rc<TaskRunnerWidgets>, commands: &[Command]) {
+    let completed: Vec<usize> = (0..commands.len())
+        .filter(|&i| widgets.task_status(i) == Some(TaskStatus::Success))
+        .collect();
+
+    if completed.is_empty() {
+        widgets.append_colored("\nNo steps had completed before cancelling.\n", "stderr");
+    } else {
+        let mut summary = String::from("\nSteps completed before cancelling:\n");
+        for &i in &completed {
+            summary.push_str(&format!("  {}. {}\n", i + 1, commands[i].description));
+        }
+        warn!("{}", summary.trim());
+        widgets.append_colored(&summary, "stderr");
+    }
+
+    let cleanup: Vec<Command> = completed
+        .iter()
+        .rev()
+        .filter_map(|&i| commands[i].on_cancel.as_deref().cloned())
+        .collect();
+
+    finalize_execution(widgets, false, super::CANCELLED_MESSAGE);
+
+    if cleanup.is_empty() {
+        return;
+    }
+
+    let Some(parent) = widgets.window.transient_for() else {
+        warn!("No parent window to run cancellation cleanup from");
+        return;
+    };
+
+    info!(
+        "Running {} cleanup step(s) after cancellation",
+        cleanup.len()
+    );
+    glib::idle_add_local_once(move || {
+        let sequence = cleanup
+            .into_iter()
+            .fold(super::CommandSequence::new(), |sequence, command| {
+                sequence.then(command)
+            });
+        super::run(&parent, sequence.build(), "Cleaning Up Cancelled Task");
+    });
+}
+
 /// Finalize dialog with success or failure message.
 pub fn finalize_execution(widgets: &TaskRunnerWidgets, success: bool, message: &str) {
-    use std::sync::atomic::Ordering;
-
     // Stop daemon before finalizing
     stop_daemon_if_needed();
 
@@ -426,6 +796,91 @@ pub fn finalize_execution(widgets: &TaskRunnerWidgets, success: bool, message: &
         widgets.append_colored(&error_msg, "error");
     }
 
-    super::ACTION_RUNNING.store(false, Ordering::SeqCst);
+    // Surface the output log automatically so failures can be diagnosed
+    if !success && message != super::CANCELLED_MESSAGE {
+        widgets.reveal_output();
+    }
+
+    // Tasks may have synced or added repositories, so reload the package
+    // list aura installs are split by
+    if core::aur_helper() == Some("aura") {
+        core::aur::refresh_sync_packages();
+    }
+
+    super::set_running(false);
     widgets.show_completion(success, message);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_progress() {
+        let cases: &[(&str, Option<f64>)] = &[
+            // Counters
+            ("( 3/12) installing linux-headers", Some(0.25)),
+            (
+                "(12/12) checking keys in keyring   [######################] 100%",
+                Some(1.0),
+            ),
+            // curl --progress-bar
+            (
+                "######################                                     48.3%",
+                Some(0.48),
+            ),
+            // The last line carrying progress wins
+            (
+                "( 1/4) upgrading foo\n( 2/4) upgrading bar\nrunning hooks...",
+                Some(0.5),
+            ),
+            // curl's default meter header and lines without progress
+            (
+                "  % Total    % Received % Xferd  Average Speed   Time",
+                None,
+            ),
+            ("resolving dependencies...", None),
+            ("(0/0) nothing to do", None),
+            ("150% faster", None),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(parse_progress(text, false), *expected, "parsing {:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_parse_pacman_progress() {
+        let cases: &[(&str, Option<f64>)] = &[
+            ("( 3/12) installing linux-headers", Some(0.25)),
+            (
+                "(2/8) upgrading mesa                [######] 100%",
+                Some(0.25),
+            ),
+            ("(1/1) removing linux-zen", Some(1.0)),
+            // Per-package download bars don't describe the whole step
+            (
+                " linux-6.12.1.arch1-1-x86_64  140.3 MiB  10.2 MiB/s 00:14 [#####-------]  37%",
+                None,
+            ),
+            (
+                " Total ( 3/12)       150.0 MiB  10.2 MiB/s 00:10 [####-------]  25%",
+                None,
+            ),
+            // Other phases restart the counter
+            (
+                "(12/12) checking keys in keyring   [######################] 100%",
+                None,
+            ),
+            ("(1/5) Arming ConditionNeedsUpdate...", None),
+            (
+                "( 1/4) upgrading foo\n( 2/4) upgrading bar\n(1/3) Updating icon theme caches...",
+                Some(0.5),
+            ),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(parse_progress(text, true), *expected, "parsing {:?}", text);
+        }
+    }
+}
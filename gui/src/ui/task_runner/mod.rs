@@ -4,8 +4,9 @@
 //! - Step-by-step execution status with visual progress tracking
 //! - Output capture (stdout/stderr) for better error reporting
 //! - Cancellation support (waits for current command to finish)
-//! - Automatic privilege escalation via pkexec
-//! - AUR helper integration (paru/yay)
+//! - Automatic privilege escalation, authenticated once per sequence
+//! - AUR helper integration (paru, yay, pikaur, trizen, aura)
+//! - Interactive terminal steps for commands that prompt for input
 //!
 //! ## Usage
 //!
@@ -50,14 +51,24 @@
 //! 2. Execute each command sequentially, updating UI status as it progresses
 //! 3. Capture command output for error reporting
 //! 4. Show completion status with appropriate success/failure messages
+//!
+//! ## Privilege Escalation
+//!
+//! Privileged and AUR steps don't invoke `pkexec` individually. Instead, the
+//! xero-auth daemon is started through a single `pkexec` prompt before the
+//! first step runs, and every privileged step in the sequence is forwarded to
+//! it by the `xero-auth` client. The daemon is shut down once the sequence
+//! finishes, so the password is entered once per sequence no matter how many
+//! privileged steps it contains. Normal steps keep running unelevated.
 
 mod command;
 mod executor;
 mod widgets;
 
+use crate::config::user::ConfirmationLevel;
 use crate::ui::utils::extract_widget;
+use adw::prelude::*;
 use gtk4::glib;
-use gtk4::prelude::*;
 use gtk4::{Button, Label, Separator, ToggleButton, Window};
 use log::{error, info, warn};
 use std::cell::RefCell;
@@ -69,6 +80,9 @@ pub use command::{Command, TaskStatus};
 
 use widgets::{TaskItem, TaskRunnerWidgets};
 
+/// Callback invoked when a sequence finishes, with whether it succeeded.
+pub type OnComplete = Box<dyn FnOnce(bool)>;
+
 /// Helper for building sequences of commands with a fluent API.
 ///
 /// # Example
@@ -129,14 +143,395 @@ pub(super) const CANCELLED_MESSAGE: &str = "Operation cancelled by user";
 /// Message displayed when all operations complete successfully.
 pub(super) const SUCCESS_MESSAGE: &str = "All operations completed successfully!";
 
+/// Message displayed on success when a step only applies after logging back in.
+pub(super) const RELOGIN_MESSAGE: &str =
+    "All operations completed successfully! Log out and back in for the changes to take effect.";
+
 /// Global flag to track if an action is currently running.
 static ACTION_RUNNING: AtomicBool = AtomicBool::new(false);
 
+thread_local! {
+    /// Callbacks notified when an action starts or finishes.
+    static RUNNING_LISTENERS: RefCell<Vec<Box<dyn Fn(bool)>>> = RefCell::new(Vec::new());
+}
+
 /// Check if an action is currently running.
 pub fn is_running() -> bool {
     ACTION_RUNNING.load(Ordering::SeqCst)
 }
 
+/// Call `listener` with the new state whenever an action starts or finishes.
+pub fn connect_running_changed(listener: impl Fn(bool) + 'static) {
+    RUNNING_LISTENERS.with(|listeners| listeners.borrow_mut().push(Box::new(listener)));
+}
+
+/// Update the running flag and notify listeners if it changed.
+pub(super) fn set_running(running: bool) {
+    if ACTION_RUNNING.swap(running, Ordering::SeqCst) == running {
+        return;
+    }
+    RUNNING_LISTENERS.with(|listeners| {
+        for listener in listeners.borrow().iter() {
+            listener(running);
+        }
+    });
+}
+
+/// Tell the user why their click did nothing while another action runs.
+fn show_already_running_dialog(parent: &Window) {
+    let dialog = adw::AlertDialog::builder()
+        .heading("Operation in Progress")
+        .body(
+            "Another operation is still running. Wait for it to finish before starting a new one.",
+        )
+        .build();
+    dialog.add_response("ok", "OK");
+    dialog.set_default_response(Some("ok"));
+    dialog.set_close_response("ok");
+    dialog.present(Some(parent));
+}
+
+/// Check whether any step in the sequence runs the package manager.
+fn uses_package_manager(commands: &CommandSequence) -> bool {
+    commands.commands.iter().any(|cmd| match cmd.command_type {
+        command::CommandType::Aur => true,
+        command::CommandType::Privileged => cmd.program == "pacman",
+        command::CommandType::Normal => false,
+    })
+}
+
+/// Check whether any step runs through the AUR helper.
+fn uses_aur_helper(commands: &CommandSequence) -> bool {
+    commands
+        .commands
+        .iter()
+        .any(|cmd| cmd.command_type == command::CommandType::Aur)
+}
+
+/// Explain that an AUR helper is needed and offer to install paru.
+fn show_aur_helper_missing_dialog(parent: &Window) {
+    let dialog = adw::AlertDialog::builder()
+        .heading("AUR Helper Missing")
+        .body(&format!(
+            "This action installs packages from the AUR, which requires an AUR helper \
+             ({}).\n\n\
+             The toolkit can build and install paru using only the official repositories. \
+             Once it is installed, run this action again.",
+            crate::core::aur::AUR_HELPERS.join(", ")
+        ))
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("install", "Install paru")]);
+    dialog.set_response_appearance("install", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("install"));
+    dialog.set_close_response("cancel");
+
+    let parent_clone = parent.clone();
+    dialog.connect_response(None, move |_, response| {
+        if response == "install" {
+            crate::core::system_check::install_aur_helper(&parent_clone);
+        }
+    });
+
+    dialog.present(Some(parent));
+}
+
+/// Check whether a step runs flatpak before the sequence installs it.
+fn needs_flatpak(commands: &CommandSequence) -> bool {
+    for cmd in &commands.commands {
+        if cmd.command_type != command::CommandType::Aur && cmd.program == "flatpak" {
+            return true;
+        }
+        if is_partial_install(cmd) && cmd.args.iter().any(|arg| arg == "flatpak") {
+            return false;
+        }
+    }
+    false
+}
+
+/// Explain that flatpak is needed and offer to install it, with Flathub,
+/// before running the sequence.
+fn show_flatpak_missing_dialog(
+    parent: &Window,
+    commands: CommandSequence,
+    title: &str,
+    on_complete: Option<OnComplete>,
+) {
+    let dialog = adw::AlertDialog::builder()
+        .heading("Flatpak Missing")
+        .body(
+            "This action installs or runs a Flatpak app, but flatpak isn't installed.\n\n\
+             The toolkit can install flatpak and add the Flathub repository first, \
+             then continue with this action.",
+        )
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("install", "Install Flatpak")]);
+    dialog.set_response_appearance("install", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("install"));
+    dialog.set_close_response("cancel");
+
+    let parent_clone = parent.clone();
+    let title = title.to_string();
+    let pending = RefCell::new(Some((commands, on_complete)));
+    dialog.connect_response(Some("install"), move |_, _| {
+        let Some((mut commands, on_complete)) = pending.borrow_mut().take() else {
+            return;
+        };
+        info!("Installing flatpak before running the sequence");
+        commands
+            .commands
+            .splice(0..0, crate::core::system_check::flatpak_install_commands());
+        start(&parent_clone, commands, &title, on_complete);
+    });
+
+    dialog.present(Some(parent));
+}
+
+/// Drop `--noconfirm` from the AUR steps the user wants to review, running
+/// them in a terminal so the helper's prompts can be answered.
+fn apply_confirmation_level(commands: &mut CommandSequence) {
+    let level = crate::config::user::shared()
+        .borrow()
+        .general
+        .confirmation_level;
+
+    for cmd in commands.commands.iter_mut() {
+        if cmd.command_type != command::CommandType::Aur
+            || !cmd.args.iter().any(|arg| arg == "--noconfirm")
+        {
+            continue;
+        }
+
+        let review = match level {
+            ConfirmationLevel::Silent => false,
+            ConfirmationLevel::ConfirmConflicts => may_conflict(cmd),
+            ConfirmationLevel::AlwaysConfirm => true,
+        };
+        if review {
+            info!("Running '{}' interactively for review", cmd.description);
+            cmd.args.retain(|arg| arg != "--noconfirm");
+            cmd.interactive = true;
+        }
+    }
+}
+
+/// Check whether a step may replace or conflict with installed packages.
+///
+/// Upgrades can apply replacements from any repository, and AUR packages
+/// can't be checked offline, so both count as possible conflicts.
+fn may_conflict(cmd: &Command) -> bool {
+    if is_full_upgrade(cmd) {
+        return true;
+    }
+    if !is_partial_install(cmd) {
+        return false;
+    }
+
+    let targets: Vec<&str> = cmd.args[1..]
+        .iter()
+        .filter(|arg| !arg.starts_with('-'))
+        .map(String::as_str)
+        .collect();
+    match crate::core::package::installed_conflicts(&targets) {
+        Some(conflicts) if conflicts.is_empty() => false,
+        Some(conflicts) => {
+            info!("'{}' conflicts with {:?}", cmd.description, conflicts);
+            true
+        }
+        None => true,
+    }
+}
+
+/// Check whether a step installs packages with `-S`/`-Sy` without `-u`.
+fn is_partial_install(cmd: &Command) -> bool {
+    let runs_pacman = match cmd.command_type {
+        command::CommandType::Aur => true,
+        command::CommandType::Privileged => cmd.program == "pacman",
+        command::CommandType::Normal => false,
+    };
+
+    runs_pacman
+        && cmd.args.first().is_some_and(|op| {
+            op.strip_prefix("-S")
+                .is_some_and(|flags| flags.chars().all(|c| c == 'y'))
+        })
+}
+
+/// Check whether a step performs a full system upgrade.
+fn is_full_upgrade(cmd: &Command) -> bool {
+    cmd.command_type != command::CommandType::Normal
+        && cmd.args.first().is_some_and(|op| {
+            op.strip_prefix("-S")
+                .is_some_and(|flags| flags.contains('u'))
+        })
+}
+
+/// Check whether the sequence installs packages without upgrading the system.
+fn risks_partial_upgrade(commands: &CommandSequence) -> bool {
+    commands.commands.iter().any(is_partial_install)
+        && !commands.commands.iter().any(is_full_upgrade)
+}
+
+/// Look for pending updates in the background and warn about a partial
+/// upgrade before starting the run if there are any.
+fn check_partial_upgrade(
+    parent: &Window,
+    commands: CommandSequence,
+    title: &str,
+    on_complete: Option<OnComplete>,
+) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(crate::core::aur::pending_update_count());
+    });
+
+    let parent = parent.clone();
+    let title = title.to_string();
+    let mut pending = Some((commands, on_complete));
+    glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
+        let count = match rx.try_recv() {
+            Ok(count) => count,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return glib::ControlFlow::Continue,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
+        };
+
+        if let Some((commands, on_complete)) = pending.take() {
+            match count {
+                Some(count) if count > 0 => {
+                    warn!("{} updates pending - warning about partial upgrade", count);
+                    show_partial_upgrade_dialog(&parent, commands, &title, on_complete, count);
+                }
+                _ => launch(&parent, commands, &title, on_complete),
+            }
+        }
+        glib::ControlFlow::Break
+    });
+}
+
+/// Warn that installing with updates pending risks a partial upgrade,
+/// offering to upgrade the whole system first.
+fn show_partial_upgrade_dialog(
+    parent: &Window,
+    commands: CommandSequence,
+    title: &str,
+    on_complete: Option<OnComplete>,
+    pending_updates: usize,
+) {
+    let dialog = adw::AlertDialog::builder()
+        .heading("System Updates Pending")
+        .body(format!(
+            "{} package update(s) are available. Installing new packages on an \
+             out-of-date system can cause a partial upgrade and break installed software.\n\n\
+             Upgrading the whole system first is recommended.",
+            pending_updates
+        ))
+        .build();
+    dialog.add_responses(&[
+        ("cancel", "Cancel"),
+        ("install", "Install Anyway"),
+        ("upgrade", "Upgrade First"),
+    ]);
+    dialog.set_response_appearance("upgrade", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("upgrade"));
+    dialog.set_close_response("cancel");
+
+    let parent_clone = parent.clone();
+    let title = title.to_string();
+    let pending = RefCell::new(Some((commands, on_complete)));
+    dialog.connect_response(None, move |_, response| {
+        let Some((mut commands, on_complete)) = pending.borrow_mut().take() else {
+            return;
+        };
+        match response {
+            "upgrade" => {
+                info!("Upgrading the system before installing");
+                commands.commands.insert(
+                    0,
+                    Command::builder()
+                        .aur()
+                        .args(&["-Syu", "--noconfirm"])
+                        .description("Upgrading system packages...")
+                        .build(),
+                );
+                apply_confirmation_level(&mut commands);
+                launch(&parent_clone, commands, &title, on_complete);
+            }
+            "install" => {
+                info!("Installing without a full upgrade");
+                launch(&parent_clone, commands, &title, on_complete);
+            }
+            _ => {}
+        }
+    });
+
+    dialog.present(Some(parent));
+}
+
+/// Explain that the pacman database is locked, offering to retry the run.
+fn show_db_locked_dialog(
+    parent: &Window,
+    commands: CommandSequence,
+    title: &str,
+    on_complete: Option<OnComplete>,
+) {
+    let dialog = adw::AlertDialog::builder()
+        .heading("Package Database Locked")
+        .body(
+            "Another package operation is currently in progress. \
+             Wait for it to finish, then retry.\n\n\
+             If no other package manager is running, the lock may be stale and can be \
+             removed with \"Unlock Pacman DB\" on the Servicing page.",
+        )
+        .build();
+    dialog.add_responses(&[("cancel", "Cancel"), ("retry", "Retry")]);
+    dialog.set_response_appearance("retry", adw::ResponseAppearance::Suggested);
+    dialog.set_default_response(Some("retry"));
+    dialog.set_close_response("cancel");
+
+    let parent_clone = parent.clone();
+    let title = title.to_string();
+    let pending = RefCell::new(Some((commands, on_complete)));
+    dialog.connect_response(None, move |_, response| {
+        if response == "retry" {
+            if let Some((commands, on_complete)) = pending.borrow_mut().take() {
+                start(&parent_clone, commands, &title, on_complete);
+            }
+        }
+    });
+
+    dialog.present(Some(parent));
+}
+
+/// Ask the user for a destination and write the full task log to it.
+fn save_log(widgets: &Rc<TaskRunnerWidgets>, commands: &Rc<Vec<Command>>, title: &str) {
+    let dialog = gtk4::FileDialog::new();
+    dialog.set_initial_name(Some("xero-toolkit-task.log"));
+
+    let contents = format!(
+        "{}{}",
+        executor::build_log_header(title, commands),
+        widgets.output_text()
+    );
+    let widgets = widgets.clone();
+
+    glib::spawn_future_local(async move {
+        let Ok(file) = dialog.save_future(Some(&widgets.window)).await else {
+            // User cancelled
+            return;
+        };
+        let Some(path) = file.path() else {
+            return;
+        };
+
+        match std::fs::write(&path, contents) {
+            Ok(()) => info!("Saved task log to {}", path.display()),
+            Err(e) => {
+                error!("Failed to save task log to {}: {}", path.display(), e);
+                widgets.append_colored(&format!("\nFailed to save log: {}\n", e), "error");
+            }
+        }
+    });
+}
+
 /// Run commands with a progress dialog.
 ///
 /// Displays a modal dialog showing command execution progress with:
@@ -167,6 +562,31 @@ pub fn is_running() -> bool {
 /// run(&window, commands, "System Setup");
 /// ```
 pub fn run(parent: &Window, commands: CommandSequence, title: &str) {
+    start(parent, commands, title, None);
+}
+
+/// Run commands with a progress dialog, then call `on_complete` with whether
+/// every step succeeded.
+///
+/// The callback runs once the sequence finishes, fails or is cancelled; it is
+/// not called if the run never starts (e.g. another task is already running).
+pub fn run_with_on_complete<F>(
+    parent: &Window,
+    commands: CommandSequence,
+    title: &str,
+    on_complete: F,
+) where
+    F: FnOnce(bool) + 'static,
+{
+    start(parent, commands, title, Some(Box::new(on_complete)));
+}
+
+fn start(
+    parent: &Window,
+    mut commands: CommandSequence,
+    title: &str,
+    on_complete: Option<OnComplete>,
+) {
     if commands.is_empty() {
         error!("No commands provided");
         return;
@@ -174,10 +594,57 @@ pub fn run(parent: &Window, commands: CommandSequence, title: &str) {
 
     if is_running() {
         warn!("Action already running - ignoring request");
+        show_already_running_dialog(parent);
+        return;
+    }
+
+    apply_confirmation_level(&mut commands);
+
+    // AUR steps would fail to resolve without a helper to run them
+    if uses_aur_helper(&commands) && crate::core::aur_helper().is_none() {
+        warn!("No AUR helper available - offering to install paru");
+        show_aur_helper_missing_dialog(parent);
+        return;
+    }
+
+    // Spawning flatpak would otherwise fail with an opaque "Failed to start command"
+    if needs_flatpak(&commands) && !crate::core::is_flatpak_available() {
+        warn!("flatpak is not installed - offering to install it first");
+        show_flatpak_missing_dialog(parent, commands, title, on_complete);
+        return;
+    }
+
+    // Installs would fail partway through if another pacman instance holds the lock
+    if uses_package_manager(&commands) && crate::core::aur::is_db_locked() {
+        warn!("Pacman database is locked - asking user to retry");
+        show_db_locked_dialog(parent, commands, title, on_complete);
+        return;
+    }
+
+    // Installing with -S on an out-of-date system risks a partial upgrade
+    if risks_partial_upgrade(&commands) {
+        check_partial_upgrade(parent, commands, title, on_complete);
+        return;
+    }
+
+    launch(parent, commands, title, on_complete);
+}
+
+/// Show the progress dialog and start executing the sequence.
+fn launch(
+    parent: &Window,
+    commands: CommandSequence,
+    title: &str,
+    on_complete: Option<OnComplete>,
+) {
+    // Another run may have started while a pre-flight check was pending
+    if is_running() {
+        warn!("Action already running - ignoring request");
+        show_already_running_dialog(parent);
         return;
     }
 
-    ACTION_RUNNING.store(true, Ordering::SeqCst);
+    set_running(true);
 
     let builder = gtk4::Builder::from_resource(crate::config::resources::dialogs::TASK_LIST);
 
@@ -185,8 +652,10 @@ pub fn run(parent: &Window, commands: CommandSequence, title: &str) {
     let title_label: Label = extract_widget(&builder, "task_title");
     let task_list_container: gtk4::Box = extract_widget(&builder, "task_list_container");
     let scrolled_window: gtk4::ScrolledWindow = extract_widget(&builder, "task_scrolled_window");
+    let progress_bar: gtk4::ProgressBar = extract_widget(&builder, "task_progress_bar");
     let cancel_button: Button = extract_widget(&builder, "cancel_button");
     let close_button: Button = extract_widget(&builder, "close_button");
+    let save_log_button: Button = extract_widget(&builder, "save_log_button");
     let sidebar_toggle: ToggleButton = extract_widget(&builder, "sidebar_toggle_button");
     let sidebar_revealer: gtk4::Revealer = extract_widget(&builder, "sidebar_revealer");
     let output_text_view: gtk4::TextView = extract_widget(&builder, "output_text_view");
@@ -200,7 +669,7 @@ pub fn run(parent: &Window, commands: CommandSequence, title: &str) {
     // Create task items for each command
     let mut task_items = Vec::new();
     for (i, cmd) in commands_vec.iter().enumerate() {
-        let task_item = TaskItem::new(&cmd.description);
+        let task_item = TaskItem::new(&cmd.description, &executor::format_command_line(cmd));
         task_item.set_status(TaskStatus::Pending);
         task_list_container.append(&task_item.container);
 
@@ -219,8 +688,10 @@ pub fn run(parent: &Window, commands: CommandSequence, title: &str) {
         title_label,
         task_list_container,
         scrolled_window,
+        progress_bar,
         cancel_button.clone(),
         close_button.clone(),
+        save_log_button.clone(),
         task_items,
         sidebar_toggle,
         sidebar_revealer,
@@ -228,6 +699,8 @@ pub fn run(parent: &Window, commands: CommandSequence, title: &str) {
         output_text_buffer,
     ));
 
+    *widgets.on_complete.borrow_mut() = on_complete;
+
     // Setup sidebar toggle binding and initialize collapsed
     widgets.setup_sidebar_toggle();
     widgets.init_sidebar_collapsed();
@@ -251,10 +724,32 @@ pub fn run(parent: &Window, commands: CommandSequence, title: &str) {
         widgets_clone.window.close();
     });
 
+    // Save log button handler
+    let widgets_clone = widgets.clone();
+    let commands_clone = commands.clone();
+    let title_owned = title.to_string();
+    save_log_button.connect_clicked(move |_| {
+        save_log(&widgets_clone, &commands_clone, &title_owned);
+    });
+
+    // Keep Escape from closing (and so cancelling) the dialog mid-task. The
+    // close button only shows once the sequence has finished.
+    let escape_controller = gtk4::EventControllerKey::new();
+    escape_controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+    let close_button_clone = close_button.clone();
+    escape_controller.connect_key_pressed(move |_, key, _, _| {
+        if key == gtk4::gdk::Key::Escape && !close_button_clone.is_visible() {
+            glib::Propagation::Stop
+        } else {
+            glib::Propagation::Proceed
+        }
+    });
+    window.add_controller(escape_controller);
+
     // Window close handler
     let cancelled_clone = cancelled.clone();
     window.connect_close_request(move |_| {
-        ACTION_RUNNING.store(false, Ordering::SeqCst);
+        set_running(false);
         *cancelled_clone.borrow_mut() = true;
         glib::Propagation::Proceed
     });
@@ -269,7 +764,7 @@ pub fn run(parent: &Window, commands: CommandSequence, title: &str) {
         )
     });
 
-    // Start daemon if needed
+    // Start daemon if needed (one authentication covers the whole sequence)
     if needs_daemon {
         if let Err(e) = crate::core::daemon::start_daemon() {
             error!("Failed to start daemon: {}", e);
@@ -279,7 +774,14 @@ pub fn run(parent: &Window, commands: CommandSequence, title: &str) {
             widgets.show_completion(false, "Failed to start authentication daemon");
             return;
         }
-        info!("Daemon ready for privileged commands");
+        let privileged_steps = commands
+            .iter()
+            .filter(|cmd| cmd.command_type != command::CommandType::Normal)
+            .count();
+        info!(
+            "Daemon ready for {} privileged command(s)",
+            privileged_steps
+        );
     }
 
     // Start executing commands
@@ -4,11 +4,13 @@
 //! including task items, status icons, and scroll management.
 
 use super::command::TaskStatus;
+use super::OnComplete;
 use adw::prelude::*;
 use gtk4::{
-    Box as GtkBox, Button, Image, Label, Revealer, ScrolledWindow, TextBuffer, TextView,
-    ToggleButton, Window,
+    Box as GtkBox, Button, Expander, Image, Label, ProgressBar, Revealer, ScrolledWindow,
+    TextBuffer, TextView, ToggleButton, Window,
 };
+use std::cell::RefCell;
 
 /// Container for all task runner dialog widgets.
 pub struct TaskRunnerWidgets {
@@ -18,13 +20,17 @@ pub struct TaskRunnerWidgets {
     // Stored for potential future use, currently only used during initialization
     pub task_list_container: GtkBox,
     pub scrolled_window: ScrolledWindow,
+    pub progress_bar: ProgressBar,
     pub cancel_button: Button,
     pub close_button: Button,
+    pub save_log_button: Button,
     pub task_items: Vec<TaskItem>,
     pub sidebar_toggle: ToggleButton,
     pub sidebar_revealer: Revealer,
     pub output_text_view: TextView,
     pub output_text_buffer: TextBuffer,
+    /// Callback run once when the sequence finishes
+    pub on_complete: RefCell<Option<OnComplete>>,
 }
 
 impl TaskRunnerWidgets {
@@ -35,8 +41,10 @@ impl TaskRunnerWidgets {
         title_label: Label,
         task_list_container: GtkBox,
         scrolled_window: ScrolledWindow,
+        progress_bar: ProgressBar,
         cancel_button: Button,
         close_button: Button,
+        save_log_button: Button,
         task_items: Vec<TaskItem>,
         sidebar_toggle: ToggleButton,
         sidebar_revealer: Revealer,
@@ -48,13 +56,16 @@ impl TaskRunnerWidgets {
             title_label,
             task_list_container,
             scrolled_window,
+            progress_bar,
             cancel_button,
             close_button,
+            save_log_button,
             task_items,
             sidebar_toggle,
             sidebar_revealer,
             output_text_view,
             output_text_buffer,
+            on_complete: RefCell::new(None),
         };
 
         // Set up color tags for output
@@ -130,22 +141,41 @@ pub struct TaskItem {
     pub container: GtkBox,
     pub status_icon: Image,
     pub spinner_icon: Image,
+    status: RefCell<TaskStatus>,
 }
 
 impl TaskItem {
-    /// Create a new task item.
-    pub fn new(description: &str) -> Self {
+    /// Create a new task item, with the resolved command line in a collapsed expander.
+    pub fn new(description: &str, command_line: &str) -> Self {
         let container = GtkBox::new(gtk4::Orientation::Horizontal, 12);
         container.set_margin_top(12);
         container.set_margin_bottom(12);
         container.set_margin_start(12);
         container.set_margin_end(12);
 
+        let text_box = GtkBox::new(gtk4::Orientation::Vertical, 4);
+        text_box.set_hexpand(true);
+
         let label = Label::new(Some(description));
         label.set_xalign(0.0);
-        label.set_hexpand(true);
         label.set_wrap(true);
 
+        let command_label = Label::new(Some(command_line));
+        command_label.set_xalign(0.0);
+        command_label.set_wrap(true);
+        command_label.set_wrap_mode(gtk4::pango::WrapMode::WordChar);
+        command_label.set_selectable(true);
+        command_label.add_css_class("monospace");
+        command_label.add_css_class("dim-label");
+        command_label.add_css_class("caption");
+
+        let command_expander = Expander::new(Some("Command"));
+        command_expander.add_css_class("caption");
+        command_expander.set_child(Some(&command_label));
+
+        text_box.append(&label);
+        text_box.append(&command_expander);
+
         // Spinner icon for running state
         let spinner_icon = Image::new();
         spinner_icon.set_icon_name(Some("circle-noth-symbolic"));
@@ -158,7 +188,7 @@ impl TaskItem {
         status_icon.set_pixel_size(24);
         status_icon.set_visible(false);
 
-        container.append(&label);
+        container.append(&text_box);
         container.append(&spinner_icon);
         container.append(&status_icon);
 
@@ -166,11 +196,14 @@ impl TaskItem {
             container,
             status_icon,
             spinner_icon,
+            status: RefCell::new(TaskStatus::Pending),
         }
     }
 
     /// Update the status of this task item.
     pub fn set_status(&self, status: TaskStatus) {
+        *self.status.borrow_mut() = status.clone();
+
         match status {
             TaskStatus::Pending => {
                 self.spinner_icon.set_visible(false);
@@ -195,6 +228,12 @@ impl TaskItem {
                 self.status_icon.set_icon_name(Some("circle-stop"));
                 self.status_icon.set_visible(true);
             }
+            TaskStatus::Skipped => {
+                self.spinner_icon.set_visible(false);
+                self.status_icon
+                    .set_icon_name(Some("triangle-exclamation-symbolic"));
+                self.status_icon.set_visible(true);
+            }
         }
     }
 }
@@ -238,6 +277,23 @@ impl TaskRunnerWidgets {
         }
     }
 
+    /// Get the last status set on a specific task.
+    pub fn task_status(&self, index: usize) -> Option<TaskStatus> {
+        self.task_items
+            .get(index)
+            .map(|task_item| task_item.status.borrow().clone())
+    }
+
+    /// Update the overall progress bar.
+    ///
+    /// `step_fraction` is the progress within the step at `index` (0.0 to 1.0),
+    /// interpolated between that step's boundaries.
+    pub fn set_step_progress(&self, index: usize, step_fraction: f64) {
+        let total = self.task_items.len().max(1) as f64;
+        let fraction = (index as f64 + step_fraction.clamp(0.0, 1.0)) / total;
+        self.progress_bar.set_fraction(fraction.clamp(0.0, 1.0));
+    }
+
     /// Set the dialog title.
     pub fn set_title(&self, title: &str) {
         self.title_label.set_text(title);
@@ -255,7 +311,7 @@ impl TaskRunnerWidgets {
         self.close_button.set_sensitive(true);
     }
 
-    /// Show completion state with a final message.
+    /// Show completion state with a final message and run the on-complete callback.
     pub fn show_completion(&self, success: bool, message: &str) {
         self.set_title(message);
 
@@ -270,6 +326,10 @@ impl TaskRunnerWidgets {
         }
 
         self.enable_close();
+
+        if let Some(on_complete) = self.on_complete.borrow_mut().take() {
+            on_complete(success);
+        }
     }
 
     /// Append text with a specific color tag.
@@ -306,6 +366,19 @@ impl TaskRunnerWidgets {
             .scroll_to_iter(&mut end, 0.0, false, 0.0, 0.0);
     }
 
+    /// Get the full text of the output log.
+    pub fn output_text(&self) -> String {
+        let (start, end) = self.output_text_buffer.bounds();
+        self.output_text_buffer
+            .text(&start, &end, false)
+            .to_string()
+    }
+
+    /// Reveal the command output sidebar.
+    pub fn reveal_output(&self) {
+        self.sidebar_toggle.set_active(true);
+    }
+
     /// Initialize sidebar to collapsed state.
     pub fn init_sidebar_collapsed(&self) {
         self.sidebar_toggle.set_active(false);
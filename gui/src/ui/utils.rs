@@ -7,12 +7,27 @@ use gtk4::{Builder, StringList};
 use std::process::Command;
 
 /// Helper to extract widgets from builder with consistent error handling.
+///
+/// Panics if the widget is missing, so only use it for widgets the app can't
+/// work without. Prefer [`try_extract_widget`] for optional page controls.
 pub fn extract_widget<T: IsA<glib::Object>>(builder: &Builder, name: &str) -> T {
     builder
         .object(name)
         .unwrap_or_else(|| panic!("Failed to get widget with id '{}'", name))
 }
 
+/// Extract a widget, logging a warning instead of panicking if it's missing.
+///
+/// Lets a page skip a single control when its .ui file and code disagree,
+/// rather than taking down the whole app.
+pub fn try_extract_widget<T: IsA<glib::Object>>(builder: &Builder, name: &str) -> Option<T> {
+    let widget = builder.object(name);
+    if widget.is_none() {
+        log::warn!("Widget with id '{}' not found, skipping it", name);
+    }
+    widget
+}
+
 /// Get the selected string value from an AdwComboRow.
 pub fn get_combo_row_value(combo: &adw::ComboRow) -> Option<String> {
     let model = combo.model()?;
@@ -21,6 +36,111 @@ pub fn get_combo_row_value(combo: &adw::ComboRow) -> Option<String> {
     string_list.string(selected).map(|s| s.to_string())
 }
 
+/// Select the entry matching `value` in an AdwComboRow.
+/// Returns false if the value is not in the model.
+pub fn set_combo_row_value(combo: &adw::ComboRow, value: &str) -> bool {
+    let Some(string_list) = combo.model().and_downcast::<StringList>() else {
+        return false;
+    };
+
+    let position =
+        (0..string_list.n_items()).find(|&i| string_list.string(i).is_some_and(|s| s == value));
+
+    if let Some(position) = position {
+        combo.set_selected(position);
+    }
+    position.is_some()
+}
+
+/// Show a short, non-blocking notification over the main window content.
+///
+/// Takes the main window's builder, which every page handler receives.
+pub fn show_toast(main_builder: &Builder, message: &str) {
+    let overlay = extract_widget::<adw::ToastOverlay>(main_builder, "toast_overlay");
+    overlay.add_toast(adw::Toast::new(message));
+}
+
+/// Key under which [`set_button_spinning`] keeps a button's original state.
+const SPINNER_STATE_KEY: &str = "xero-spinner-state";
+
+/// Button state saved while it spins, restored when the spinner stops.
+struct SpinnerState {
+    sensitive: bool,
+    label: Option<glib::GString>,
+}
+
+/// Show a button as busy by spinning its icon, or stop the spinner.
+///
+/// The icon is either the button's child or the first child of its box. A
+/// spinning button is insensitive so the action can't be started twice.
+/// Its sensitivity and label are saved when the spinner starts and restored
+/// when it stops, so progress text set in between doesn't stick.
+pub fn set_button_spinning(button: &gtk4::Button, spinning: bool) {
+    if spinning {
+        // SAFETY: the key is only ever used with `SpinnerState`.
+        let saved = unsafe { button.data::<SpinnerState>(SPINNER_STATE_KEY) }.is_some();
+        if !saved {
+            let state = SpinnerState {
+                sensitive: button.is_sensitive(),
+                label: button.label(),
+            };
+            // SAFETY: as above.
+            unsafe { button.set_data(SPINNER_STATE_KEY, state) };
+        }
+        button.set_sensitive(false);
+    } else {
+        // SAFETY: as above.
+        match unsafe { button.steal_data::<SpinnerState>(SPINNER_STATE_KEY) } {
+            Some(state) => {
+                if let Some(label) = state.label {
+                    button.set_label(&label);
+                }
+                button.set_sensitive(state.sensitive);
+            }
+            None => button.set_sensitive(true),
+        }
+    }
+
+    let icon = button.child().and_then(|child| {
+        child.clone().downcast::<gtk4::Image>().ok().or_else(|| {
+            child
+                .downcast_ref::<gtk4::Box>()?
+                .first_child()
+                .and_downcast()
+        })
+    });
+    if let Some(icon) = icon {
+        if spinning {
+            icon.add_css_class("spinning");
+        } else {
+            icon.remove_css_class("spinning");
+        }
+    }
+}
+
+/// Disable every sensitive action button (`btn_*`) below `root`.
+///
+/// Returns the buttons that were disabled, so only those are re-enabled later
+/// and buttons a page disabled on purpose stay that way.
+pub fn disable_action_buttons(root: &gtk4::Widget) -> Vec<gtk4::Button> {
+    let mut disabled = Vec::new();
+    let mut child = root.first_child();
+    while let Some(widget) = child {
+        if let Some(button) = widget.downcast_ref::<gtk4::Button>() {
+            let is_action = button
+                .buildable_id()
+                .is_some_and(|id| id.starts_with("btn_"));
+            if is_action && button.is_sensitive() {
+                button.set_sensitive(false);
+                disabled.push(button.clone());
+            }
+        }
+        disabled.extend(disable_action_buttons(&widget));
+        child = widget.next_sibling();
+    }
+    disabled
+}
+
 /// Run a command and return stdout as a trimmed string.
 pub fn run_command(program: &str, args: &[&str]) -> Option<String> {
     Command::new(program)
@@ -38,7 +158,83 @@ pub fn is_service_enabled(service: &str) -> bool {
         .unwrap_or(false)
 }
 
+/// Check if a program is available in PATH.
+pub fn command_exists(program: &str) -> bool {
+    run_command("which", &[program]).is_some()
+}
+
 /// Check if a path exists.
 pub fn path_exists(path: &str) -> bool {
     std::path::Path::new(path).exists()
 }
+
+/// Describe the estimated download size of installing some packages.
+///
+/// Returns e.g. "~1.40 GB download", or None when nothing needs downloading
+/// or the size can't be determined.
+pub fn download_size_hint(packages: &[&str]) -> Option<String> {
+    match crate::core::aur::download_size(packages) {
+        Some(bytes) if bytes > 0 => Some(format!(
+            "~{} download",
+            crate::core::download::format_bytes(bytes)
+        )),
+        _ => None,
+    }
+}
+
+/// Whole number greater than zero (resolutions, refresh rates, limits).
+pub fn is_positive_integer(value: &str) -> bool {
+    value.parse::<u32>().is_ok_and(|n| n > 0)
+}
+
+/// Fraction from 0 to 1 (gamut wideness, opacity).
+pub fn is_unit_fraction(value: &str) -> bool {
+    value.parse::<f64>().is_ok_and(|n| (0.0..=1.0).contains(&n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_positive_integer() {
+        for valid in ["1", "60", "2560"] {
+            assert!(is_positive_integer(valid), "{valid:?} should be valid");
+        }
+        for invalid in ["0", "-1", "1.5", "60hz", " 60", ""] {
+            assert!(
+                !is_positive_integer(invalid),
+                "{invalid:?} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_unit_fraction() {
+        for valid in ["0", "0.5", "1", "1.0"] {
+            assert!(is_unit_fraction(valid), "{valid:?} should be valid");
+        }
+        for invalid in ["-0.1", "1.01", "NaN", "half", ""] {
+            assert!(!is_unit_fraction(invalid), "{invalid:?} should be invalid");
+        }
+    }
+
+    #[gtk4::test]
+    fn test_set_button_spinning_restores_state() {
+        let button = gtk4::Button::with_label("Clean");
+        button.set_sensitive(true);
+
+        set_button_spinning(&button, true);
+        assert!(!button.is_sensitive());
+        button.set_label("Cleaning...");
+
+        set_button_spinning(&button, false);
+        assert!(button.is_sensitive());
+        assert_eq!(button.label().as_deref(), Some("Clean"));
+
+        button.set_sensitive(false);
+        set_button_spinning(&button, true);
+        set_button_spinning(&button, false);
+        assert!(!button.is_sensitive());
+    }
+}
@@ -0,0 +1,18 @@
+#!/bin/bash
+
+# Let wheel users start CoreCtrl's helper without a password prompt
+rules="/etc/polkit-1/rules.d/90-corectrl.rules"
+
+cat > "$rules" <<'RULES'
+polkit.addRule(function(action, subject) {
+    if ((action.id == "org.corectrl.helper.init" ||
+         action.id == "org.corectrl.helperkiller.init") &&
+        subject.local == true &&
+        subject.active == true &&
+        subject.isInGroup("wheel")) {
+            return polkit.Result.YES;
+    }
+});
+RULES
+
+chmod 644 "$rules"
@@ -0,0 +1,59 @@
+#!/bin/bash
+# Make a kernel the default boot entry.
+# Usage: set_default_kernel.sh <grub|systemd-boot> <kernel package>
+set -euo pipefail
+
+bootloader="$1"
+kernel="$2"
+
+case "$bootloader" in
+  grub)
+    # Let grub-set-default pick the entry instead of a fixed menu position
+    if grep -q '^GRUB_DEFAULT=' /etc/default/grub; then
+      sed -i 's/^GRUB_DEFAULT=.*/GRUB_DEFAULT=saved/' /etc/default/grub
+    else
+      echo 'GRUB_DEFAULT=saved' >> /etc/default/grub
+    fi
+    grub-mkconfig -o /boot/grub/grub.cfg
+
+    # Entries are identified as gnulinux-advanced-<uuid>>gnulinux-<kernel>-advanced-<uuid>
+    entry=$(awk -v kernel="$kernel" '
+      match($0, /\$menuentry_id_option \047[^\047]+\047/) {
+        id = substr($0, RSTART + 22, RLENGTH - 23)
+        if ($1 == "submenu") submenu = id
+        else if ($1 == "menuentry" && index(id, "gnulinux-" kernel "-advanced-") == 1) {
+          print (submenu != "" ? submenu ">" : "") id
+          exit
+        }
+      }' /boot/grub/grub.cfg)
+
+    if [[ -z "$entry" ]]; then
+      echo "No GRUB entry found for $kernel" >&2
+      exit 1
+    fi
+    grub-set-default "$entry"
+    echo "Default GRUB entry set to $entry"
+    ;;
+  systemd-boot)
+    esp=$(bootctl --print-esp-path)
+    entry=""
+    for conf in "$esp"/loader/entries/*.conf; do
+      if grep -qE "^linux[[:space:]]+.*/vmlinuz-$kernel\$" "$conf" &&
+        ! grep -q 'fallback' "$conf"; then
+        entry=$(basename "$conf")
+        break
+      fi
+    done
+
+    if [[ -z "$entry" ]]; then
+      echo "No systemd-boot entry found for $kernel" >&2
+      exit 1
+    fi
+    bootctl set-default "$entry"
+    echo "Default systemd-boot entry set to $entry"
+    ;;
+  *)
+    echo "Unsupported bootloader: $bootloader" >&2
+    exit 1
+    ;;
+esac
@@ -14,6 +14,10 @@ struct Args {
     #[arg(short, long)]
     env: Vec<String>,
 
+    /// Working directory to run the program in (defaults to the current directory)
+    #[arg(short = 'C', long)]
+    cwd: Option<String>,
+
     /// The program to execute
     program: String,
 
@@ -41,6 +45,13 @@ async fn main() {
     env_vars.extend(args.env);
     args.env = env_vars;
 
+    // Run in the caller's directory unless one was given explicitly
+    let working_dir = args.cwd.clone().or_else(|| {
+        std::env::current_dir()
+            .ok()
+            .map(|dir| dir.to_string_lossy().to_string())
+    });
+
     let mut client = match Client::new().await {
         Ok(client) => client,
         Err(e) => {
@@ -54,7 +65,7 @@ async fn main() {
             &args.program,
             &args.args,
             args.env,
-            None,
+            working_dir.as_deref(),
             |line| print!("{}", line),
             |line| eprint!("{}", line),
         )
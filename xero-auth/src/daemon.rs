@@ -234,7 +234,7 @@ async fn handle_client(
                 env,
                 working_dir,
             } => {
-                execute_command(&writer_arc, program, args, env, working_dir).await?;
+                execute_command(&mut reader, &writer_arc, program, args, env, working_dir).await?;
             }
         }
     }
@@ -243,6 +243,7 @@ async fn handle_client(
 }
 
 async fn execute_command(
+    reader: &mut tokio::net::unix::ReadHalf<'_>,
     writer: &Arc<Mutex<tokio::net::unix::WriteHalf<'_>>>,
     program: String,
     args: Vec<String>,
@@ -278,7 +279,7 @@ async fn execute_command(
             std::process::exit(1);
         }
         Fork::Parent(pid, master) => {
-            let exit_code = read_pty_output(writer.clone(), master, pid).await?;
+            let exit_code = read_pty_output(reader, writer.clone(), master, pid).await?;
             let mut w = writer.lock().await;
             write_message(&mut *w, &DaemonMessage::Completed { exit_code }).await?;
         }
@@ -287,7 +288,14 @@ async fn execute_command(
     Ok(())
 }
 
+/// Stream the command's output to the client and wait for it to exit.
+///
+/// The child is a session leader (the PTY fork calls `setsid`), so its pid is
+/// also its process group. If the client disconnects before the command
+/// finishes, e.g. because a timed out or cancelled step killed it, the whole
+/// group is killed so nothing keeps running as root unattended.
 async fn read_pty_output(
+    reader: &mut tokio::net::unix::ReadHalf<'_>,
     writer: Arc<Mutex<tokio::net::unix::WriteHalf<'_>>>,
     master: pty::prelude::Master,
     pid: libc::pid_t,
@@ -327,9 +335,22 @@ async fn read_pty_output(
         }
     };
 
+    // The client sends nothing while a command runs, so any read ending means it went away
+    let disconnect = async {
+        while let Ok(Some(message)) = read_message::<_, ClientMessage>(reader).await {
+            warn!("Ignoring {:?} while a command runs", message);
+        }
+    };
+
     tokio::select! {
         _ = read_handle => {},
         _ = output_task => {},
+        _ = disconnect => {
+            warn!("Client disconnected, killing process group {}", pid);
+            unsafe {
+                libc::killpg(pid, libc::SIGKILL);
+            }
+        }
     }
 
     let exit_code = tokio::task::spawn_blocking(move || {